@@ -3,16 +3,32 @@ use std::collections::{HashMap, HashSet};
 use std::os::windows::process::CommandExt;
 use std::path::{Path, PathBuf};
 
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 use uuid::Uuid;
 
-use crate::git::service::{TIMEOUT_LOCAL, TIMEOUT_NETWORK, TIMEOUT_QUICK};
+use crate::confirmation::ConfirmationRequest;
+use crate::git::graph::layout_graph;
+use crate::git::oplog::OperationLogEntry;
+use crate::git::redact::redact_secrets;
+use crate::git::service::{
+    GitTimeouts, Timeout, PROTECTED_ENV_VARS, TIMEOUT_LOCAL, TIMEOUT_NETWORK, TIMEOUT_QUICK,
+};
 use crate::git::{
+    is_auth_failure, is_missing_identity, CheckoutCommitResult, CommandError, CommandResult,
     ConflictFile, DiagnosticInfo, FullRebaseStatus, GitCommandResult, GitCommandType, GitError,
-    GitResponse, GitResult, RebaseStepInfo, RebaseTodoItem,
+    GitResponse, GitResult, LfsPointerInfo, MergeCheckResult, MergeDryRunResult, MergeRegion,
+    RebaseStepInfo, RebaseTodoItem, ResetDryRunResult, ResolveConflictResult,
+    MISSING_IDENTITY_MESSAGE,
+};
+use crate::models::{
+    BranchInfo, BranchSort, CommitCount, CommitDiff, CommitParents, CommitRefs, CommitSignature,
+    Contributor, CurrentBranch, DiffFile, DiffHunk, DiffLine, DiffLineType, DirectoryEntry,
+    FileCommit, FileContent, GitObject, GraphCommit, GraphRow, HeadInfo, TreeNode, UpdateCheck,
+};
+use crate::secrets;
+use crate::settings::{
+    parse_settings, save_settings, AppSettings, AppState, DateFormat, RepoEntry,
 };
-use crate::models::{CommitDiff, DiffFile, DiffHunk, DiffLine, DiffLineType, FileCommit};
-use crate::settings::{save_settings, AppSettings, AppState, RepoEntry};
 use glob::Pattern;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -20,13 +36,18 @@ use serde_json::json;
 use tauri::Emitter;
 
 mod ai_commands;
+mod config_commands;
 mod conflict_commands;
 mod diff_commands;
+mod hook_commands;
+mod lfs_commands;
+mod maintenance_commands;
 mod rebase_commands;
 mod settings_commands;
+mod sparse_commands;
 mod terminal_commands;
 
-pub use diff_commands::StageLineSelection;
+pub use diff_commands::{DiffContext, DiffDoneEvent, StageLineSelection};
 
 // ---------------------------------------------------------------------------
 // Helpers
@@ -47,6 +68,35 @@ fn resolve_repo_path(
     get_active_repo_path(state)
 }
 
+/// The repository's git directory. In a linked worktree (or a submodule)
+/// `.git` is a file containing `gitdir: <path>`, possibly relative to the
+/// working tree, instead of the directory itself.
+fn resolve_git_dir(repo_path: &Path) -> PathBuf {
+    let dot_git = repo_path.join(".git");
+    if dot_git.is_file() {
+        let target = std::fs::read_to_string(&dot_git).ok().and_then(|content| {
+            content
+                .lines()
+                .find_map(|line| line.strip_prefix("gitdir:"))
+                .map(|target| target.trim().to_string())
+        });
+        if let Some(target) = target {
+            return repo_path.join(target);
+        }
+    }
+    dot_git
+}
+
+/// The git directory shared by all worktrees, where hooks and `info/` live.
+/// A linked worktree's git dir names it in its `commondir` file.
+fn resolve_common_git_dir(repo_path: &Path) -> PathBuf {
+    let git_dir = resolve_git_dir(repo_path);
+    match std::fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common) => git_dir.join(common.trim()),
+        Err(_) => git_dir,
+    }
+}
+
 fn get_active_repo_path(state: &State<AppState>) -> Result<String, String> {
     let settings = state.settings.lock().map_err(|e| e.to_string())?;
     let active_id = settings
@@ -66,7 +116,7 @@ async fn git_run(
     state: &State<'_, AppState>,
     repo_path: Option<String>,
     args: &[&str],
-    timeout: u64,
+    timeout: Timeout,
 ) -> Result<GitResponse, String> {
     let path = resolve_repo_path(state, repo_path)?;
     let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
@@ -77,26 +127,107 @@ async fn git_run(
         .map_err(|e| e.to_string())
 }
 
+/// Like `git_run`, but keeps the `GitError` variant for commands that return
+/// a structured `CommandError`.
+async fn git_run_typed(
+    state: &State<'_, AppState>,
+    repo_path: Option<String>,
+    args: Vec<String>,
+    timeout: Timeout,
+) -> CommandResult<GitResponse> {
+    let path = resolve_repo_path(state, repo_path)?;
+    Ok(state.git.run(Path::new(&path), &args, timeout).await?)
+}
+
 fn emit_git_change_event(app: &AppHandle) -> Result<(), String> {
     app.emit("git-event", json!({ "type": "change" }))
         .map_err(|e| e.to_string())
 }
 
+/// Temp files (or directories) removed when the guard is dropped, so they are
+/// cleaned up on early returns, panics and cancelled commands alike.
+#[derive(Default)]
+struct TempFileGuard {
+    paths: Vec<PathBuf>,
+}
+
+impl TempFileGuard {
+    /// Register `path` for removal and hand it back. Register before writing,
+    /// so a partially written file is removed too.
+    fn track(&mut self, path: PathBuf) -> PathBuf {
+        self.paths.push(path.clone());
+        path
+    }
+}
+
+impl Drop for TempFileGuard {
+    fn drop(&mut self) {
+        for path in &self.paths {
+            if path.is_dir() {
+                let _ = std::fs::remove_dir_all(path);
+            } else {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+}
+
 async fn git_run_vec(
     state: &State<'_, AppState>,
     repo_path: Option<String>,
     args: Vec<String>,
-    timeout: u64,
+    timeout: Timeout,
 ) -> Result<GitResponse, String> {
     let path = resolve_repo_path(state, repo_path)?;
     git_run_vec_at_path(state, &path, args, timeout).await
 }
 
+/// Cap on `log` output so a pathological history (or a missing `--max-count`)
+/// cannot flood the IPC channel; longer output is cut at a line boundary.
+const MAX_LOG_OUTPUT_BYTES: usize = 32 * 1024 * 1024;
+
+/// Run a command whose stdout carries commit metadata (`log`, `rev-list
+/// --format`) and decode it with the repository's `i18n.logOutputEncoding`,
+/// falling back to UTF-8, so non-UTF-8 subjects and authors aren't garbled.
+async fn git_run_log(
+    state: &State<'_, AppState>,
+    repo_path: Option<String>,
+    args: Vec<String>,
+    timeout: Timeout,
+) -> CommandResult<String> {
+    let path = resolve_repo_path(state, repo_path)?;
+    let repo = Path::new(&path);
+
+    let config_args: Vec<String> = vec![
+        "config".into(),
+        "--get".into(),
+        "i18n.logOutputEncoding".into(),
+    ];
+    // Exits 1 when the key is unset
+    let log_encoding = state
+        .git
+        .run_unchecked(repo, &config_args, TIMEOUT_QUICK)
+        .await
+        .ok()
+        .filter(|resp| resp.exit_code == 0)
+        .map(|resp| resp.stdout.trim().to_string())
+        .filter(|encoding| !encoding.is_empty());
+
+    let resp = state
+        .git
+        .run_with_output_bytes_capped(repo, &args, timeout, Some(MAX_LOG_OUTPUT_BYTES))
+        .await?;
+    Ok(crate::git::encoding::decode_output(
+        &resp.stdout,
+        log_encoding.as_deref(),
+    ))
+}
+
 async fn git_run_vec_at_path(
     state: &State<'_, AppState>,
     repo_path: &str,
     args: Vec<String>,
-    timeout: u64,
+    timeout: Timeout,
 ) -> Result<GitResponse, String> {
     state
         .git
@@ -110,7 +241,7 @@ async fn git_run_result_with_event(
     state: &State<'_, AppState>,
     repo_path: Option<String>,
     args: Vec<String>,
-    timeout: u64,
+    timeout: Timeout,
     command_type: GitCommandType,
 ) -> Result<GitCommandResult, String> {
     let resp = git_run_vec(state, repo_path, args, timeout).await?;
@@ -123,7 +254,7 @@ async fn git_run_result_at_path_with_event(
     state: &State<'_, AppState>,
     repo_path: &str,
     args: Vec<String>,
-    timeout: u64,
+    timeout: Timeout,
     command_type: GitCommandType,
 ) -> Result<GitCommandResult, String> {
     let resp = git_run_vec_at_path(state, repo_path, args, timeout).await?;
@@ -136,7 +267,7 @@ async fn git_run_void_with_event(
     state: &State<'_, AppState>,
     repo_path: Option<String>,
     args: Vec<String>,
-    timeout: u64,
+    timeout: Timeout,
 ) -> Result<(), String> {
     git_run_vec(state, repo_path, args, timeout).await?;
     emit_git_change_event(app)?;
@@ -153,6 +284,46 @@ fn map_git_result(resp: GitResponse, command_type: GitCommandType) -> GitCommand
     }
 }
 
+/// Map the outcome of a network git command (push/pull/fetch).
+///
+/// Because the executor runs with `GIT_TERMINAL_PROMPT=0`, missing credentials
+/// always surface as a hard failure. Those are turned into an unsuccessful
+/// `GitCommandResult` with a friendly explanation, keeping git's original
+/// message underneath for debugging. Any other error is passed through.
+fn map_network_result(
+    result: GitResult<GitResponse>,
+    command_type: GitCommandType,
+) -> CommandResult<GitCommandResult> {
+    // Remote URLs and credential helpers can echo tokens into stderr
+    match result {
+        Ok(resp) => {
+            let mut mapped = map_git_result(resp, command_type);
+            mapped.stderr = redact_secrets(&mapped.stderr);
+            Ok(mapped)
+        }
+        Err(GitError::CommandError(msg)) if is_auth_failure(&msg) => Ok(GitCommandResult {
+            success: false,
+            stdout: String::new(),
+            stderr: format!(
+                "Authentication failed: no usable credentials are configured for this remote.\n\
+                 Git runs non-interactively here, so it cannot prompt for a username or password. \
+                 Configure a credential helper (e.g. Git Credential Manager) or an SSH key, \
+                 or sign in once from a terminal. If you use Git Credential Manager, note that \
+                 GCM_INTERACTIVE is set to \"never\" for app-initiated commands.\n\n\
+                 Original error:\n{}",
+                redact_secrets(msg.trim())
+            ),
+            // git exits with 128 on fatal errors such as authentication failures
+            exit_code: 128,
+            command_type,
+        }),
+        Err(GitError::CommandError(msg)) => {
+            Err(GitError::CommandError(redact_secrets(&msg)).into())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
 #[cfg(target_os = "windows")]
 fn hide_console_window(cmd: &mut std::process::Command) {
     const CREATE_NO_WINDOW: u32 = 0x0800_0000;
@@ -162,6 +333,9 @@ fn hide_console_window(cmd: &mut std::process::Command) {
 #[cfg(not(target_os = "windows"))]
 fn hide_console_window(_cmd: &mut std::process::Command) {}
 
+/// Match `path` against the exclusion patterns gitignore-style: patterns are
+/// evaluated in order and the last one that matches wins, so a later
+/// `!pattern` re-includes a path excluded by an earlier pattern.
 fn is_excluded(path: &str, exclusions: &[String]) -> bool {
     if exclusions.is_empty() {
         return false;
@@ -170,19 +344,94 @@ fn is_excluded(path: &str, exclusions: &[String]) -> bool {
     // Normalize path to use forward slashes for glob matching
     let normalized_path = path.replace('\\', "/");
 
+    let mut excluded = false;
     for pattern_str in exclusions {
         let pattern_str = pattern_str.trim();
+        let (negated, pattern_str) = match pattern_str.strip_prefix('!') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, pattern_str),
+        };
         if pattern_str.is_empty() {
             continue;
         }
 
-        if let Ok(pattern) = Pattern::new(pattern_str) {
-            if pattern.matches(&normalized_path) {
-                return true;
-            }
+        if exclusion_matches(pattern_str, &normalized_path) {
+            excluded = !negated;
+        }
+    }
+    excluded
+}
+
+/// Whether one exclusion pattern covers `path`. As in gitignore, a pattern
+/// that matches a directory covers everything under it, a trailing `/` only
+/// matches directories, and a pattern without a `/` (other than a trailing
+/// one) matches a file or directory name at any depth. A leading `/` anchors
+/// the pattern to the repository root.
+fn exclusion_matches(pattern_str: &str, path: &str) -> bool {
+    let (pattern_str, dir_only) = match pattern_str.strip_suffix('/') {
+        Some(rest) => (rest, true),
+        None => (pattern_str, false),
+    };
+    let anchored = pattern_str.contains('/');
+    let Ok(pattern) = Pattern::new(pattern_str.trim_start_matches('/')) else {
+        return false;
+    };
+
+    // Git reports untracked directories with a trailing slash
+    let (path, path_is_dir) = match path.strip_suffix('/') {
+        Some(rest) => (rest, true),
+        None => (path, false),
+    };
+
+    // Every leading directory of the path, then the path itself
+    let mut candidates = path
+        .match_indices('/')
+        .map(|(i, _)| (&path[..i], true))
+        .chain(std::iter::once((path, path_is_dir)));
+    candidates.any(|(candidate, is_dir)| {
+        if dir_only && !is_dir {
+            return false;
         }
+        if pattern.matches(candidate) {
+            return true;
+        }
+        let name = candidate.rsplit('/').next().unwrap_or(candidate);
+        !anchored && pattern.matches(name)
+    })
+}
+
+/// When safe mode is enabled, require a valid confirmation token bound to
+/// exactly this destructive `operation` and `paths`.
+fn check_destructive_confirmation(
+    state: &State<'_, AppState>,
+    operation: &str,
+    paths: &[String],
+    confirmation_token: Option<&str>,
+) -> Result<(), String> {
+    check_destructive_confirmations(
+        state,
+        &[ConfirmationRequest {
+            token: confirmation_token,
+            operation,
+            paths,
+        }],
+    )
+}
+
+/// `check_destructive_confirmation` for several operations run together. No
+/// token is consumed unless all of them are valid.
+fn check_destructive_confirmations(
+    state: &State<'_, AppState>,
+    requests: &[ConfirmationRequest<'_>],
+) -> Result<(), String> {
+    let required = {
+        let settings = state.settings.lock().map_err(|e| e.to_string())?;
+        settings.require_confirmation
+    };
+    if !required {
+        return Ok(());
     }
-    false
+    state.confirmations.consume_all(requests)
 }
 
 fn split_rename_path(path: &str) -> Option<(String, String)> {
@@ -207,6 +456,15 @@ fn resolve_file_target_path(raw_path: &str) -> String {
     }
 }
 
+/// `stash@{N}` with a non-negative index; anything else could be an option
+/// or an arbitrary revision.
+fn is_valid_stash_ref(stash_ref: &str) -> bool {
+    stash_ref
+        .strip_prefix("stash@{")
+        .and_then(|rest| rest.strip_suffix('}'))
+        .is_some_and(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
+}
+
 async fn resolve_stash_ref_by_commit_hash(
     state: &State<'_, AppState>,
     repo_path: &str,
@@ -321,6 +579,16 @@ pub fn cmd_add_repo(
     settings_commands::cmd_add_repo_impl(app_handle, state, name, path)
 }
 
+#[tauri::command]
+pub async fn cmd_git_init(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+    initial_branch: Option<String>,
+) -> Result<AppSettings, String> {
+    settings_commands::cmd_git_init_impl(app_handle, state, path, initial_branch).await
+}
+
 #[tauri::command]
 pub fn cmd_remove_repo(
     app_handle: AppHandle,
@@ -371,6 +639,16 @@ pub fn cmd_set_excluded_files(
     settings_commands::cmd_set_excluded_files_impl(app_handle, state, exclusions)
 }
 
+#[tauri::command]
+pub fn cmd_set_repo_excluded_files(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    repo_id: String,
+    exclusions: Vec<String>,
+) -> Result<AppSettings, String> {
+    settings_commands::cmd_set_repo_excluded_files_impl(app_handle, state, repo_id, exclusions)
+}
+
 #[tauri::command]
 pub fn cmd_set_repo_filter(
     app_handle: AppHandle,
@@ -381,6 +659,69 @@ pub fn cmd_set_repo_filter(
     settings_commands::cmd_set_repo_filter_impl(app_handle, state, repo_id, filter)
 }
 
+#[tauri::command]
+pub fn cmd_set_repo_env(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    repo_id: String,
+    env: HashMap<String, String>,
+) -> Result<AppSettings, String> {
+    settings_commands::cmd_set_repo_env_impl(app_handle, state, repo_id, env)
+}
+
+#[tauri::command]
+pub fn cmd_set_require_confirmation(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    enabled: bool,
+) -> Result<AppSettings, String> {
+    settings_commands::cmd_set_require_confirmation_impl(app_handle, state, enabled)
+}
+
+#[tauri::command]
+pub fn cmd_set_timeouts(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    timeouts: GitTimeouts,
+) -> Result<AppSettings, String> {
+    settings_commands::cmd_set_timeouts_impl(app_handle, state, timeouts)
+}
+
+#[tauri::command]
+pub fn cmd_set_date_format(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    date_format: Option<DateFormat>,
+) -> Result<AppSettings, String> {
+    settings_commands::cmd_set_date_format_impl(app_handle, state, date_format)
+}
+
+/// `None` restores the default limit.
+#[tauri::command]
+pub fn cmd_set_max_file_view_bytes(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    max_bytes: Option<u64>,
+) -> Result<AppSettings, String> {
+    settings_commands::cmd_set_max_file_view_bytes_impl(app_handle, state, max_bytes)
+}
+
+/// Write the settings (without the Gemini token) to a JSON file at `path`.
+#[tauri::command]
+pub fn cmd_export_settings(state: State<AppState>, path: String) -> Result<(), String> {
+    settings_commands::cmd_export_settings_impl(state, path)
+}
+
+/// Merge a file written by `cmd_export_settings` into the current settings.
+#[tauri::command]
+pub fn cmd_import_settings(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    path: String,
+) -> Result<settings_commands::SettingsImport, String> {
+    settings_commands::cmd_import_settings_impl(app_handle, state, path)
+}
+
 #[tauri::command]
 pub fn cmd_set_gemini_api_token(
     app_handle: AppHandle,
@@ -457,6 +798,15 @@ pub async fn cmd_diagnostics(state: State<'_, AppState>) -> Result<DiagnosticInf
     Ok(state.git.diagnostics().await)
 }
 
+/// Most recent git invocations first, for the debug panel.
+#[tauri::command]
+pub fn cmd_get_operation_log(
+    state: State<AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<OperationLogEntry>, String> {
+    Ok(state.operation_log.recent(limit))
+}
+
 // ---------------------------------------------------------------------------
 // Git Commands (all async)
 // ---------------------------------------------------------------------------
@@ -465,8 +815,8 @@ pub async fn cmd_diagnostics(state: State<'_, AppState>) -> Result<DiagnosticInf
 pub async fn cmd_git_status(
     state: State<'_, AppState>,
     repo_path: Option<String>,
-) -> Result<String, String> {
-    let resp = git_run(&state, repo_path, &["status"], TIMEOUT_LOCAL).await?;
+) -> CommandResult<String> {
+    let resp = git_run_typed(&state, repo_path, vec!["status".into()], TIMEOUT_LOCAL).await?;
     Ok(resp.stdout)
 }
 
@@ -475,10 +825,15 @@ pub async fn cmd_git_pull(
     app: AppHandle,
     state: State<'_, AppState>,
     repo_path: Option<String>,
-) -> Result<GitCommandResult, String> {
-    let resp = git_run(&state, repo_path, &["pull"], TIMEOUT_NETWORK).await?;
+) -> CommandResult<GitCommandResult> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let result = state
+        .git
+        .run(Path::new(&path), &["pull".to_string()], TIMEOUT_NETWORK)
+        .await;
+    let mapped = map_network_result(result, GitCommandType::Pull)?;
     emit_git_change_event(&app)?;
-    Ok(map_git_result(resp, GitCommandType::Pull))
+    Ok(mapped)
 }
 
 #[tauri::command]
@@ -486,7 +841,7 @@ pub async fn cmd_git_push(
     app: AppHandle,
     state: State<'_, AppState>,
     repo_path: Option<String>,
-) -> Result<GitCommandResult, String> {
+) -> CommandResult<GitCommandResult> {
     let path = resolve_repo_path(&state, repo_path)?;
 
     // Check if the current branch has an upstream configured
@@ -509,13 +864,12 @@ pub async fn cmd_git_push(
         .map(|r| r.exit_code == 0)
         .unwrap_or(false);
 
-    let resp = if has_upstream {
+    let result = if has_upstream {
         // Normal push — upstream already set
         state
             .git
             .run(Path::new(&path), &["push".to_string()], TIMEOUT_NETWORK)
             .await
-            .map_err(|e| e.to_string())?
     } else {
         // Get current branch name for -u push
         let branch_resp = state
@@ -529,15 +883,14 @@ pub async fn cmd_git_push(
                 ],
                 TIMEOUT_LOCAL,
             )
-            .await
-            .map_err(|e| e.to_string())?;
+            .await?;
         let branch = branch_resp.stdout.trim().to_string();
 
         if branch.is_empty() || branch == "HEAD" {
-            return Err(
+            return Err(CommandError::InvalidInput(
                 "Cannot push: you are in a detached HEAD state. Please checkout a branch first."
                     .to_string(),
-            );
+            ));
         }
 
         state
@@ -553,20 +906,24 @@ pub async fn cmd_git_push(
                 TIMEOUT_NETWORK,
             )
             .await
-            .map_err(|e| e.to_string())?
     };
 
+    let mapped = map_network_result(result, GitCommandType::Push)?;
     emit_git_change_event(&app)?;
-    Ok(map_git_result(resp, GitCommandType::Push))
+    Ok(mapped)
 }
 
 #[tauri::command]
 pub async fn cmd_git_fetch(
     state: State<'_, AppState>,
     repo_path: Option<String>,
-) -> Result<GitCommandResult, String> {
-    let resp = git_run(&state, repo_path, &["fetch"], TIMEOUT_NETWORK).await?;
-    Ok(map_git_result(resp, GitCommandType::Fetch))
+) -> CommandResult<GitCommandResult> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let result = state
+        .git
+        .run(Path::new(&path), &["fetch".to_string()], TIMEOUT_NETWORK)
+        .await;
+    map_network_result(result, GitCommandType::Fetch)
 }
 
 #[tauri::command]
@@ -574,48 +931,215 @@ pub async fn cmd_git_commit(
     app: AppHandle,
     state: State<'_, AppState>,
     message: String,
+    run_hooks: Option<bool>,
     repo_path: Option<String>,
-) -> Result<GitCommandResult, String> {
+) -> CommandResult<GitCommandResult> {
     let path = resolve_repo_path(&state, repo_path)?;
+    unstage_excluded_files(&state, &path).await?;
 
-    // Safety: unstage any excluded files before committing so they are never
-    // included, even if staged externally (CLI, IDE, etc.)
-    let exclusions = {
-        let settings = state.settings.lock().map_err(|e| e.to_string())?;
-        settings.excluded_files.clone()
-    };
-
-    if !exclusions.is_empty() {
-        let diff_args: Vec<String> = vec!["diff".into(), "--cached".into(), "--name-only".into()];
-        let diff_resp = state
+    let mut args: Vec<String> = vec!["commit".into(), "-m".into(), message];
+    let resp = if run_hooks.unwrap_or(true) {
+        // Stream hook output (lint/test runs) live. stdin is closed and the
+        // usual timeout applies, so a hook waiting for input cannot hang us.
+        let emitter = app.clone();
+        state
+            .git
+            .run_streaming(
+                Path::new(&path),
+                &args,
+                Vec::new(),
+                TIMEOUT_LOCAL,
+                move |stream, line| {
+                    let _ = emitter.emit("hook-output", json!({ "stream": stream, "line": line }));
+                },
+            )
+            .await
+            .map_err(map_commit_error)?
+    } else {
+        args.push("--no-verify".into());
+        state
             .git
-            .run(Path::new(&path), &diff_args, TIMEOUT_QUICK)
+            .run(Path::new(&path), &args, TIMEOUT_LOCAL)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(map_commit_error)?
+    };
+    emit_git_change_event(&app)?;
+    Ok(map_git_result(resp, GitCommandType::Commit))
+}
 
-        for file in diff_resp.stdout.lines() {
-            let file = file.trim();
-            if !file.is_empty() && is_excluded(file, &exclusions) {
-                let unstage_args: Vec<String> =
-                    vec!["restore".into(), "--staged".into(), file.to_string()];
-                let _ = state
-                    .git
-                    .run(Path::new(&path), &unstage_args, TIMEOUT_QUICK)
-                    .await;
-            }
+/// First-time users commit before configuring an identity and get git's
+/// multi-line "Please tell me who you are" help; replace it with a specific
+/// error the UI can act on.
+fn map_commit_error(err: GitError) -> CommandError {
+    match err {
+        GitError::CommandError(msg) if is_missing_identity(&msg) => {
+            CommandError::MissingIdentity(MISSING_IDENTITY_MESSAGE.to_string())
         }
+        other => other.into(),
     }
+}
 
-    let args: Vec<String> = vec!["commit".into(), "-m".into(), message];
-    let resp = state
+/// Safety: unstage any excluded files before committing so they are never
+/// included, even if staged externally (CLI, IDE, etc.)
+async fn unstage_excluded_files(state: &State<'_, AppState>, path: &str) -> CommandResult<()> {
+    let exclusions = load_exclusion_patterns(state, path)?;
+    if exclusions.is_empty() {
+        return Ok(());
+    }
+
+    let diff_args: Vec<String> = vec!["diff".into(), "--cached".into(), "--name-only".into()];
+    let diff_resp = state
         .git
-        .run(Path::new(&path), &args, TIMEOUT_LOCAL)
+        .run(Path::new(path), &diff_args, TIMEOUT_QUICK)
+        .await?;
+
+    let supports_restore = state.git.supports_restore().await;
+    for file in diff_resp.stdout.lines() {
+        let file = file.trim();
+        if !file.is_empty() && is_excluded(file, &exclusions) {
+            let unstage_args = build_unstage_args(&[file.to_string()], supports_restore);
+            let _ = state
+                .git
+                .run(Path::new(path), &unstage_args, TIMEOUT_QUICK)
+                .await;
+        }
+    }
+    Ok(())
+}
+
+/// Parse `rev-list --left-right --count @{u}...HEAD`, which prints
+/// "<behind>\t<ahead>".
+fn parse_ahead_behind(output: &str) -> Option<(u32, u32)> {
+    let mut counts = output.split_whitespace().map(|n| n.parse::<u32>().ok());
+    Some((counts.next()??, counts.next()??))
+}
+
+#[tauri::command]
+pub async fn cmd_git_amend_staged(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> CommandResult<GitCommandResult> {
+    let path = resolve_repo_path(&state, repo_path)?;
+
+    // Refuse to rewrite a commit that is already on the upstream branch.
+    // ahead == 0 means HEAD is reachable from upstream, i.e. it has been pushed.
+    let ahead_behind_args: Vec<String> = vec![
+        "rev-list".into(),
+        "--left-right".into(),
+        "--count".into(),
+        "@{u}...HEAD".into(),
+    ];
+    if let Ok(resp) = state
+        .git
+        .run(Path::new(&path), &ahead_behind_args, TIMEOUT_QUICK)
         .await
-        .map_err(|e| e.to_string())?;
+    {
+        let ahead = parse_ahead_behind(&resp.stdout).map(|(_, ahead)| ahead);
+        if ahead == Some(0) {
+            return Err(CommandError::InvalidInput(
+                "The last commit has already been pushed. Amending it would rewrite public history."
+                    .to_string(),
+            ));
+        }
+    }
+
+    unstage_excluded_files(&state, &path).await?;
+
+    let args: Vec<String> = vec!["commit".into(), "--amend".into(), "--no-edit".into()];
+    let envs = vec![("GIT_EDITOR".to_string(), "true".to_string())];
+    let resp = state
+        .git
+        .run_with_env(Path::new(&path), &args, envs, TIMEOUT_LOCAL)
+        .await?;
     emit_git_change_event(&app)?;
     Ok(map_git_result(resp, GitCommandType::Commit))
 }
 
+/// The team's commit template: `commit.template` if configured, otherwise a
+/// `.gitmessage` at the repository root. `None` when neither exists.
+#[tauri::command]
+pub async fn cmd_get_commit_template(
+    state: State<'_, AppState>,
+    include_comments: Option<bool>,
+    repo_path: Option<String>,
+) -> Result<Option<String>, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+
+    // `--path` expands `~/` the same way `git commit` does
+    let configured = git_run(
+        &state,
+        Some(path.clone()),
+        &["config", "--path", "--get", "commit.template"],
+        TIMEOUT_QUICK,
+    )
+    .await
+    .ok()
+    .map(|resp| resp.stdout.trim().to_string())
+    .filter(|value| !value.is_empty());
+
+    let template_path = match configured {
+        Some(value) => {
+            let template = Path::new(&path).join(value);
+            if !template.is_file() {
+                return Err(format!(
+                    "commit.template points to a missing file: {}",
+                    template.display()
+                ));
+            }
+            template
+        }
+        None => {
+            let fallback = Path::new(&path).join(".gitmessage");
+            if !fallback.is_file() {
+                return Ok(None);
+            }
+            fallback
+        }
+    };
+
+    let bytes = std::fs::read(&template_path)
+        .map_err(|e| format!("Failed to read commit template: {}", e))?;
+    let content = String::from_utf8_lossy(&bytes);
+    if include_comments.unwrap_or(false) {
+        return Ok(Some(content.into_owned()));
+    }
+
+    let stripped: Vec<&str> = content
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect();
+    Ok(Some(stripped.join("\n").trim_end().to_string()))
+}
+
+/// Full messages of the last `limit` commits, newest first, without
+/// duplicates. Used for quick reuse and as examples for the AI prompt.
+#[tauri::command]
+pub async fn cmd_get_recent_commit_messages(
+    state: State<'_, AppState>,
+    limit: usize,
+    repo_path: Option<String>,
+) -> Result<Vec<String>, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let limit_arg = format!("-n{}", limit.clamp(1, 500));
+    let args = vec!["log".to_string(), limit_arg, "--format=%B%x00".to_string()];
+    let stdout = match git_run_log(&state, Some(path), args, TIMEOUT_LOCAL).await {
+        Ok(stdout) => stdout,
+        // No commits yet
+        Err(e) if e.to_string().contains("does not have any commits") => return Ok(Vec::new()),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let mut seen = HashSet::new();
+    Ok(stdout
+        .split('\0')
+        .map(str::trim)
+        .filter(|message| !message.is_empty())
+        .filter(|message| seen.insert(message.to_string()))
+        .map(str::to_string)
+        .collect())
+}
+
 #[tauri::command]
 pub async fn cmd_generate_commit_message(
     state: State<'_, AppState>,
@@ -631,14 +1155,15 @@ pub async fn cmd_git_add_all(
 ) -> Result<String, String> {
     let path = resolve_repo_path(&state, repo_path)?;
 
-    let exclusions = {
-        let settings = state.settings.lock().map_err(|e| e.to_string())?;
-        settings.excluded_files.clone()
-    };
+    let exclusions = load_exclusion_patterns(&state, &path)?;
 
+    // Pathspec excludes keep large excluded directories out of the index in
+    // the first place; negations can't be expressed that way, so re-included
+    // paths are left for the user to stage individually.
     let mut args = vec!["add".to_string(), ".".to_string()];
     for exc in exclusions {
-        if !exc.trim().is_empty() {
+        let exc = exc.trim();
+        if !exc.is_empty() && !exc.starts_with('!') {
             args.push(format!(":!{}", exc));
         }
     }
@@ -648,6 +1173,11 @@ pub async fn cmd_git_add_all(
         .run(Path::new(&path), &args, TIMEOUT_LOCAL)
         .await
         .map_err(|e| e.to_string())?;
+    // Pathspecs only match from the repository root; catch excluded names
+    // deeper in the tree with the same matching the status view uses.
+    unstage_excluded_files(&state, &path)
+        .await
+        .map_err(|e| e.to_string())?;
     Ok(resp.stdout)
 }
 
@@ -658,7 +1188,7 @@ pub async fn cmd_git_unstage_all(
     repo_path: Option<String>,
 ) -> Result<(), String> {
     // git restore --staged .
-    let args: Vec<String> = vec!["restore".into(), "--staged".into(), ".".into()];
+    let args = build_unstage_args(&[".".to_string()], state.git.supports_restore().await);
     git_run_void_with_event(&app, &state, repo_path, args, TIMEOUT_LOCAL).await
 }
 
@@ -668,18 +1198,12 @@ pub async fn cmd_git_checkout(
     state: State<'_, AppState>,
     branch: String,
     repo_path: Option<String>,
-) -> Result<GitCommandResult, String> {
+) -> CommandResult<GitCommandResult> {
     let args: Vec<String> = vec!["checkout".into(), branch];
     // checkout output often goes to stderr even on success
-    git_run_result_with_event(
-        &app,
-        &state,
-        repo_path,
-        args,
-        TIMEOUT_LOCAL,
-        GitCommandType::Checkout,
-    )
-    .await
+    let resp = git_run_typed(&state, repo_path, args, TIMEOUT_LOCAL).await?;
+    emit_git_change_event(&app)?;
+    Ok(map_git_result(resp, GitCommandType::Checkout))
 }
 
 #[tauri::command]
@@ -705,16 +1229,25 @@ pub async fn cmd_git_log(
 ) -> Result<String, String> {
     let path = resolve_repo_path(&state, repo_path)?;
     let limit_str = format!("-n{}", limit);
-    let args: Vec<String> = vec![
-        "log".into(),
-        limit_str,
-        "--oneline".into(),
-        "--graph".into(),
-        "--decorate".into(),
-    ];
-    let resp = state
-        .git
-        .run(Path::new(&path), &args, TIMEOUT_LOCAL)
+    let mut args: Vec<String> = vec!["log".into(), limit_str, "--graph".into()];
+    match load_date_format(&state)? {
+        Some(date_format) => {
+            args.push("--format=%h%d %s (%cd)".into());
+            args.push(date_format.git_arg().into());
+        }
+        None => {
+            args.push("--oneline".into());
+            args.push("--decorate".into());
+        }
+    }
+    let resp = state
+        .git
+        .run_capped(
+            Path::new(&path),
+            &args,
+            TIMEOUT_LOCAL,
+            Some(MAX_LOG_OUTPUT_BYTES),
+        )
         .await
         .map_err(|e| e.to_string())?;
     Ok(resp.stdout)
@@ -772,32 +1305,544 @@ pub async fn cmd_get_pending_commits_count(
     }
 }
 
+/// The commits a push would send, newest first: `@{u}..HEAD`, or everything
+/// not on `origin` when the branch has no upstream yet (the same fallback as
+/// `cmd_get_pending_commits_count`).
 #[tauri::command]
-pub async fn cmd_get_commit_graph(
+pub async fn cmd_get_unpushed_commits(
     state: State<'_, AppState>,
-    limit: usize,
     repo_path: Option<String>,
-) -> Result<String, String> {
+) -> Result<Vec<GraphCommit>, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let date_arg = load_date_arg(&state, GRAPH_DATE_ARG)?.to_string();
+    let log_args = |range: &[&str]| {
+        let mut args = vec![
+            "log".to_string(),
+            GRAPH_COMMIT_FORMAT.to_string(),
+            date_arg.clone(),
+        ];
+        args.extend(range.iter().map(|r| r.to_string()));
+        args
+    };
+
+    let upstream_args = log_args(&["@{u}..HEAD"]);
+    let result = git_run_log(&state, Some(path.clone()), upstream_args, TIMEOUT_LOCAL).await;
+    let stdout = match result {
+        Ok(stdout) => stdout,
+        // No upstream configured
+        Err(_) => git_run_log(
+            &state,
+            Some(path),
+            log_args(&["HEAD", "--not", "--remotes=origin"]),
+            TIMEOUT_LOCAL,
+        )
+        .await
+        .map_err(|e| e.to_string())?,
+    };
+    Ok(stdout.lines().filter_map(parse_graph_commit_line).collect())
+}
+
+/// Commit total for `branch` (HEAD by default) plus the number of distinct
+/// authors. An empty repository reports zeros rather than failing on the
+/// missing HEAD.
+#[tauri::command]
+pub async fn cmd_get_commit_count(
+    state: State<'_, AppState>,
+    branch: Option<String>,
+    repo_path: Option<String>,
+) -> Result<CommitCount, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let repo = Some(path);
+
+    let branch = branch
+        .map(|b| b.trim().to_string())
+        .filter(|b| !b.is_empty());
+    let rev = match branch {
+        Some(b) if b.starts_with('-') => return Err(format!("Invalid branch: '{}'", b)),
+        Some(b) => b,
+        None => {
+            let has_head = git_run(
+                &state,
+                repo.clone(),
+                &["rev-parse", "--verify", "-q", "HEAD"],
+                TIMEOUT_QUICK,
+            )
+            .await
+            .is_ok();
+            if !has_head {
+                return Ok(CommitCount::default());
+            }
+            "HEAD".to_string()
+        }
+    };
+
+    let count = git_run(
+        &state,
+        repo.clone(),
+        &["rev-list", "--count", &rev, "--"],
+        TIMEOUT_LOCAL,
+    )
+    .await?;
+    let total_commits = count
+        .stdout
+        .trim()
+        .parse::<u64>()
+        .map_err(|e| format!("Unexpected rev-list output: {}", e))?;
+
+    // Counted by email, the same way `cmd_get_contributors` lists them
+    let shortlog = git_run(&state, repo, &["shortlog", "-sne", "--all"], TIMEOUT_LOCAL).await?;
+    let contributors = parse_shortlog_contributors(&shortlog.stdout).len() as u32;
+
+    Ok(CommitCount {
+        total_commits,
+        contributors,
+    })
+}
+
+/// Parse `git shortlog -sne` ("<count>\t<name> <<email>>" per line). Entries
+/// sharing an email (compared case-insensitively) are merged under the name
+/// with the most commits. Sorted by commit count, highest first.
+fn parse_shortlog_contributors(output: &str) -> Vec<Contributor> {
+    let mut by_email: HashMap<String, (Contributor, u32)> = HashMap::new();
+
+    for line in output.lines() {
+        let Some((count, ident)) = line.trim().split_once('\t') else {
+            continue;
+        };
+        let Ok(count) = count.trim().parse::<u32>() else {
+            continue;
+        };
+        let (name, email) = match ident.rsplit_once(" <") {
+            Some((name, email)) => (name.trim(), email.trim_end_matches('>')),
+            None => (ident.trim(), ""),
+        };
+
+        let key = if email.is_empty() {
+            name.to_lowercase()
+        } else {
+            email.to_lowercase()
+        };
+        let entry = by_email.entry(key).or_insert_with(|| {
+            (
+                Contributor {
+                    name: name.to_string(),
+                    email: email.to_string(),
+                    commit_count: 0,
+                },
+                0,
+            )
+        });
+        entry.0.commit_count += count;
+        // Keep the spelling used for the most commits
+        if count > entry.1 {
+            entry.0.name = name.to_string();
+            entry.1 = count;
+        }
+    }
+
+    let mut contributors: Vec<Contributor> = by_email.into_values().map(|(c, _)| c).collect();
+    contributors.sort_by(|a, b| {
+        b.commit_count
+            .cmp(&a.commit_count)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    contributors
+}
+
+/// Authors across all refs, optionally limited to commits `since` a date
+/// git understands ("2024-01-01", "3 months ago").
+#[tauri::command]
+pub async fn cmd_get_contributors(
+    state: State<'_, AppState>,
+    since: Option<String>,
+    repo_path: Option<String>,
+) -> Result<Vec<Contributor>, String> {
     let path = resolve_repo_path(&state, repo_path)?;
-    let args = build_commit_graph_args(limit);
+
+    let mut args: Vec<String> = vec!["shortlog".into(), "-sne".into(), "--all".into()];
+    let since = since
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    if let Some(since) = since {
+        args.push(format!("--since={}", since));
+    }
+
     let resp = state
         .git
         .run(Path::new(&path), &args, TIMEOUT_LOCAL)
         .await
         .map_err(|e| e.to_string())?;
-    Ok(resp.stdout)
+    Ok(parse_shortlog_contributors(&resp.stdout))
+}
+
+/// Upper bound on `UpdateCheck::new_commits`; `behind` still has the total.
+const INCOMING_COMMITS_LIMIT: usize = 100;
+
+/// Fetch, then describe what the upstream gained without touching the
+/// working tree.
+#[tauri::command]
+pub async fn cmd_check_for_updates(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<UpdateCheck, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let repo = Some(path.clone());
+
+    let has_upstream = git_run(
+        &state,
+        repo.clone(),
+        &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"],
+        TIMEOUT_QUICK,
+    )
+    .await
+    .is_ok();
+    if !has_upstream {
+        return Ok(UpdateCheck::default());
+    }
+
+    state
+        .git
+        .run(Path::new(&path), &["fetch".to_string()], TIMEOUT_NETWORK)
+        .await
+        .map_err(|e| redact_secrets(&e.to_string()))?;
+    emit_git_change_event(&app)?;
+
+    let counts = git_run(
+        &state,
+        repo.clone(),
+        &["rev-list", "--left-right", "--count", "@{u}...HEAD"],
+        TIMEOUT_QUICK,
+    )
+    .await?;
+    let (behind, ahead) = parse_ahead_behind(&counts.stdout).unwrap_or_default();
+
+    let new_commits = if behind == 0 {
+        Vec::new()
+    } else {
+        let args = vec![
+            "log".to_string(),
+            format!("--max-count={}", INCOMING_COMMITS_LIMIT),
+            GRAPH_COMMIT_FORMAT.to_string(),
+            load_date_arg(&state, GRAPH_DATE_ARG)?.to_string(),
+            "HEAD..@{u}".to_string(),
+        ];
+        git_run_log(&state, repo, args, TIMEOUT_LOCAL)
+            .await
+            .map_err(|e| e.to_string())?
+            .lines()
+            .filter_map(parse_graph_commit_line)
+            .collect()
+    };
+
+    Ok(UpdateCheck {
+        has_upstream,
+        behind,
+        ahead,
+        new_commits,
+    })
+}
+
+#[tauri::command]
+pub async fn cmd_get_commit_graph(
+    state: State<'_, AppState>,
+    limit: usize,
+    repo_path: Option<String>,
+) -> CommandResult<String> {
+    let args = build_commit_graph_args(limit, load_date_arg(&state, GRAPH_DATE_ARG)?);
+    git_run_log(&state, repo_path, args, TIMEOUT_LOCAL).await
+}
+
+/// The committer date (`%cd`) follows the `--date=` option, so pair this with
+/// `load_date_arg`.
+const GRAPH_COMMIT_FORMAT: &str = "--pretty=format:%H|%P|%d|%an|%cd|%s";
+
+/// The graph's own date format, strict ISO 8601 that the frontend re-renders.
+const GRAPH_DATE_ARG: &str = "--date=iso-strict";
+
+/// File and line history's own date format.
+const HISTORY_DATE_ARG: &str = "--date=short";
+
+/// The user's date style for log output; `None` leaves each view its own.
+fn load_date_format(state: &State<'_, AppState>) -> Result<Option<DateFormat>, String> {
+    let settings = state.settings.lock().map_err(|e| e.to_string())?;
+    Ok(settings.date_format)
+}
+
+/// The `--date=` option for a view: the user's `DateFormat` if one is set,
+/// otherwise `builtin`.
+fn load_date_arg(
+    state: &State<'_, AppState>,
+    builtin: &'static str,
+) -> Result<&'static str, String> {
+    Ok(load_date_format(state)?.map_or(builtin, DateFormat::git_arg))
+}
+
+/// Size above which files and diffs are not sent to the viewers.
+fn load_file_view_limit(state: &State<'_, AppState>) -> Result<u64, String> {
+    let settings = state.settings.lock().map_err(|e| e.to_string())?;
+    Ok(settings.file_view_limit())
+}
+
+/// Size in bytes of a git object such as `HEAD:path` or `:path`, without
+/// reading it; `None` when the object doesn't exist.
+async fn git_object_size(
+    state: &State<'_, AppState>,
+    repo_path: &str,
+    object: &str,
+) -> Option<u64> {
+    let args = vec!["cat-file".to_string(), "-s".to_string(), object.to_string()];
+    let resp = state
+        .git
+        .run(Path::new(repo_path), &args, TIMEOUT_QUICK)
+        .await
+        .ok()?;
+    resp.stdout.trim().parse().ok()
 }
 
-fn build_commit_graph_args(limit: usize) -> Vec<String> {
+fn build_commit_graph_args(limit: usize, date_arg: &str) -> Vec<String> {
     vec![
         "log".to_string(),
         format!("--max-count={}", limit),
         "--all".to_string(),
-        "--pretty=format:%H|%P|%d|%an|%cI|%s".to_string(),
-        "--date=local".to_string(),
+        GRAPH_COMMIT_FORMAT.to_string(),
+        date_arg.to_string(),
     ]
 }
 
+/// Parse one `GRAPH_COMMIT_FORMAT` line.
+fn parse_graph_commit_line(line: &str) -> Option<GraphCommit> {
+    let parts: Vec<&str> = line.split('|').collect();
+    if parts.len() < 6 || parts[0].trim().is_empty() {
+        return None;
+    }
+
+    let refs = parts[2]
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    Some(GraphCommit {
+        hash: parts[0].trim().to_string(),
+        parents: parts[1].split_whitespace().map(str::to_string).collect(),
+        refs,
+        author: parts[3].to_string(),
+        date: parts[4].to_string(),
+        subject: parts[5..].join("|"), // Rejoin subject in case it contained pipes
+    })
+}
+
+#[tauri::command]
+pub async fn cmd_get_commit_graph_structured(
+    state: State<'_, AppState>,
+    limit: usize,
+    repo_path: Option<String>,
+) -> Result<Vec<GraphCommit>, String> {
+    let args = build_commit_graph_args(limit, load_date_arg(&state, GRAPH_DATE_ARG)?);
+    let stdout = git_run_log(&state, repo_path, args, TIMEOUT_LOCAL)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(stdout.lines().filter_map(parse_graph_commit_line).collect())
+}
+
+/// The structured graph with a lane and connecting edges for every commit,
+/// so the frontend only has to draw it.
+#[tauri::command]
+pub async fn cmd_get_commit_graph_lanes(
+    state: State<'_, AppState>,
+    limit: usize,
+    repo_path: Option<String>,
+) -> Result<Vec<GraphRow>, String> {
+    let mut args = build_commit_graph_args(limit, load_date_arg(&state, GRAPH_DATE_ARG)?);
+    // The layout needs every child listed before its parents
+    args.push("--date-order".to_string());
+    let stdout = git_run_log(&state, repo_path, args, TIMEOUT_LOCAL)
+        .await
+        .map_err(|e| e.to_string())?;
+    let commits = stdout.lines().filter_map(parse_graph_commit_line).collect();
+    Ok(layout_graph(commits))
+}
+
+/// Commits in `rev-list --parents` output that list `hash` as a parent.
+fn find_children(rev_list: &str, hash: &str) -> Vec<String> {
+    rev_list
+        .lines()
+        .filter_map(|line| {
+            let mut shas = line.split_whitespace();
+            let commit = shas.next()?;
+            shas.any(|parent| parent == hash)
+                .then(|| commit.to_string())
+        })
+        .collect()
+}
+
+/// Parents of a commit, and optionally its children on any ref, so the graph
+/// can step to a neighbour without reloading.
+#[tauri::command]
+pub async fn cmd_get_commit_parents(
+    state: State<'_, AppState>,
+    commit_hash: String,
+    include_children: Option<bool>,
+    repo_path: Option<String>,
+) -> Result<CommitParents, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let commit_hash = commit_hash.trim();
+    if commit_hash.is_empty() || commit_hash.starts_with('-') {
+        return Err(format!("Invalid commit: '{}'", commit_hash));
+    }
+
+    let args: Vec<String> = vec![
+        "rev-list".into(),
+        "--parents".into(),
+        "-n1".into(),
+        commit_hash.to_string(),
+    ];
+    let resp = git_run_vec(&state, Some(path.clone()), args, TIMEOUT_QUICK).await?;
+    let mut shas = resp.stdout.split_whitespace().map(str::to_string);
+    let hash = shas
+        .next()
+        .ok_or_else(|| format!("Commit not found: '{}'", commit_hash))?;
+    let parents: Vec<String> = shas.collect();
+
+    // A child can't be an ancestor, so everything reachable from the commit
+    // is excluded up front
+    let children = if include_children.unwrap_or(false) {
+        let args: Vec<String> = vec![
+            "rev-list".into(),
+            "--parents".into(),
+            "--all".into(),
+            "--not".into(),
+            hash.clone(),
+        ];
+        let resp = git_run_vec(&state, Some(path), args, TIMEOUT_LOCAL).await?;
+        Some(find_children(&resp.stdout, &hash))
+    } else {
+        None
+    };
+
+    Ok(CommitParents {
+        is_merge: parents.len() > 1,
+        hash,
+        parents,
+        children,
+    })
+}
+
+/// Parse the `[GNUPG:]` status lines `verify-commit --raw` writes to stderr.
+/// Only `GOODSIG` means valid; the `EXP*`/`REVKEY`/`BAD` variants replace it.
+fn parse_gpg_status(raw: &str) -> CommitSignature {
+    let mut signature = CommitSignature::default();
+    for line in raw.lines() {
+        let Some(status) = line.trim().strip_prefix("[GNUPG:] ") else {
+            continue;
+        };
+        let (keyword, rest) = status.split_once(' ').unwrap_or((status, ""));
+        match keyword {
+            "GOODSIG" | "BADSIG" | "EXPSIG" | "EXPKEYSIG" | "REVKEYSIG" => {
+                let (key_id, signer) = rest.split_once(' ').unwrap_or((rest, ""));
+                signature.signed = true;
+                signature.valid = keyword == "GOODSIG";
+                signature.key_id = Some(key_id.to_string()).filter(|k| !k.is_empty());
+                signature.signer = Some(signer.trim().to_string()).filter(|s| !s.is_empty());
+            }
+            // The key isn't in the keyring, so there is no signer name
+            "ERRSIG" => {
+                signature.signed = true;
+                signature.key_id = rest.split_whitespace().next().map(str::to_string);
+            }
+            _ => {}
+        }
+    }
+    signature
+}
+
+/// Check a commit's GPG signature. An unsigned commit is reported as
+/// `signed: false` rather than an error.
+#[tauri::command]
+pub async fn cmd_verify_commit_signature(
+    state: State<'_, AppState>,
+    commit_hash: String,
+    repo_path: Option<String>,
+) -> Result<CommitSignature, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let commit_hash = commit_hash.trim();
+    if commit_hash.is_empty() || commit_hash.starts_with('-') {
+        return Err(format!("Invalid commit: '{}'", commit_hash));
+    }
+
+    let args: Vec<String> = vec![
+        "verify-commit".into(),
+        "--raw".into(),
+        commit_hash.to_string(),
+    ];
+    let resp = state
+        .git
+        .run_unchecked(Path::new(&path), &args, TIMEOUT_QUICK)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let signature = parse_gpg_status(&resp.stderr);
+    // Non-zero with no status lines: either unsigned (no output) or a real
+    // failure such as an unknown commit
+    if resp.exit_code != 0 && !signature.signed && !resp.stderr.trim().is_empty() {
+        return Err(resp.stderr.trim().to_string());
+    }
+    Ok(signature)
+}
+
+#[tauri::command]
+pub async fn cmd_git_describe(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<String, String> {
+    // --always falls back to the short hash when no tag is reachable
+    let resp = git_run(
+        &state,
+        repo_path,
+        &["describe", "--tags", "--always", "--dirty"],
+        TIMEOUT_QUICK,
+    )
+    .await?;
+    Ok(resp.stdout.trim().to_string())
+}
+
+#[tauri::command]
+pub async fn cmd_git_log_pickaxe(
+    state: State<'_, AppState>,
+    term: String,
+    regex: bool,
+    repo_path: Option<String>,
+) -> Result<Vec<GraphCommit>, String> {
+    if term.is_empty() {
+        return Err("Search term cannot be empty".to_string());
+    }
+
+    // The term is glued to the flag as a single argument, so -S treats any
+    // special characters literally (no shell involved either way).
+    let search = if regex {
+        format!("-G{}", term)
+    } else {
+        format!("-S{}", term)
+    };
+    let args = vec![
+        "log".to_string(),
+        search,
+        GRAPH_COMMIT_FORMAT.to_string(),
+        load_date_arg(&state, GRAPH_DATE_ARG)?.to_string(),
+    ];
+
+    // Pickaxe has to diff every commit in history, so allow the long timeout
+    let stdout = git_run_log(&state, repo_path, args, TIMEOUT_NETWORK)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(stdout.lines().filter_map(parse_graph_commit_line).collect())
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -807,6 +1852,15 @@ pub struct FileStatus {
     pub staged: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSummary {
+    pub staged: u32,
+    pub unstaged: u32,
+    pub untracked: u32,
+    pub conflicted: u32,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CommitChangedFile {
@@ -824,6 +1878,16 @@ pub struct BlameLine {
     pub content: String,
 }
 
+/// Who last touched a single line, for hover popovers.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BlameLineInfo {
+    pub commit_hash: String,
+    pub author: String,
+    pub date: String,
+    pub summary: String,
+}
+
 fn strip_surrounding_quotes(path: &str) -> String {
     let trimmed = path.trim();
     if trimmed.starts_with('"') && trimmed.ends_with('"') && trimmed.len() >= 2 {
@@ -903,22 +1967,22 @@ fn filter_excluded_status_entries(entries: Vec<FileStatus>, exclusions: &[String
         .collect()
 }
 
-fn load_exclusion_patterns(state: &State<'_, AppState>) -> Result<Vec<String>, String> {
+/// The global exclusion patterns followed by those of the repository at
+/// `repo_path`.
+fn load_exclusion_patterns(
+    state: &State<'_, AppState>,
+    repo_path: &str,
+) -> Result<Vec<String>, String> {
     let settings = state.settings.lock().map_err(|e| e.to_string())?;
-    Ok(settings.excluded_files.clone())
+    Ok(settings.exclusions_for_repo(repo_path))
 }
 
 async fn fetch_raw_status_output(
     state: &State<'_, AppState>,
     repo_path: Option<String>,
-) -> Result<String, String> {
-    let path = resolve_repo_path(state, repo_path)?;
+) -> CommandResult<String> {
     let args = vec!["status".to_string(), "--porcelain".to_string()];
-    let resp = state
-        .git
-        .run(Path::new(&path), &args, TIMEOUT_LOCAL)
-        .await
-        .map_err(|e| e.to_string())?;
+    let resp = git_run_typed(state, repo_path, args, TIMEOUT_LOCAL).await?;
     Ok(resp.stdout)
 }
 
@@ -926,23 +1990,130 @@ async fn fetch_raw_status_output(
 pub async fn cmd_get_status_files(
     state: State<'_, AppState>,
     repo_path: Option<String>,
-) -> Result<Vec<FileStatus>, String> {
-    let raw_output = fetch_raw_status_output(&state, repo_path).await?;
-    let exclusions = load_exclusion_patterns(&state)?;
+) -> CommandResult<Vec<FileStatus>> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let raw_output = fetch_raw_status_output(&state, Some(path.clone())).await?;
+    let exclusions = load_exclusion_patterns(&state, &path)?;
     let entries = parse_status_entries(&raw_output);
     Ok(filter_excluded_status_entries(entries, &exclusions))
 }
 
+/// Paths that `patterns` would hide: tracked files from `ls-files` plus the
+/// entries the status view currently shows. Lets the settings UI preview a
+/// pattern list before saving it; matching is the same `is_excluded`.
 #[tauri::command]
-pub async fn cmd_get_diff_file(
+pub async fn cmd_preview_exclusions(
     state: State<'_, AppState>,
-    file_path: String,
-    staged: bool,
-    encoding: Option<String>,
+    patterns: Vec<String>,
     repo_path: Option<String>,
-) -> Result<String, String> {
-    diff_commands::cmd_get_diff_file_impl(state, file_path, staged, encoding, repo_path).await
-}
+) -> CommandResult<Vec<String>> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let raw_status = fetch_raw_status_output(&state, Some(path.clone())).await?;
+    let args: Vec<String> = vec!["ls-files".into(), "-z".into()];
+    let tracked = git_run_typed(&state, Some(path), args, TIMEOUT_LOCAL).await?;
+
+    let status_paths = parse_status_entries(&raw_status)
+        .into_iter()
+        .map(|entry| resolve_file_target_path(&entry.path));
+    let tracked_paths = tracked
+        .stdout
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(str::to_string);
+
+    let paths: std::collections::BTreeSet<String> = status_paths.chain(tracked_paths).collect();
+    Ok(paths
+        .into_iter()
+        .filter(|p| is_excluded(p, &patterns))
+        .collect())
+}
+
+/// Staged/unstaged/untracked/conflicted counts from one status call.
+#[tauri::command]
+pub async fn cmd_get_change_summary(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> CommandResult<ChangeSummary> {
+    let args: Vec<String> = vec!["status".into(), "--porcelain=v2".into(), "-z".into()];
+    let path = resolve_repo_path(&state, repo_path)?;
+    let resp = git_run_typed(&state, Some(path.clone()), args, TIMEOUT_LOCAL).await?;
+    let exclusions = load_exclusion_patterns(&state, &path)?;
+    Ok(parse_change_summary(&resp.stdout, &exclusions))
+}
+
+/// Count `git status --porcelain=v2 -z` records. A file with both staged and
+/// unstaged changes counts towards both. Renames/copies (`2`) are followed by
+/// an extra NUL-separated record holding the original path, which is skipped
+/// so each entry is counted once.
+fn parse_change_summary(output: &str, exclusions: &[String]) -> ChangeSummary {
+    let mut summary = ChangeSummary::default();
+    let mut records = output.split('\0').filter(|r| !r.is_empty());
+
+    while let Some(record) = records.next() {
+        let (kind, fields, path_index) = match record.chars().next() {
+            Some('1') => ('1', record.splitn(9, ' ').collect::<Vec<_>>(), 8),
+            Some('2') => {
+                records.next();
+                ('2', record.splitn(10, ' ').collect::<Vec<_>>(), 9)
+            }
+            Some('u') => ('u', record.splitn(11, ' ').collect::<Vec<_>>(), 10),
+            Some('?') => ('?', record.splitn(2, ' ').collect::<Vec<_>>(), 1),
+            _ => continue,
+        };
+        let Some(path) = fields.get(path_index) else {
+            continue;
+        };
+        if is_excluded(path, exclusions) {
+            continue;
+        }
+
+        match kind {
+            '?' => summary.untracked += 1,
+            'u' => summary.conflicted += 1,
+            _ => {
+                let xy = fields.get(1).copied().unwrap_or("..");
+                let mut flags = xy.chars();
+                if flags.next().is_some_and(|x| x != '.') {
+                    summary.staged += 1;
+                }
+                if flags.next().is_some_and(|y| y != '.') {
+                    summary.unstaged += 1;
+                }
+            }
+        }
+    }
+    summary
+}
+
+#[tauri::command]
+pub async fn cmd_get_diff_file(
+    state: State<'_, AppState>,
+    file_path: String,
+    staged: bool,
+    encoding: Option<String>,
+    repo_path: Option<String>,
+) -> Result<FileContent, String> {
+    diff_commands::cmd_get_diff_file_impl(state, file_path, staged, encoding, repo_path).await
+}
+
+/// Like `cmd_get_diff_file`, but parsed and sent hunk by hunk as `diff-chunk`
+/// events, followed by `diff-done`, so huge diffs render progressively.
+/// `stream_id` is echoed in every event to tell concurrent streams apart.
+#[tauri::command]
+pub async fn cmd_get_diff_file_stream(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    stream_id: String,
+    file_path: String,
+    staged: bool,
+    encoding: Option<String>,
+    repo_path: Option<String>,
+) -> Result<DiffDoneEvent, String> {
+    diff_commands::cmd_get_diff_file_stream_impl(
+        app, state, stream_id, file_path, staged, encoding, repo_path,
+    )
+    .await
+}
 
 #[tauri::command]
 pub async fn cmd_get_file_base_content(
@@ -951,7 +2122,7 @@ pub async fn cmd_get_file_base_content(
     staged: bool,
     encoding: Option<String>,
     repo_path: Option<String>,
-) -> Result<String, String> {
+) -> Result<FileContent, String> {
     diff_commands::cmd_get_file_base_content_impl(state, file_path, staged, encoding, repo_path)
         .await
 }
@@ -963,31 +2134,120 @@ pub async fn cmd_get_file_modified_content(
     staged: bool,
     encoding: Option<String>,
     repo_path: Option<String>,
-) -> Result<String, String> {
+) -> Result<FileContent, String> {
     diff_commands::cmd_get_file_modified_content_impl(state, file_path, staged, encoding, repo_path)
         .await
 }
 
+#[tauri::command]
+pub async fn cmd_get_diff_context(
+    state: State<'_, AppState>,
+    file_path: String,
+    commit: Option<String>,
+    around_line: u32,
+    context_lines: u32,
+    repo_path: Option<String>,
+    encoding: Option<String>,
+) -> Result<DiffContext, String> {
+    diff_commands::cmd_get_diff_context_impl(
+        state,
+        file_path,
+        commit,
+        around_line,
+        context_lines,
+        encoding,
+        repo_path,
+    )
+    .await
+}
+
 #[tauri::command]
 pub async fn cmd_git_add(
     app: AppHandle,
     state: State<'_, AppState>,
     path: String,
     repo_path: Option<String>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     let r_path = resolve_repo_path(&state, repo_path)?;
-
-    let exclusions = {
-        let settings = state.settings.lock().map_err(|e| e.to_string())?;
-        settings.excluded_files.clone()
-    };
+    let exclusions = load_exclusion_patterns(&state, &r_path)?;
 
     if is_excluded(&path, &exclusions) {
-        return Err(format!("File {} is excluded from git operations", path));
+        return Err(CommandError::InvalidInput(format!(
+            "File {} is excluded from git operations",
+            path
+        )));
     }
 
     let args: Vec<String> = vec!["add".into(), path];
-    git_run_vec_at_path(&state, &r_path, args, TIMEOUT_LOCAL).await?;
+    git_run_typed(&state, Some(r_path), args, TIMEOUT_LOCAL).await?;
+    emit_git_change_event(&app)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn cmd_git_mv(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    from: String,
+    to: String,
+    repo_path: Option<String>,
+) -> CommandResult<()> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    let exclusions = load_exclusion_patterns(&state, &r_path)?;
+
+    for path in [&from, &to] {
+        if is_excluded(path, &exclusions) {
+            return Err(CommandError::InvalidInput(format!(
+                "File {} is excluded from git operations",
+                path
+            )));
+        }
+    }
+
+    let tracked_args: Vec<String> = vec![
+        "ls-files".into(),
+        "--error-unmatch".into(),
+        "--".into(),
+        from.clone(),
+    ];
+    if state
+        .git
+        .run(Path::new(&r_path), &tracked_args, TIMEOUT_QUICK)
+        .await
+        .is_err()
+    {
+        return Err(CommandError::InvalidInput(format!(
+            "{} is not tracked by git",
+            from
+        )));
+    }
+
+    // On a case-insensitive filesystem `Readme.md -> README.md` finds the
+    // source itself; git mv handles that rename and refuses a real clash
+    let case_only = from.to_lowercase() == to.to_lowercase();
+    if !case_only && Path::new(&r_path).join(&to).exists() {
+        return Err(CommandError::InvalidInput(format!(
+            "Cannot move {}: destination {} already exists",
+            from, to
+        )));
+    }
+
+    let args: Vec<String> = vec!["mv".into(), "--".into(), from.clone(), to.clone()];
+    let result = state
+        .git
+        .run(Path::new(&r_path), &args, TIMEOUT_LOCAL)
+        .await;
+    match result {
+        Ok(_) => {}
+        Err(GitError::CommandError(msg)) if msg.contains("destination exists") => {
+            return Err(CommandError::InvalidInput(format!(
+                "Cannot move {}: destination {} already exists",
+                from, to
+            )));
+        }
+        Err(e) => return Err(e.into()),
+    }
+
     emit_git_change_event(&app)?;
     Ok(())
 }
@@ -1014,30 +2274,239 @@ pub async fn cmd_git_unstage_line(
     diff_commands::cmd_git_unstage_line_impl(app, state, path, line, repo_path).await
 }
 
+/// Apply a `.patch`/`.diff` to the working tree, or to the index when
+/// `cached` is set.
+#[tauri::command]
+pub async fn cmd_git_apply_patch(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    patch_content: String,
+    cached: bool,
+    reverse: bool,
+    repo_path: Option<String>,
+) -> Result<(), String> {
+    diff_commands::cmd_git_apply_patch_impl(app, state, patch_content, cached, reverse, repo_path)
+        .await
+}
+
 #[tauri::command]
 pub async fn cmd_git_unstage(
     app: AppHandle,
     state: State<'_, AppState>,
     path: String,
     repo_path: Option<String>,
-) -> Result<(), String> {
+) -> CommandResult<()> {
     // git restore --staged <path>
-    let args: Vec<String> = vec!["restore".into(), "--staged".into(), path];
-    git_run_void_with_event(&app, &state, repo_path, args, TIMEOUT_LOCAL).await
+    let args = build_unstage_args(&[path], state.git.supports_restore().await);
+    git_run_typed(&state, repo_path, args, TIMEOUT_LOCAL).await?;
+    emit_git_change_event(&app)?;
+    Ok(())
+}
+
+/// Arguments to unstage `paths`. `git restore --staged` only exists since
+/// git 2.23, so older versions fall back to `git reset HEAD`.
+fn build_unstage_args(paths: &[String], supports_restore: bool) -> Vec<String> {
+    let mut args: Vec<String> = if supports_restore {
+        vec!["restore".into(), "--staged".into()]
+    } else {
+        vec!["reset".into(), "HEAD".into()]
+    };
+    args.push("--".into());
+    args.extend(paths.iter().cloned());
+    args
+}
+
+/// Where the backups written by `cmd_git_discard_changes` ended up.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscardResult {
+    pub backup_patch_path: Option<String>,
+    pub backup_untracked_dir: Option<String>,
+    /// Why untracked files were discarded without a backup.
+    pub backup_warning: Option<String>,
+}
+
+/// Limits on the untracked-file backup, so discarding a stray
+/// `node_modules/` or build directory doesn't copy gigabytes first.
+const MAX_UNTRACKED_BACKUP_BYTES: u64 = 100 * 1024 * 1024;
+const MAX_UNTRACKED_BACKUP_FILES: usize = 10_000;
+
+/// Collect the regular files under `path` (or `path` itself) with their total
+/// size, skipping symlinks and `ignored` paths. Returns `false` as soon as the
+/// backup limits are exceeded.
+fn collect_backup_files(
+    path: &Path,
+    ignored: &HashSet<PathBuf>,
+    files: &mut Vec<PathBuf>,
+    total_bytes: &mut u64,
+) -> std::io::Result<bool> {
+    if ignored.contains(path) {
+        return Ok(true);
+    }
+    let meta = std::fs::symlink_metadata(path)?;
+    if meta.is_dir() {
+        for entry in std::fs::read_dir(path)? {
+            if !collect_backup_files(&entry?.path(), ignored, files, total_bytes)? {
+                return Ok(false);
+            }
+        }
+    } else if meta.is_file() {
+        files.push(path.to_path_buf());
+        *total_bytes += meta.len();
+    }
+    Ok(files.len() <= MAX_UNTRACKED_BACKUP_FILES && *total_bytes <= MAX_UNTRACKED_BACKUP_BYTES)
+}
+
+/// Save everything `cmd_git_discard_changes` is about to throw away into the
+/// app data dir: a binary patch of the tracked changes (against HEAD, so both
+/// staged and unstaged edits are covered) and a copy of the untracked files.
+/// Ignored files are not copied, and untracked files over the backup limits
+/// are not copied at all; `backup_warning` says so instead.
+async fn backup_discarded_changes(
+    app: &AppHandle,
+    state: &State<'_, AppState>,
+    repo_path: &str,
+    tracked_paths: &HashSet<String>,
+    untracked_paths: &HashSet<String>,
+) -> Result<DiscardResult, String> {
+    let mut result = DiscardResult::default();
+    if tracked_paths.is_empty() && untracked_paths.is_empty() {
+        return Ok(result);
+    }
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    let backup_root = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("discard-backups");
+    std::fs::create_dir_all(&backup_root)
+        .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+
+    if !tracked_paths.is_empty() {
+        let mut args: Vec<String> =
+            vec!["diff".into(), "HEAD".into(), "--binary".into(), "--".into()];
+        args.extend(tracked_paths.iter().cloned());
+        let resp = state
+            .git
+            .run_with_output_bytes(Path::new(repo_path), &args, TIMEOUT_LOCAL)
+            .await
+            .map_err(|e| format!("Failed to back up changes before discarding: {}", e))?;
+
+        if !resp.stdout.is_empty() {
+            let patch_path = backup_root.join(format!("discard-{}.patch", timestamp));
+            std::fs::write(&patch_path, &resp.stdout)
+                .map_err(|e| format!("Failed to write backup patch: {}", e))?;
+            result.backup_patch_path = Some(patch_path.to_string_lossy().to_string());
+        }
+    }
+
+    if !untracked_paths.is_empty() {
+        let args: Vec<String> = vec![
+            "ls-files".into(),
+            "-z".into(),
+            "--others".into(),
+            "--ignored".into(),
+            "--exclude-standard".into(),
+            "--directory".into(),
+        ];
+        let ignored = state
+            .git
+            .run(Path::new(repo_path), &args, TIMEOUT_LOCAL)
+            .await
+            .map(|resp| crate::watcher::parse_ignored_paths(Path::new(repo_path), &resp.stdout))
+            .unwrap_or_default();
+
+        let mut files = Vec::new();
+        let mut total_bytes = 0;
+        let mut within_limits = true;
+        for path in untracked_paths {
+            let source = Path::new(repo_path).join(path);
+            if !source.exists() {
+                continue;
+            }
+            let fits = collect_backup_files(&source, &ignored, &mut files, &mut total_bytes)
+                .map_err(|e| format!("Failed to back up untracked file {}: {}", path, e))?;
+            if !fits {
+                within_limits = false;
+                break;
+            }
+        }
+
+        if !within_limits {
+            result.backup_warning = Some(format!(
+                "Untracked files were not backed up: more than {} files or {} MB",
+                MAX_UNTRACKED_BACKUP_FILES,
+                MAX_UNTRACKED_BACKUP_BYTES / (1024 * 1024)
+            ));
+        } else if !files.is_empty() {
+            let untracked_dir = backup_root.join(format!("discard-{}-untracked", timestamp));
+            for file in &files {
+                let relative = file.strip_prefix(repo_path).unwrap_or(file);
+                let destination = untracked_dir.join(relative);
+                if let Some(parent) = destination.parent() {
+                    std::fs::create_dir_all(parent)
+                        .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+                }
+                std::fs::copy(file, &destination)
+                    .map_err(|e| format!("Failed to back up {}: {}", relative.display(), e))?;
+            }
+            result.backup_untracked_dir = Some(untracked_dir.to_string_lossy().to_string());
+        }
+    }
+
+    Ok(result)
+}
+
+/// List the untracked paths `git clean -d` would remove, minus excluded files,
+/// so the UI can confirm them before calling `cmd_git_discard_changes`.
+#[tauri::command]
+pub async fn cmd_git_clean_preview(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<Vec<String>, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let resp = git_run(&state, Some(path.clone()), &["clean", "-nd"], TIMEOUT_LOCAL).await?;
+    let exclusions = load_exclusion_patterns(&state, &path)?;
+
+    Ok(resp
+        .stdout
+        .lines()
+        .filter_map(|line| line.strip_prefix("Would remove "))
+        .map(strip_surrounding_quotes)
+        .filter(|path| !path.is_empty() && !is_excluded(path, &exclusions))
+        .collect())
+}
+
+#[tauri::command]
+pub fn cmd_prepare_destructive(
+    state: State<AppState>,
+    operation: String,
+    paths: Vec<String>,
+) -> Result<String, String> {
+    state.confirmations.issue(operation.trim(), &paths)
 }
 
+/// In safe mode, removing untracked directories with `clean -fd` also needs
+/// a `clean` token from `cmd_prepare_destructive`, issued for exactly those
+/// directories without a trailing slash.
 #[tauri::command]
 pub async fn cmd_git_discard_changes(
     app: AppHandle,
     state: State<'_, AppState>,
     files: Vec<FileStatus>,
     repo_path: Option<String>,
-) -> Result<(), String> {
+    confirmation_token: Option<String>,
+    clean_paths: Option<Vec<String>>,
+    clean_confirmation_token: Option<String>,
+) -> Result<DiscardResult, String> {
     let r_path = resolve_repo_path(&state, repo_path)?;
-    let exclusions = {
-        let settings = state.settings.lock().map_err(|e| e.to_string())?;
-        settings.excluded_files.clone()
-    };
+    let requested_paths: Vec<String> = files.iter().map(|f| f.path.clone()).collect();
+
+    let exclusions = load_exclusion_patterns(&state, &r_path)?;
 
     let mut tracked_paths = HashSet::<String>::new();
     let mut untracked_paths = HashSet::<String>::new();
@@ -1061,14 +2530,55 @@ pub async fn cmd_git_discard_changes(
         }
     }
 
+    // Only delete untracked paths the user reviewed via `cmd_git_clean_preview`.
+    if let Some(reviewed) = clean_paths {
+        let reviewed: HashSet<String> = reviewed
+            .iter()
+            .map(|p| p.trim().trim_end_matches('/').to_string())
+            .collect();
+        untracked_paths.retain(|p| reviewed.contains(p.trim_end_matches('/')));
+    }
+
+    // Clean files and directories separately so `-d` only ever applies to
+    // directories that were explicitly selected.
+    let (untracked_dirs, untracked_files): (Vec<String>, Vec<String>) = untracked_paths
+        .iter()
+        .cloned()
+        .partition(|p| p.ends_with('/') || Path::new(&r_path).join(p).is_dir());
+    let dirs: Vec<String> = untracked_dirs
+        .iter()
+        .map(|d| d.trim_end_matches('/').to_string())
+        .collect();
+    let mut confirmations = vec![ConfirmationRequest {
+        token: confirmation_token.as_deref(),
+        operation: "discard_changes",
+        paths: &requested_paths,
+    }];
+    if !dirs.is_empty() {
+        confirmations.push(ConfirmationRequest {
+            token: clean_confirmation_token.as_deref(),
+            operation: "clean",
+            paths: &dirs,
+        });
+    }
+    check_destructive_confirmations(&state, &confirmations)?;
+
+    let backup =
+        backup_discarded_changes(&app, &state, &r_path, &tracked_paths, &untracked_paths).await?;
+
     if !tracked_paths.is_empty() {
-        let mut args: Vec<String> = vec![
-            "restore".into(),
-            "--source=HEAD".into(),
-            "--staged".into(),
-            "--worktree".into(),
-            "--".into(),
-        ];
+        let mut args: Vec<String> = if state.git.supports_restore().await {
+            vec![
+                "restore".into(),
+                "--source=HEAD".into(),
+                "--staged".into(),
+                "--worktree".into(),
+                "--".into(),
+            ]
+        } else {
+            // Pre-2.23 equivalent: reset both index and worktree from HEAD
+            vec!["checkout".into(), "HEAD".into(), "--".into()]
+        };
         args.extend(tracked_paths.into_iter());
         state
             .git
@@ -1077,9 +2587,19 @@ pub async fn cmd_git_discard_changes(
             .map_err(|e| e.to_string())?;
     }
 
-    if !untracked_paths.is_empty() {
+    if !untracked_files.is_empty() {
+        let mut args: Vec<String> = vec!["clean".into(), "-f".into(), "--".into()];
+        args.extend(untracked_files);
+        state
+            .git
+            .run(Path::new(&r_path), &args, TIMEOUT_LOCAL)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    if !untracked_dirs.is_empty() {
         let mut args: Vec<String> = vec!["clean".into(), "-fd".into(), "--".into()];
-        args.extend(untracked_paths.into_iter());
+        args.extend(untracked_dirs);
         state
             .git
             .run(Path::new(&r_path), &args, TIMEOUT_LOCAL)
@@ -1088,7 +2608,7 @@ pub async fn cmd_git_discard_changes(
     }
 
     emit_git_change_event(&app)?;
-    Ok(())
+    Ok(backup)
 }
 
 #[tauri::command]
@@ -1099,10 +2619,7 @@ pub async fn cmd_git_stash_file(
     repo_path: Option<String>,
 ) -> Result<(), String> {
     let r_path = resolve_repo_path(&state, repo_path)?;
-    let exclusions = {
-        let settings = state.settings.lock().map_err(|e| e.to_string())?;
-        settings.excluded_files.clone()
-    };
+    let exclusions = load_exclusion_patterns(&state, &r_path)?;
 
     let raw_path = file.path.trim();
     if raw_path.is_empty() {
@@ -1138,20 +2655,88 @@ pub async fn cmd_git_stash_file(
     Ok(())
 }
 
+/// Stash a selection of files as a single stash entry.
+///
+/// Renames are skipped: a pathspec naming only one side of a rename would
+/// split it into an add and a delete across the stash and the worktree.
+#[tauri::command]
+pub async fn cmd_git_stash_files(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    files: Vec<FileStatus>,
+    message: Option<String>,
+    repo_path: Option<String>,
+) -> Result<(), String> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    let exclusions = load_exclusion_patterns(&state, &r_path)?;
+
+    let mut include_untracked = false;
+    let mut paths: Vec<String> = Vec::new();
+    for file in &files {
+        let raw_path = file.path.trim();
+        let is_rename = file.status.contains('R') || split_rename_path(raw_path).is_some();
+        if raw_path.is_empty() || is_rename {
+            continue;
+        }
+        if is_excluded(raw_path, &exclusions) {
+            continue;
+        }
+        include_untracked |= is_untracked_status(&file.status);
+        if !paths.iter().any(|p| p == raw_path) {
+            paths.push(raw_path.to_string());
+        }
+    }
+
+    if paths.is_empty() {
+        return Err("No stashable files selected".to_string());
+    }
+
+    let stash_message = match message.filter(|m| !m.trim().is_empty()) {
+        Some(message) => message,
+        None if paths.len() == 1 => format!("stash {}", paths[0]),
+        None => format!("stash {} files", paths.len()),
+    };
+
+    let mut args: Vec<String> = vec!["stash".into(), "push".into(), "-m".into(), stash_message];
+    if include_untracked {
+        args.push("-u".into());
+    }
+    args.push("--".into());
+    args.extend(paths);
+
+    state
+        .git
+        .run(Path::new(&r_path), &args, TIMEOUT_LOCAL)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    emit_git_change_event(&app)?;
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn cmd_git_stash_all(
     app: AppHandle,
     state: State<'_, AppState>,
+    include_untracked: Option<bool>,
+    keep_index: Option<bool>,
+    message: Option<String>,
     repo_path: Option<String>,
 ) -> Result<(), String> {
     let r_path = resolve_repo_path(&state, repo_path)?;
-    let args: Vec<String> = vec![
-        "stash".into(),
-        "push".into(),
-        "-u".into(),
-        "-m".into(),
-        "stash all".into(),
-    ];
+    let mut args: Vec<String> = vec!["stash".into(), "push".into()];
+    if include_untracked.unwrap_or(true) {
+        args.push("-u".into());
+    }
+    if keep_index.unwrap_or(false) {
+        args.push("--keep-index".into());
+    }
+    // Without -m git labels the stash "WIP on <branch>: <hash> <subject>",
+    // which says more than any fixed default would
+    if let Some(message) = message.filter(|m| !m.trim().is_empty()) {
+        args.push("-m".into());
+        args.push(message);
+    }
 
     state
         .git
@@ -1180,7 +2765,7 @@ pub async fn cmd_git_apply_stash(
         &r_path,
         args,
         TIMEOUT_LOCAL,
-        GitCommandType::Other,
+        GitCommandType::Stash,
     )
     .await
 }
@@ -1202,7 +2787,7 @@ pub async fn cmd_git_pop_stash(
         &r_path,
         args,
         TIMEOUT_LOCAL,
-        GitCommandType::Other,
+        GitCommandType::Stash,
     )
     .await
 }
@@ -1224,7 +2809,7 @@ pub async fn cmd_git_delete_stash(
         &r_path,
         args,
         TIMEOUT_LOCAL,
-        GitCommandType::Other,
+        GitCommandType::Stash,
     )
     .await
 }
@@ -1265,7 +2850,7 @@ pub async fn cmd_git_edit_stash_message(
         .await
         .map_err(|e| e.to_string())?;
     if drop_resp.exit_code != 0 {
-        return Ok(map_git_result(drop_resp, GitCommandType::Other));
+        return Ok(map_git_result(drop_resp, GitCommandType::Stash));
     }
 
     let store_args = vec![
@@ -1283,7 +2868,7 @@ pub async fn cmd_git_edit_stash_message(
 
     emit_git_change_event(&app)?;
 
-    Ok(map_git_result(store_resp, GitCommandType::Other))
+    Ok(map_git_result(store_resp, GitCommandType::Stash))
 }
 
 #[tauri::command]
@@ -1311,24 +2896,103 @@ pub async fn cmd_create_patch_from_stash(
 }
 
 #[tauri::command]
-pub async fn cmd_open_repo_file(
+pub async fn cmd_git_stash_show(
     state: State<'_, AppState>,
-    file_path: String,
+    stash_ref: String,
     repo_path: Option<String>,
-) -> Result<(), String> {
-    let r_path = resolve_repo_path(&state, repo_path)?;
-    let raw_path = file_path.trim();
-    if raw_path.is_empty() {
-        return Err("No file path provided".to_string());
+    encoding: Option<String>,
+) -> Result<Vec<DiffFile>, String> {
+    let stash_ref = stash_ref.trim().to_string();
+    if !is_valid_stash_ref(&stash_ref) {
+        return Err(format!("Invalid stash reference: {}", stash_ref));
     }
 
-    let target_path = resolve_file_target_path(raw_path);
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    let args = vec![
+        "stash".to_string(),
+        "show".to_string(),
+        "-p".to_string(),
+        stash_ref,
+    ];
+    let resp = state
+        .git
+        .run_with_output_bytes(Path::new(&r_path), &args, TIMEOUT_LOCAL)
+        .await
+        .map_err(|e| e.to_string())?;
 
-    let candidate = PathBuf::from(&target_path);
-    let full_path = if candidate.is_absolute() {
-        candidate
-    } else {
-        Path::new(&r_path).join(candidate)
+    let decoded = {
+        let settings = state.settings.lock().map_err(|e| e.to_string())?;
+        crate::git::encoding::decode_bytes(&resp.stdout, Path::new(""), &settings, encoding)
+    };
+    Ok(parse_diff_output(&decoded))
+}
+
+/// Diff the working tree against a stash entry: what changed since it was
+/// stashed, from the stash's side.
+#[tauri::command]
+pub async fn cmd_diff_working_vs_stash(
+    state: State<'_, AppState>,
+    stash_ref: String,
+    repo_path: Option<String>,
+    encoding: Option<String>,
+) -> Result<Vec<DiffFile>, String> {
+    let stash_ref = stash_ref.trim().to_string();
+    if !is_valid_stash_ref(&stash_ref) {
+        return Err(format!("Invalid stash reference: {}", stash_ref));
+    }
+
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    let args = vec!["diff".to_string(), stash_ref];
+    let resp = state
+        .git
+        .run_with_output_bytes(Path::new(&r_path), &args, TIMEOUT_LOCAL)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let decoded = {
+        let settings = state.settings.lock().map_err(|e| e.to_string())?;
+        crate::git::encoding::decode_bytes(&resp.stdout, Path::new(""), &settings, encoding)
+    };
+    Ok(parse_diff_output(&decoded))
+}
+
+/// Number of stash entries, for a badge; cheaper than listing them.
+#[tauri::command]
+pub async fn cmd_get_stash_count(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<u32, String> {
+    // `stash list` prints nothing (and succeeds) when there is no refs/stash
+    let resp = git_run(
+        &state,
+        repo_path,
+        &["stash", "list", "--format=%H"],
+        TIMEOUT_QUICK,
+    )
+    .await?;
+    let count = resp.stdout.lines().filter(|l| !l.trim().is_empty()).count();
+    Ok(count as u32)
+}
+
+#[tauri::command]
+pub async fn cmd_open_repo_file(
+    state: State<'_, AppState>,
+    file_path: String,
+    repo_path: Option<String>,
+) -> Result<(), String> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    let raw_path = file_path.trim();
+    if raw_path.is_empty() {
+        return Err("No file path provided".to_string());
+    }
+
+    let target_path = resolve_file_target_path(raw_path);
+
+    let candidate = PathBuf::from(&target_path);
+    let full_path = if candidate.is_absolute() {
+        candidate
+    } else {
+        Path::new(&r_path).join(candidate)
     };
 
     if !full_path.exists() {
@@ -1397,6 +3061,58 @@ pub async fn cmd_git_ignore_file(
     Ok(())
 }
 
+/// Path of the repository's local-only ignore file. `--git-path` resolves it
+/// in the common git dir, so linked worktrees share the main checkout's file.
+async fn local_exclude_path(
+    state: &State<'_, AppState>,
+    repo_path: &str,
+) -> Result<PathBuf, String> {
+    let args = vec![
+        "rev-parse".to_string(),
+        "--git-path".to_string(),
+        "info/exclude".to_string(),
+    ];
+    let resp = state
+        .git
+        .run(Path::new(repo_path), &args, TIMEOUT_QUICK)
+        .await
+        .map_err(|e| e.to_string())?;
+    // Relative output is relative to the repository root
+    Ok(Path::new(repo_path).join(resp.stdout.trim()))
+}
+
+/// Contents of `.git/info/exclude`; empty when the file doesn't exist yet.
+#[tauri::command]
+pub async fn cmd_get_local_exclude(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<String, String> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    let exclude_path = local_exclude_path(&state, &r_path).await?;
+    match std::fs::read_to_string(&exclude_path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(format!("Failed to read {}: {}", exclude_path.display(), e)),
+    }
+}
+
+#[tauri::command]
+pub async fn cmd_set_local_exclude(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    content: String,
+    repo_path: Option<String>,
+) -> Result<(), String> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    let exclude_path = local_exclude_path(&state, &r_path).await?;
+    if let Some(parent) = exclude_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&exclude_path, content)
+        .map_err(|e| format!("Failed to write {}: {}", exclude_path.display(), e))?;
+    emit_git_change_event(&app)
+}
+
 #[tauri::command]
 pub async fn cmd_show_in_folder(
     state: State<'_, AppState>,
@@ -1615,6 +3331,142 @@ pub async fn cmd_create_patch_from_commit(
     Ok(resp.stdout)
 }
 
+/// Export the commits in `from..to` (`to` defaults to HEAD) as numbered
+/// `.patch` files in `output_dir`, returning the paths of the files written.
+#[tauri::command]
+pub async fn cmd_format_patch(
+    state: State<'_, AppState>,
+    from: String,
+    to: Option<String>,
+    output_dir: String,
+    repo_path: Option<String>,
+) -> Result<Vec<String>, String> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    let from = from.trim();
+    if from.is_empty() {
+        return Err("No start commit provided".to_string());
+    }
+    let to = to
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .unwrap_or("HEAD");
+    if output_dir.trim().is_empty() {
+        return Err("No output directory provided".to_string());
+    }
+    for rev in [from, to] {
+        if rev.starts_with('-') {
+            return Err(format!("Invalid commit: '{}'", rev));
+        }
+        let verify = format!("{}^{{commit}}", rev);
+        git_run(
+            &state,
+            Some(r_path.clone()),
+            &["rev-parse", "--verify", "-q", &verify],
+            TIMEOUT_QUICK,
+        )
+        .await
+        .map_err(|_| format!("Unknown commit: '{}'", rev))?;
+    }
+
+    // A relative directory is taken relative to the repository
+    let out_dir = Path::new(&r_path).join(output_dir.trim());
+    std::fs::create_dir_all(&out_dir)
+        .map_err(|e| format!("Failed to create output directory: {}", e))?;
+
+    let args = vec![
+        "format-patch".to_string(),
+        format!("{}..{}", from, to),
+        "-o".to_string(),
+        out_dir.to_string_lossy().to_string(),
+    ];
+    let resp = state
+        .git
+        .run(Path::new(&r_path), &args, TIMEOUT_LOCAL)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // format-patch prints one generated file per line
+    Ok(resp
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Fail early if files can't be created in `dir`. The read-only permission
+/// bit says nothing about a directory on Unix, so try creating a file.
+fn check_dir_writable(dir: &Path) -> Result<(), String> {
+    let probe = dir.join(format!(".git-tools-write-test-{}", Uuid::new_v4()));
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .map_err(|e| format!("Cannot write to {}: {}", dir.display(), e))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+/// A bundle written by `cmd_git_bundle_create`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleInfo {
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// Pack `refs` (every ref when empty) into a single bundle file that can be
+/// cloned or fetched from without a network remote.
+#[tauri::command]
+pub async fn cmd_git_bundle_create(
+    state: State<'_, AppState>,
+    output_path: String,
+    refs: Vec<String>,
+    repo_path: Option<String>,
+) -> Result<BundleInfo, String> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    if output_path.trim().is_empty() {
+        return Err("No output path provided".to_string());
+    }
+    let is_invalid_ref = |r: &&String| r.trim().is_empty() || r.starts_with('-');
+    if let Some(bad) = refs.iter().find(is_invalid_ref) {
+        return Err(format!("Invalid ref '{}'", bad));
+    }
+
+    let output = Path::new(&r_path).join(output_path.trim());
+    if output.is_dir() {
+        return Err(format!("{} is a directory", output.display()));
+    }
+    let parent = output.parent().unwrap_or(Path::new(&r_path));
+    check_dir_writable(parent)?;
+
+    let mut args = vec![
+        "bundle".to_string(),
+        "create".to_string(),
+        output.to_string_lossy().to_string(),
+    ];
+    if refs.is_empty() {
+        args.push("--all".to_string());
+    } else {
+        args.extend(refs.iter().map(|r| r.trim().to_string()));
+    }
+    state
+        .git
+        .run(Path::new(&r_path), &args, TIMEOUT_NETWORK)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let size_bytes = std::fs::metadata(&output)
+        .map(|meta| meta.len())
+        .map_err(|e| format!("Bundle was not written: {}", e))?;
+    Ok(BundleInfo {
+        path: output.to_string_lossy().to_string(),
+        size_bytes,
+    })
+}
+
 #[tauri::command]
 pub async fn cmd_delete_file(
     state: State<'_, AppState>,
@@ -1737,6 +3589,74 @@ pub async fn cmd_git_blame(
     Ok(parse_blame_output(&resp.stdout))
 }
 
+/// Parse `git blame --porcelain` output for a single line.
+fn parse_blame_line_info(stdout: &str) -> Option<BlameLineInfo> {
+    let mut lines = stdout.lines();
+    let (commit_hash, _) = parse_blame_header(lines.next()?)?;
+    let mut info = BlameLineInfo {
+        commit_hash,
+        ..Default::default()
+    };
+
+    for line in lines {
+        if line.starts_with('\t') {
+            break;
+        }
+        if let Some(author) = line.strip_prefix("author ") {
+            info.author = author.to_string();
+        } else if let Some(date) = line.strip_prefix("author-time ") {
+            info.date = date.to_string();
+        } else if let Some(summary) = line.strip_prefix("summary ") {
+            info.summary = summary.to_string();
+        }
+    }
+    Some(info)
+}
+
+/// Blame one line (`-L<n>,<n>`), much cheaper than blaming the whole file.
+#[tauri::command]
+pub async fn cmd_blame_line(
+    state: State<'_, AppState>,
+    file_path: String,
+    line_number: u32,
+    repo_path: Option<String>,
+) -> Result<BlameLineInfo, String> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    let raw_path = file_path.trim();
+    if raw_path.is_empty() {
+        return Err("No file path provided".to_string());
+    }
+    if line_number == 0 {
+        return Err("Line numbers start at 1".to_string());
+    }
+
+    let args = vec![
+        "blame".to_string(),
+        format!("-L{},{}", line_number, line_number),
+        "--porcelain".to_string(),
+        "--".to_string(),
+        resolve_file_target_path(raw_path),
+    ];
+    let resp = match state
+        .git
+        .run(Path::new(&r_path), &args, TIMEOUT_QUICK)
+        .await
+    {
+        Ok(resp) => resp,
+        // "fatal: file <path> has only <n> lines"
+        Err(e) if e.to_string().contains(" has only ") => {
+            return Err(format!(
+                "Line {} is beyond the end of {}",
+                line_number, raw_path
+            ));
+        }
+        Err(e) => return Err(e.to_string()),
+    };
+
+    parse_blame_line_info(&resp.stdout)
+        .ok_or_else(|| format!("No blame information for line {}", line_number))
+}
+
 // ---------------------------------------------------------------------------
 // Conflict Resolution Commands (all async)
 // ---------------------------------------------------------------------------
@@ -1759,6 +3679,31 @@ pub async fn cmd_get_conflict_file(
     conflict_commands::cmd_get_conflict_file_impl(state, path, encoding, repo_path).await
 }
 
+#[tauri::command]
+pub async fn cmd_get_conflict_merged(
+    state: State<'_, AppState>,
+    path: String,
+    encoding: Option<String>,
+    repo_path: Option<String>,
+) -> Result<Vec<MergeRegion>, String> {
+    conflict_commands::cmd_get_conflict_merged_impl(state, path, encoding, repo_path).await
+}
+
+#[tauri::command]
+pub async fn cmd_resolve_conflict_with_content(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+    content: String,
+    encoding: Option<String>,
+    repo_path: Option<String>,
+) -> Result<ResolveConflictResult, String> {
+    conflict_commands::cmd_resolve_conflict_with_content_impl(
+        app, state, path, content, encoding, repo_path,
+    )
+    .await
+}
+
 #[tauri::command]
 pub async fn cmd_resolve_ours(
     app: AppHandle,
@@ -1805,6 +3750,16 @@ pub async fn cmd_get_operation_state(
     conflict_commands::cmd_get_operation_state_impl(state, repo_path).await
 }
 
+/// Which operation (merge, rebase, cherry-pick, revert, bisect) is in
+/// progress, with its progress and conflicts, in one call.
+#[tauri::command]
+pub async fn cmd_get_repo_state(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<conflict_commands::RepoState, String> {
+    conflict_commands::cmd_get_repo_state_impl(state, repo_path).await
+}
+
 // ---------------------------------------------------------------------------
 // File Operations
 // ---------------------------------------------------------------------------
@@ -1817,27 +3772,123 @@ pub fn cmd_write_file(
     encoding: Option<String>,
     repo_path: Option<String>,
 ) -> Result<(), String> {
-    use std::fs;
-
     let r_path = resolve_repo_path(&state, repo_path)?;
-    let full_path = Path::new(&r_path).join(&path);
+    write_repo_file(&state, &r_path, &path, &content, encoding)
+}
+
+/// Resolve `path` relative to the repository root, rejecting anything that
+/// would land outside it.
+///
+/// `..`/`.` components are normalized lexically, then the deepest existing
+/// ancestor is canonicalized so symlinks pointing out of the repository are
+/// caught as well. The target itself does not need to exist yet.
+fn resolve_path_within_repo(repo_path: &str, path: &str) -> Result<PathBuf, String> {
+    use std::path::Component;
+
+    let outside_err = || "Invalid path: outside of repository".to_string();
+
+    let root = std::fs::canonicalize(repo_path)
+        .map_err(|e| format!("Failed to resolve repository path {}: {}", repo_path, e))?;
+
+    let mut normalized = root.clone();
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(part) => normalized.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() || !normalized.starts_with(&root) {
+                    return Err(outside_err());
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return Err(outside_err()),
+        }
+    }
+    if !normalized.starts_with(&root) || normalized == root {
+        return Err(outside_err());
+    }
+
+    // Walk up to the deepest ancestor that exists and resolve symlinks there.
+    // `symlink_metadata` also stops on a dangling link, which then fails to
+    // canonicalize instead of being skipped and written through.
+    let mut existing = normalized.as_path();
+    let mut remainder = Vec::new();
+    while std::fs::symlink_metadata(existing).is_err() {
+        let Some(name) = existing.file_name() else {
+            return Err(outside_err());
+        };
+        remainder.push(name.to_os_string());
+        existing = existing.parent().ok_or_else(outside_err)?;
+    }
+
+    let mut resolved = std::fs::canonicalize(existing)
+        .map_err(|e| format!("Failed to resolve path {}: {}", path, e))?;
+    for name in remainder.into_iter().rev() {
+        resolved.push(name);
+    }
 
-    if !full_path.starts_with(&r_path) {
-        return Err("Invalid path: cannot write outside of repository".to_string());
+    if !resolved.starts_with(&root) {
+        return Err(outside_err());
     }
+    Ok(resolved)
+}
+
+/// Write `content` to `path` inside the repository, encoded according to the
+/// configured/overridden encoding. Refuses to write outside the repository.
+fn write_repo_file(
+    state: &State<'_, AppState>,
+    repo_path: &str,
+    path: &str,
+    content: &str,
+    encoding: Option<String>,
+) -> Result<(), String> {
+    use std::fs;
+
+    let full_path = resolve_path_within_repo(repo_path, path)?;
 
     let settings = state.settings.lock().map_err(|e| e.to_string())?;
-    let bytes = crate::git::encoding::encode_string(
-        &content,
-        Path::new(&path),
-        &settings,
-        encoding,
-    );
+    let bytes = crate::git::encoding::encode_string(content, Path::new(path), &settings, encoding);
 
     fs::write(&full_path, bytes).map_err(|e| format!("Failed to write file {}: {}", path, e))?;
 
     Ok(())
 }
+
+// ---------------------------------------------------------------------------
+// Maintenance Commands
+// ---------------------------------------------------------------------------
+
+/// Check the object database with `fsck --full`, streaming its progress as
+/// `git-progress` events.
+#[tauri::command]
+pub async fn cmd_git_fsck(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<maintenance_commands::FsckResult, String> {
+    maintenance_commands::cmd_git_fsck_impl(app, state, repo_path).await
+}
+
+/// Run `gc`, optionally `--aggressive`, and report the git directory's size
+/// before and after.
+#[tauri::command]
+pub async fn cmd_git_gc(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    aggressive: bool,
+    repo_path: Option<String>,
+) -> Result<maintenance_commands::GcResult, String> {
+    maintenance_commands::cmd_git_gc_impl(app, state, aggressive, repo_path).await
+}
+
+/// Disk usage of the repository and its working tree, with object counts.
+#[tauri::command]
+pub async fn cmd_get_repo_size(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<maintenance_commands::RepoSize, String> {
+    maintenance_commands::cmd_get_repo_size_impl(state, repo_path).await
+}
+
 // ---------------------------------------------------------------------------
 // Branch Management Commands
 // ---------------------------------------------------------------------------
@@ -1856,6 +3907,61 @@ pub async fn cmd_get_branch_tip(
     }
 }
 
+/// `merge-base` exits 1 with no output both when there is no common ancestor
+/// and when `--is-ancestor` is false; anything else is a real failure.
+async fn run_merge_base(
+    state: &State<'_, AppState>,
+    repo_path: Option<String>,
+    flags: &[&str],
+    a: &str,
+    b: &str,
+) -> Result<Option<String>, String> {
+    let path = resolve_repo_path(state, repo_path)?;
+    let (a, b) = (a.trim(), b.trim());
+    for r in [a, b] {
+        if r.is_empty() || r.starts_with('-') {
+            return Err(format!("Invalid ref: '{}'", r));
+        }
+    }
+    let mut args: Vec<String> = vec!["merge-base".into()];
+    args.extend(flags.iter().map(|f| f.to_string()));
+    args.extend([a.to_string(), b.to_string()]);
+
+    let resp = state
+        .git
+        .run_unchecked(Path::new(&path), &args, TIMEOUT_QUICK)
+        .await
+        .map_err(|e| e.to_string())?;
+    match resp.exit_code {
+        0 => Ok(Some(resp.stdout.trim().to_string())),
+        1 if resp.stderr.trim().is_empty() => Ok(None),
+        _ => Err(resp.stderr.trim().to_string()),
+    }
+}
+
+/// The best common ancestor of `a` and `b`, or `None` for unrelated histories.
+#[tauri::command]
+pub async fn cmd_get_merge_base(
+    state: State<'_, AppState>,
+    a: String,
+    b: String,
+    repo_path: Option<String>,
+) -> Result<Option<String>, String> {
+    run_merge_base(&state, repo_path, &[], &a, &b).await
+}
+
+/// Whether `a` is an ancestor of `b`, i.e. `b` can fast-forward from `a`.
+#[tauri::command]
+pub async fn cmd_is_ancestor(
+    state: State<'_, AppState>,
+    a: String,
+    b: String,
+    repo_path: Option<String>,
+) -> Result<bool, String> {
+    let result = run_merge_base(&state, repo_path, &["--is-ancestor"], &a, &b).await?;
+    Ok(result.is_some())
+}
+
 #[tauri::command]
 pub async fn cmd_get_git_branches(
     state: State<'_, AppState>,
@@ -1902,19 +4008,272 @@ pub async fn cmd_get_git_branches(
     Ok(branches)
 }
 
-#[tauri::command]
-pub async fn cmd_get_current_branch(
-    state: State<'_, AppState>,
+/// Split `branch -a --format=%(refname)` output into local and remote-tracking
+/// branch names, skipping the `origin/HEAD` symref.
+fn split_branch_refs(output: &str) -> (Vec<String>, Vec<String>) {
+    let mut branches = Vec::new();
+    let mut remote_branches = Vec::new();
+    for line in output.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix("refs/heads/") {
+            branches.push(name.to_string());
+        } else if let Some(name) = line.strip_prefix("refs/remotes/") {
+            if !name.ends_with("/HEAD") {
+                remote_branches.push(name.to_string());
+            }
+        }
+    }
+    (branches, remote_branches)
+}
+
+/// Which tags point at a commit and which branches (local and remote) contain
+/// it, for the commit details panel.
+#[tauri::command]
+pub async fn cmd_get_refs_for_commit(
+    state: State<'_, AppState>,
+    commit_hash: String,
     repo_path: Option<String>,
-) -> Result<String, String> {
+) -> Result<CommitRefs, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let commit_hash = commit_hash.trim();
+    if commit_hash.is_empty() || commit_hash.starts_with('-') {
+        return Err(format!("Invalid commit: '{}'", commit_hash));
+    }
+
+    let tag_args: Vec<String> = vec!["tag".into(), "--points-at".into(), commit_hash.to_string()];
+    let tags_resp = git_run_vec(&state, Some(path.clone()), tag_args, TIMEOUT_LOCAL).await?;
+    let tags = tags_resp
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let branch_args: Vec<String> = vec![
+        "branch".into(),
+        "-a".into(),
+        "--format=%(refname)".into(),
+        "--contains".into(),
+        commit_hash.to_string(),
+    ];
+    let branches_resp = git_run_vec(&state, Some(path), branch_args, TIMEOUT_LOCAL).await?;
+    let (branches, remote_branches) = split_branch_refs(&branches_resp.stdout);
+
+    Ok(CommitRefs {
+        tags,
+        branches,
+        remote_branches,
+    })
+}
+
+/// `for-each-ref` format for `BranchInfo`, NUL-separated with the subject last.
+const BRANCH_INFO_FORMAT: &str = "--format=%(HEAD)%00%(refname)%00%(upstream:short)%00\
+     %(upstream:track)%00%(objectname)%00%(committerdate:iso-strict)%00%(contents:subject)";
+
+/// Parse `%(upstream:track)`, e.g. "[ahead 2, behind 1]", into (ahead, behind).
+/// "[gone]" and an empty value are (0, 0).
+fn parse_upstream_track(track: &str) -> (u32, u32) {
+    let mut ahead = 0;
+    let mut behind = 0;
+    for part in track.trim_matches(|c| c == '[' || c == ']').split(", ") {
+        match part.split_once(' ') {
+            Some(("ahead", n)) => ahead = n.parse().unwrap_or(0),
+            Some(("behind", n)) => behind = n.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    (ahead, behind)
+}
+
+/// Parse one `BRANCH_INFO_FORMAT` line. Symbolic remote HEADs
+/// (`refs/remotes/origin/HEAD`) and non-branch refs are skipped.
+fn parse_branch_info_line(line: &str) -> Option<BranchInfo> {
+    let fields: Vec<&str> = line.splitn(7, '\0').collect();
+    let [head, refname, upstream, track, hash, date, subject] = fields[..] else {
+        return None;
+    };
+
+    let name = if let Some(local) = refname.strip_prefix("refs/heads/") {
+        local.to_string()
+    } else if let Some(remote) = refname.strip_prefix("refs/remotes/") {
+        if remote.ends_with("/HEAD") {
+            return None;
+        }
+        format!("remotes/{}", remote)
+    } else {
+        return None;
+    };
+
+    let (ahead, behind) = parse_upstream_track(track);
+    let upstream = Some(upstream.to_string()).filter(|u| !u.is_empty());
+    Some(BranchInfo {
+        name,
+        is_current: head == "*",
+        upstream_gone: upstream.is_some() && track.contains("gone"),
+        upstream,
+        last_commit_hash: hash.to_string(),
+        last_commit_date: date.to_string(),
+        last_commit_subject: subject.to_string(),
+        ahead,
+        behind,
+    })
+}
+
+/// Keep only branches whose name contains `filter` (case-insensitive), order
+/// them for `sort` and move the current branch to the front if it matched. `for-each-ref`
+/// has already sorted by name or date; only ahead/behind is sorted here.
+fn arrange_branches(
+    mut branches: Vec<BranchInfo>,
+    sort: BranchSort,
+    filter: Option<&str>,
+) -> Vec<BranchInfo> {
+    if let Some(filter) = filter.map(str::to_lowercase).filter(|f| !f.is_empty()) {
+        branches.retain(|b| b.name.to_lowercase().contains(&filter));
+    }
+    if sort == BranchSort::AheadBehind {
+        branches.sort_by_key(|b| std::cmp::Reverse(b.ahead + b.behind));
+    }
+    // Stable, so the order within each group is preserved
+    branches.sort_by_key(|b| !b.is_current);
+    branches
+}
+
+/// Branches with their tip commit and ahead/behind counts in one
+/// `for-each-ref` call. Most recently committed first unless `sort` says
+/// otherwise; the current branch is first if it matches `filter`.
+#[tauri::command]
+pub async fn cmd_get_branches_detailed(
+    state: State<'_, AppState>,
+    include_remote: bool,
+    sort: Option<BranchSort>,
+    filter: Option<String>,
+    repo_path: Option<String>,
+) -> Result<Vec<BranchInfo>, String> {
+    let sort = sort.unwrap_or_default();
+    let sort_arg = match sort {
+        BranchSort::Name => "--sort=refname",
+        // Date breaks ties between equally diverged branches
+        BranchSort::CommitDate | BranchSort::AheadBehind => "--sort=-committerdate",
+    };
+    let mut args = vec!["for-each-ref", sort_arg, BRANCH_INFO_FORMAT, "refs/heads"];
+    if include_remote {
+        args.push("refs/remotes");
+    }
+
+    let resp = git_run(&state, repo_path, &args, TIMEOUT_LOCAL).await?;
+    let branches = resp
+        .stdout
+        .lines()
+        .filter_map(parse_branch_info_line)
+        .collect();
+    Ok(arrange_branches(branches, sort, filter.as_deref()))
+}
+
+#[tauri::command]
+pub async fn cmd_get_current_branch(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<CurrentBranch, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
     let resp = git_run(
         &state,
-        repo_path,
+        Some(path.clone()),
         &["branch", "--show-current"],
         TIMEOUT_QUICK,
     )
     .await?;
-    Ok(resp.stdout.trim().to_string())
+    let branch = resp.stdout.trim();
+    if !branch.is_empty() {
+        return Ok(CurrentBranch {
+            branch: Some(branch.to_string()),
+            detached: false,
+            describe: None,
+        });
+    }
+
+    // `--show-current` prints nothing only when HEAD is detached
+    let args = ["rev-parse", "--short", "HEAD"];
+    let resp = git_run(&state, Some(path), &args, TIMEOUT_QUICK).await?;
+    Ok(CurrentBranch {
+        branch: None,
+        detached: true,
+        describe: Some(resp.stdout.trim().to_string()),
+    })
+}
+
+/// Branch, commit and upstream state of HEAD in one snapshot, so the header
+/// doesn't combine answers from separate polls.
+#[tauri::command]
+pub async fn cmd_get_head_info(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<HeadInfo, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let repo = Path::new(&path);
+
+    // Silent exit 1: `rev-parse` on an unborn branch, `symbolic-ref` on a
+    // detached HEAD
+    let commit_args: Vec<String> = vec![
+        "rev-parse".into(),
+        "--verify".into(),
+        "-q".into(),
+        "HEAD".into(),
+    ];
+    let branch_args: Vec<String> = vec![
+        "symbolic-ref".into(),
+        "-q".into(),
+        "--short".into(),
+        "HEAD".into(),
+    ];
+    let (commit_resp, branch_resp) = tokio::try_join!(
+        state.git.run_unchecked(repo, &commit_args, TIMEOUT_QUICK),
+        state.git.run_unchecked(repo, &branch_args, TIMEOUT_QUICK),
+    )
+    .map_err(|e| e.to_string())?;
+
+    let commit = commit_resp.stdout.trim().to_string();
+    let branch = Some(branch_resp.stdout.trim().to_string())
+        .filter(|b| branch_resp.exit_code == 0 && !b.is_empty());
+
+    let mut short_commit = String::new();
+    if !commit.is_empty() {
+        // Abbreviate the commit already read, not whatever HEAD is now
+        let args: Vec<String> = vec!["rev-parse".into(), "--short".into(), commit.clone()];
+        let resp = state
+            .git
+            .run_unchecked(repo, &args, TIMEOUT_QUICK)
+            .await
+            .map_err(|e| e.to_string())?;
+        short_commit = resp.stdout.trim().to_string();
+    }
+
+    let (mut upstream, mut ahead, mut behind) = (None, 0, 0);
+    if let Some(branch) = &branch {
+        let args: Vec<String> = vec![
+            "for-each-ref".into(),
+            "--format=%(upstream:short)%00%(upstream:track)".into(),
+            format!("refs/heads/{}", branch),
+        ];
+        let resp = state
+            .git
+            .run_unchecked(repo, &args, TIMEOUT_QUICK)
+            .await
+            .map_err(|e| e.to_string())?;
+        if let Some((name, track)) = resp.stdout.trim_end().split_once('\0') {
+            upstream = Some(name.to_string()).filter(|u| !u.is_empty());
+            (ahead, behind) = parse_upstream_track(track);
+        }
+    }
+
+    Ok(HeadInfo {
+        detached: branch.is_none(),
+        branch,
+        commit,
+        short_commit,
+        upstream,
+        ahead,
+        behind,
+    })
 }
 
 #[tauri::command]
@@ -1923,7 +4282,7 @@ pub async fn cmd_git_switch_branch(
     state: State<'_, AppState>,
     branch_name: String,
     repo_path: Option<String>,
-) -> Result<GitCommandResult, String> {
+) -> CommandResult<GitCommandResult> {
     let mut target = branch_name.as_str();
 
     // Handle remote branches (e.g., "remotes/origin/main" -> "main")
@@ -1937,16 +4296,111 @@ pub async fn cmd_git_switch_branch(
         }
     }
 
-    let args: Vec<String> = vec!["switch".into(), target.to_string()];
-    git_run_result_with_event(
-        &app,
+    // `git switch` only exists since 2.23; `checkout` does the same DWIM here
+    let subcommand = if state.git.supports_switch().await {
+        "switch"
+    } else {
+        "checkout"
+    };
+    let args: Vec<String> = vec![subcommand.into(), target.to_string()];
+    let resp = git_run_typed(&state, repo_path, args, TIMEOUT_LOCAL).await?;
+    emit_git_change_event(&app)?;
+    Ok(map_git_result(resp, GitCommandType::Checkout))
+}
+
+/// Check out a commit for inspection, detaching HEAD.
+#[tauri::command]
+pub async fn cmd_git_checkout_commit(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    commit_hash: String,
+    repo_path: Option<String>,
+) -> CommandResult<CheckoutCommitResult> {
+    let commit_hash = commit_hash.trim().to_string();
+    if commit_hash.is_empty() || commit_hash.starts_with('-') {
+        return Err(CommandError::InvalidInput(format!(
+            "Invalid commit: {}",
+            commit_hash
+        )));
+    }
+
+    let path = resolve_repo_path(&state, repo_path)?;
+    // `--detach` so a hash that happens to match a branch name still detaches
+    let args: Vec<String> = vec!["checkout".into(), "--detach".into(), commit_hash.clone()];
+    let resp = state
+        .git
+        .run(Path::new(&path), &args, TIMEOUT_LOCAL)
+        .await
+        .map_err(|e| match e {
+            GitError::CommandError(msg) if msg.contains("would be overwritten by checkout") => {
+                CommandError::GitCommandFailed(format!(
+                    "Cannot check out {}: uncommitted changes would be overwritten. \
+                     Commit or stash them first.\n{}",
+                    commit_hash, msg
+                ))
+            }
+            other => other.into(),
+        })?;
+
+    // `symbolic-ref -q HEAD` fails exactly when HEAD is detached
+    let detached = git_run(
         &state,
-        repo_path,
-        args,
-        TIMEOUT_LOCAL,
-        GitCommandType::Checkout,
+        Some(path),
+        &["symbolic-ref", "-q", "HEAD"],
+        TIMEOUT_QUICK,
+    )
+    .await
+    .is_err();
+
+    emit_git_change_event(&app)?;
+    Ok(CheckoutCommitResult {
+        result: map_git_result(resp, GitCommandType::Checkout),
+        detached,
+    })
+}
+
+/// Leave detached HEAD by switching back to a local branch.
+#[tauri::command]
+pub async fn cmd_git_reattach(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    branch_name: String,
+    repo_path: Option<String>,
+) -> CommandResult<GitCommandResult> {
+    let branch_name = branch_name.trim().to_string();
+    if branch_name.is_empty() || branch_name.starts_with('-') {
+        return Err(CommandError::InvalidInput(format!(
+            "Invalid branch name: {}",
+            branch_name
+        )));
+    }
+
+    let path = resolve_repo_path(&state, repo_path)?;
+    let branch_ref = format!("refs/heads/{}", branch_name);
+    if git_run(
+        &state,
+        Some(path.clone()),
+        &["show-ref", "--verify", "--quiet", &branch_ref],
+        TIMEOUT_QUICK,
     )
     .await
+    .is_err()
+    {
+        return Err(CommandError::InvalidInput(format!(
+            "Local branch '{}' does not exist",
+            branch_name
+        )));
+    }
+
+    let subcommand = if state.git.supports_switch().await {
+        "switch"
+    } else {
+        "checkout"
+    };
+    let args: Vec<String> = vec![subcommand.into(), branch_name];
+    let resp = git_run_typed(&state, Some(path), args, TIMEOUT_LOCAL).await?;
+    emit_git_change_event(&app)?;
+    Ok(map_git_result(resp, GitCommandType::Checkout))
 }
 
 #[tauri::command]
@@ -1991,13 +4445,71 @@ pub async fn cmd_git_merge(
     app: AppHandle,
     state: State<'_, AppState>,
     branch: String,
+    no_ff: Option<bool>,
+    squash: Option<bool>,
+    message: Option<String>,
+    repo_path: Option<String>,
+) -> CommandResult<GitCommandResult> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let squash = squash.unwrap_or(false);
+
+    let mut args: Vec<String> = vec!["merge".into()];
+    if no_ff.unwrap_or(false) {
+        args.push("--no-ff".into());
+    }
+    if squash {
+        args.push("--squash".into());
+    }
+    if let Some(msg) = message.filter(|m| !m.trim().is_empty()) {
+        args.push("-m".into());
+        args.push(msg);
+    }
+    args.push(branch);
+
+    let result = match state.git.run(Path::new(&path), &args, TIMEOUT_LOCAL).await {
+        Ok(mut resp) => {
+            if squash && resp.exit_code == 0 {
+                // --squash never creates a commit; the result sits in the index
+                if !resp.stdout.is_empty() && !resp.stdout.ends_with('\n') {
+                    resp.stdout.push('\n');
+                }
+                resp.stdout
+                    .push_str("Squash merge complete: changes are staged and ready to commit.\n");
+            }
+            map_git_result(resp, GitCommandType::Merge)
+        }
+        // Conflicts are an expected outcome of a merge — report them as a result
+        Err(GitError::MergeConflict) => GitCommandResult {
+            success: false,
+            stdout: String::new(),
+            stderr: "CONFLICT: merge conflicts detected".into(),
+            exit_code: 1,
+            command_type: GitCommandType::Merge,
+        },
+        Err(e) => return Err(e.into()),
+    };
+
+    emit_git_change_event(&app)?;
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn cmd_git_merge_abort(
+    app: AppHandle,
+    state: State<'_, AppState>,
     repo_path: Option<String>,
 ) -> Result<GitCommandResult, String> {
-    let args: Vec<String> = vec!["merge".into(), branch];
+    let path = resolve_repo_path(&state, repo_path)?;
+    let git_dir = resolve_git_dir(Path::new(&path));
+    if !git_dir.join("MERGE_HEAD").exists() {
+        return Err("No merge is in progress, so there is nothing to abort.".to_string());
+    }
+
+    let args: Vec<String> = vec!["merge".into(), "--abort".into()];
     git_run_result_with_event(
         &app,
         &state,
-        repo_path,
+        Some(path),
         args,
         TIMEOUT_LOCAL,
         GitCommandType::Merge,
@@ -2005,6 +4517,244 @@ pub async fn cmd_git_merge(
     .await
 }
 
+/// Run a merge-like command (`merge --no-commit`, `cherry-pick --no-commit`)
+/// to see whether it would conflict, then throw the result away with
+/// `reset --merge`. Needs a clean tree so the reset cannot lose user work.
+async fn dry_run_in_worktree(
+    state: &State<'_, AppState>,
+    repo_path: &str,
+    args: Vec<String>,
+) -> Result<MergeDryRunResult, String> {
+    let repo = Some(repo_path.to_string());
+    let status = git_run(
+        state,
+        repo.clone(),
+        &["status", "--porcelain", "--untracked-files=no"],
+        TIMEOUT_LOCAL,
+    )
+    .await?;
+    if !status.stdout.trim().is_empty() {
+        return Err("Commit or stash your changes before previewing this operation.".to_string());
+    }
+
+    let outcome = state
+        .git
+        .run(Path::new(repo_path), &args, TIMEOUT_LOCAL)
+        .await;
+    let conflicting_files = match &outcome {
+        Err(GitError::MergeConflict) => git_run(
+            state,
+            repo.clone(),
+            &["diff", "--name-only", "--diff-filter=U", "-z"],
+            TIMEOUT_LOCAL,
+        )
+        .await?
+        .stdout
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(str::to_string)
+        .collect(),
+        _ => Vec::new(),
+    };
+
+    // Also clears MERGE_HEAD / CHERRY_PICK_HEAD
+    git_run(state, repo, &["reset", "--merge", "HEAD"], TIMEOUT_LOCAL).await?;
+
+    match outcome {
+        Ok(_) => Ok(MergeDryRunResult::default()),
+        Err(GitError::MergeConflict) => Ok(MergeDryRunResult {
+            would_conflict: true,
+            conflicting_files,
+        }),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Report whether merging `branch` into HEAD would conflict, leaving the
+/// repository as it was.
+#[tauri::command]
+pub async fn cmd_git_merge_dry_run(
+    state: State<'_, AppState>,
+    branch: String,
+    repo_path: Option<String>,
+) -> Result<MergeDryRunResult, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let branch = branch.trim();
+    if branch.is_empty() || branch.starts_with('-') {
+        return Err(format!("Invalid branch: '{}'", branch));
+    }
+
+    let args: Vec<String> = vec![
+        "merge".into(),
+        "--no-commit".into(),
+        "--no-ff".into(),
+        branch.to_string(),
+    ];
+    dry_run_in_worktree(&state, &path, args).await
+}
+
+/// Conflicted paths from `git merge-tree --write-tree --name-only -z`: the
+/// tree OID comes first, then one path per entry up to an empty entry that
+/// starts the (suppressed) informational messages.
+fn parse_merge_tree_conflicts(output: &str) -> Vec<String> {
+    output
+        .split('\0')
+        .skip(1)
+        .take_while(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Preflight check for the merge button: would merging `branch` into HEAD
+/// conflict? Uses `merge-tree --write-tree`, which never touches the working
+/// tree or index; older git falls back to a dry-run merge.
+#[tauri::command]
+pub async fn cmd_can_merge_cleanly(
+    state: State<'_, AppState>,
+    branch: String,
+    repo_path: Option<String>,
+) -> Result<MergeCheckResult, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let branch = branch.trim();
+    if branch.is_empty() || branch.starts_with('-') {
+        return Err(format!("Invalid branch: '{}'", branch));
+    }
+
+    if !state.git.supports_merge_tree_write_tree().await {
+        let args: Vec<String> = vec![
+            "merge".into(),
+            "--no-commit".into(),
+            "--no-ff".into(),
+            branch.to_string(),
+        ];
+        let dry_run = dry_run_in_worktree(&state, &path, args).await?;
+        return Ok(MergeCheckResult {
+            clean: !dry_run.would_conflict,
+            conflicting_files: dry_run.conflicting_files,
+        });
+    }
+
+    let args: Vec<String> = vec![
+        "merge-tree".into(),
+        "--write-tree".into(),
+        "--name-only".into(),
+        "--no-messages".into(),
+        "-z".into(),
+        "HEAD".into(),
+        branch.to_string(),
+    ];
+    let resp = state
+        .git
+        .run_unchecked(Path::new(&path), &args, TIMEOUT_LOCAL)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // 0 = clean, 1 = conflicts, anything else is a real failure
+    match resp.exit_code {
+        0 => Ok(MergeCheckResult {
+            clean: true,
+            conflicting_files: Vec::new(),
+        }),
+        1 => Ok(MergeCheckResult {
+            clean: false,
+            conflicting_files: parse_merge_tree_conflicts(&resp.stdout),
+        }),
+        code => Err(format!(
+            "git merge-tree failed (exit {}): {}",
+            code,
+            resp.stderr.trim()
+        )),
+    }
+}
+
+/// Report whether cherry-picking `commit_hash` onto HEAD would conflict,
+/// leaving the repository as it was.
+#[tauri::command]
+pub async fn cmd_git_cherry_pick_dry_run(
+    state: State<'_, AppState>,
+    commit_hash: String,
+    repo_path: Option<String>,
+) -> Result<MergeDryRunResult, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let commit = commit_hash.trim();
+    if commit.is_empty() || commit.starts_with('-') {
+        return Err(format!("Invalid commit: '{}'", commit));
+    }
+
+    let args: Vec<String> = vec![
+        "cherry-pick".into(),
+        "--no-commit".into(),
+        commit.to_string(),
+    ];
+    dry_run_in_worktree(&state, &path, args).await
+}
+
+/// Report whether rebasing HEAD onto `branch` would stop on a conflict. The
+/// rebase runs on a detached copy of HEAD in a temporary worktree, so the
+/// branch, index and working tree are never touched.
+#[tauri::command]
+pub async fn cmd_git_rebase_dry_run(
+    state: State<'_, AppState>,
+    branch: String,
+    repo_path: Option<String>,
+) -> Result<MergeDryRunResult, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let branch = branch.trim();
+    if branch.is_empty() || branch.starts_with('-') {
+        return Err(format!("Invalid branch: '{}'", branch));
+    }
+
+    let worktree =
+        std::env::temp_dir().join(format!("git-tools-rebase-preview-{}", Uuid::new_v4()));
+    let worktree_arg = worktree.to_string_lossy().to_string();
+    git_run(
+        &state,
+        Some(path.clone()),
+        &["worktree", "add", "--detach", &worktree_arg, "HEAD"],
+        TIMEOUT_LOCAL,
+    )
+    .await?;
+
+    let args: Vec<String> = vec!["rebase".into(), branch.to_string()];
+    let outcome = state.git.run(&worktree, &args, TIMEOUT_LOCAL).await;
+    let conflicting_files = match &outcome {
+        Err(GitError::MergeConflict) => git_run(
+            &state,
+            Some(worktree_arg.clone()),
+            &["diff", "--name-only", "--diff-filter=U", "-z"],
+            TIMEOUT_LOCAL,
+        )
+        .await
+        .map(|resp| {
+            resp.stdout
+                .split('\0')
+                .filter(|p| !p.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+
+    // Also drops the stopped rebase, whose state lives in the worktree's git dir
+    git_run(
+        &state,
+        Some(path),
+        &["worktree", "remove", "--force", &worktree_arg],
+        TIMEOUT_LOCAL,
+    )
+    .await?;
+
+    match outcome {
+        Ok(_) => Ok(MergeDryRunResult::default()),
+        Err(GitError::MergeConflict) => Ok(MergeDryRunResult {
+            would_conflict: true,
+            conflicting_files,
+        }),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
 #[tauri::command]
 pub async fn cmd_git_rebase(
     app: AppHandle,
@@ -2043,14 +4793,50 @@ pub async fn cmd_git_cherry_pick(
     .await
 }
 
+/// Cherry-pick every commit in `from..to` (so `from` itself is excluded). A
+/// conflict part-way through leaves the sequence paused and is reported as an
+/// unsuccessful result; `cmd_abort_operation` backs the whole range out.
 #[tauri::command]
-pub async fn cmd_abort_operation(
+pub async fn cmd_cherry_pick_range(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    from: String,
+    to: String,
+    repo_path: Option<String>,
+) -> CommandResult<GitCommandResult> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let (from, to) = (from.trim(), to.trim());
+    let is_ancestor = run_merge_base(&state, Some(path.clone()), &["--is-ancestor"], from, to)
+        .await?
+        .is_some();
+    if !is_ancestor {
+        return Err(format!("'{}' is not an ancestor of '{}'", from, to).into());
+    }
+
+    let args: Vec<String> = vec!["cherry-pick".into(), format!("{}..{}", from, to)];
+    let result = match state.git.run(Path::new(&path), &args, TIMEOUT_LOCAL).await {
+        Ok(resp) => map_git_result(resp, GitCommandType::CherryPick),
+        Err(GitError::MergeConflict) => GitCommandResult {
+            success: false,
+            stdout: String::new(),
+            stderr: "CONFLICT: cherry-pick stopped on a conflicting commit".into(),
+            exit_code: 1,
+            command_type: GitCommandType::CherryPick,
+        },
+        Err(e) => return Err(e.into()),
+    };
+    emit_git_change_event(&app)?;
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn cmd_abort_operation(
     app: AppHandle,
     state: State<'_, AppState>,
     repo_path: Option<String>,
 ) -> Result<GitCommandResult, String> {
     let path = resolve_repo_path(&state, repo_path)?;
-    let git_dir = Path::new(&path).join(".git");
+    let git_dir = resolve_git_dir(Path::new(&path));
 
     let is_merging = git_dir.join("MERGE_HEAD").exists();
     let is_rebasing = git_dir.join("REBASE_HEAD").exists()
@@ -2059,27 +4845,30 @@ pub async fn cmd_abort_operation(
     let is_cherry_picking = git_dir.join("CHERRY_PICK_HEAD").exists();
     let is_reverting = git_dir.join("REVERT_HEAD").exists();
 
-    let args: Vec<String> = if is_rebasing {
-        vec!["rebase".into(), "--abort".into()]
+    let (operation, command_type) = if is_rebasing {
+        ("rebase", GitCommandType::Rebase)
     } else if is_merging {
-        vec!["merge".into(), "--abort".into()]
+        ("merge", GitCommandType::Merge)
     } else if is_cherry_picking {
-        vec!["cherry-pick".into(), "--abort".into()]
+        ("cherry-pick", GitCommandType::CherryPick)
     } else if is_reverting {
-        vec!["revert".into(), "--abort".into()]
+        ("revert", GitCommandType::Revert)
     } else {
         return Err("No merge/rebase/cherry-pick/revert operation is in progress.".to_string());
     };
 
-    git_run_result_with_event(
-        &app,
-        &state,
-        Some(path),
-        args,
-        TIMEOUT_LOCAL,
-        GitCommandType::Other,
-    )
-    .await
+    let args: Vec<String> = vec![operation.into(), "--abort".into()];
+    git_run_result_with_event(&app, &state, Some(path), args, TIMEOUT_LOCAL, command_type).await
+}
+
+/// Abort whichever operation is in progress, including a bisect.
+#[tauri::command]
+pub async fn cmd_abort_current_operation(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<conflict_commands::AbortedOperation, String> {
+    conflict_commands::cmd_abort_current_operation_impl(app, state, repo_path).await
 }
 
 #[tauri::command]
@@ -2106,9 +4895,77 @@ pub async fn cmd_git_revert(
         Some(path),
         args,
         TIMEOUT_LOCAL,
-        GitCommandType::Other,
+        GitCommandType::Revert,
+    )
+    .await
+}
+
+/// Describe what resetting to `commit_hash` with `mode` would leave behind:
+/// commits no longer on the current branch and, for `hard`, files whose
+/// uncommitted changes would be lost. Nothing is changed.
+#[tauri::command]
+pub async fn cmd_git_reset_dry_run(
+    state: State<'_, AppState>,
+    commit_hash: String,
+    mode: String,
+    repo_path: Option<String>,
+) -> Result<ResetDryRunResult, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let target_commit = commit_hash.trim();
+    if target_commit.is_empty() || target_commit.starts_with('-') {
+        return Err(format!("Invalid commit: '{}'", target_commit));
+    }
+    let normalized_mode = mode.trim().to_lowercase();
+    if !matches!(normalized_mode.as_str(), "soft" | "mixed" | "hard") {
+        return Err("Invalid reset mode. Expected soft, mixed, or hard.".to_string());
+    }
+
+    let verify = format!("{}^{{commit}}", target_commit);
+    git_run(
+        &state,
+        Some(path.clone()),
+        &["rev-parse", "--verify", "-q", &verify],
+        TIMEOUT_QUICK,
     )
     .await
+    .map_err(|_| format!("Unknown commit: '{}'", target_commit))?;
+
+    let range = format!("{}..HEAD", target_commit);
+    let count = git_run(
+        &state,
+        Some(path.clone()),
+        &["rev-list", "--count", &range],
+        TIMEOUT_LOCAL,
+    )
+    .await?;
+    let commits_left_behind = count
+        .stdout
+        .trim()
+        .parse::<u32>()
+        .map_err(|e| format!("Unexpected rev-list output: {}", e))?;
+
+    // Soft and mixed resets keep the working tree as it is
+    let discarded_files = if normalized_mode == "hard" {
+        git_run(
+            &state,
+            Some(path),
+            &["diff", "--name-only", "-z", "HEAD"],
+            TIMEOUT_LOCAL,
+        )
+        .await?
+        .stdout
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(str::to_string)
+        .collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(ResetDryRunResult {
+        commits_left_behind,
+        discarded_files,
+    })
 }
 
 #[tauri::command]
@@ -2118,6 +4975,7 @@ pub async fn cmd_git_reset(
     commit_hash: String,
     mode: String,
     repo_path: Option<String>,
+    confirmation_token: Option<String>,
 ) -> Result<GitCommandResult, String> {
     let path = resolve_repo_path(&state, repo_path)?;
     let target_commit = commit_hash.trim();
@@ -2130,6 +4988,15 @@ pub async fn cmd_git_reset(
         return Err("Invalid reset mode. Expected soft, mixed, or hard.".to_string());
     }
 
+    if normalized_mode == "hard" {
+        check_destructive_confirmation(
+            &state,
+            "reset_hard",
+            &[target_commit.to_string()],
+            confirmation_token.as_deref(),
+        )?;
+    }
+
     let mode_flag = format!("--{}", normalized_mode);
     let args: Vec<String> = vec!["reset".into(), mode_flag, target_commit.to_string()];
     git_run_result_with_event(
@@ -2138,7 +5005,7 @@ pub async fn cmd_git_reset(
         Some(path),
         args,
         TIMEOUT_LOCAL,
-        GitCommandType::Other,
+        GitCommandType::Reset,
     )
     .await
 }
@@ -2190,7 +5057,7 @@ pub async fn cmd_git_create_tag(
         Some(path),
         args,
         TIMEOUT_LOCAL,
-        GitCommandType::Branch,
+        GitCommandType::Tag,
     )
     .await
 }
@@ -2222,6 +5089,95 @@ pub async fn cmd_git_delete_branch(
     .await
 }
 
+/// Branches `cmd_get_merged_branches` never offers for cleanup.
+const PROTECTED_BRANCHES: &[&str] = &["main", "master", "develop"];
+
+/// Parse `git branch --format=%(HEAD)%(refname:short)`, dropping the current
+/// branch, the `into` target, a detached HEAD entry and protected branches.
+fn parse_merged_branches(output: &str, into: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter(|line| !line.starts_with('*'))
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && !name.starts_with('('))
+        .filter(|name| *name != into && !PROTECTED_BRANCHES.contains(name))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Local branches fully merged into `into` (the current branch by default)
+/// that are safe to delete.
+#[tauri::command]
+pub async fn cmd_get_merged_branches(
+    state: State<'_, AppState>,
+    into: Option<String>,
+    repo_path: Option<String>,
+) -> Result<Vec<String>, String> {
+    let target = into
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or("HEAD");
+    if target.starts_with('-') {
+        return Err("Branch name cannot start with '-'".to_string());
+    }
+    let resp = git_run(
+        &state,
+        repo_path,
+        &[
+            "branch",
+            "--format=%(HEAD)%(refname:short)",
+            "--merged",
+            target,
+        ],
+        TIMEOUT_LOCAL,
+    )
+    .await?;
+    Ok(parse_merged_branches(&resp.stdout, target))
+}
+
+/// Delete several local branches in one `git branch -d`. Unmerged branches
+/// are left in place and reported by git; protected branches are refused.
+#[tauri::command]
+pub async fn cmd_delete_branches(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    names: Vec<String>,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let names: Vec<String> = names
+        .iter()
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+    if names.is_empty() {
+        return Err("No branch names provided".to_string());
+    }
+    if names.iter().any(|name| name.starts_with('-')) {
+        return Err("Branch names cannot start with '-'".to_string());
+    }
+    if let Some(name) = names
+        .iter()
+        .find(|name| PROTECTED_BRANCHES.contains(&name.as_str()))
+    {
+        return Err(format!("Refusing to delete protected branch '{}'", name));
+    }
+
+    let mut args: Vec<String> = vec!["branch".into(), "-d".into()];
+    args.extend(names);
+    git_run_result_with_event(
+        &app,
+        &state,
+        Some(path),
+        args,
+        TIMEOUT_LOCAL,
+        GitCommandType::Branch,
+    )
+    .await
+}
+
 #[tauri::command]
 pub async fn cmd_git_delete_remote_branch(
     app: AppHandle,
@@ -2237,21 +5193,84 @@ pub async fn cmd_git_delete_remote_branch(
     if target_remote.is_empty() || target_branch.is_empty() {
         return Err("Remote and branch name are required".to_string());
     }
+    if target_remote.starts_with('-') || target_branch.starts_with('-') {
+        return Err("Remote and branch name cannot start with '-'".to_string());
+    }
 
     let args: Vec<String> = vec![
         "push".into(),
         target_remote.to_string(),
-        format!(":{}", target_branch),
+        "--delete".into(),
+        target_branch.to_string(),
     ];
-    git_run_result_with_event(
-        &app,
+    let result = state
+        .git
+        .run(Path::new(&path), &args, TIMEOUT_NETWORK)
+        .await;
+    let resp = match result {
+        Ok(resp) => resp,
+        Err(GitError::CommandError(msg)) if msg.contains("remote ref does not exist") => {
+            // Already gone on the remote (e.g. deleted when its PR was merged);
+            // drop the stale remote-tracking branch so the tree matches
+            let tracking_ref = format!("refs/remotes/{}/{}", target_remote, target_branch);
+            let update_args: Vec<String> = vec!["update-ref".into(), "-d".into(), tracking_ref];
+            let _ = state
+                .git
+                .run(Path::new(&path), &update_args, TIMEOUT_QUICK)
+                .await;
+            emit_git_change_event(&app)?;
+            return Ok(GitCommandResult {
+                success: true,
+                stdout: format!(
+                    "'{}/{}' no longer exists on the remote",
+                    target_remote, target_branch
+                ),
+                stderr: String::new(),
+                exit_code: 0,
+                command_type: GitCommandType::Push,
+            });
+        }
+        Err(e) => return Err(redact_secrets(&e.to_string())),
+    };
+
+    emit_git_change_event(&app)?;
+    Ok(map_git_result(resp, GitCommandType::Push))
+}
+
+/// Remote-tracking refs listed as ` * [pruned] origin/branch` by `git remote
+/// prune`.
+fn parse_pruned_refs(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("* [pruned] "))
+        .map(|name| name.trim().to_string())
+        .collect()
+}
+
+/// Delete remote-tracking branches whose branch no longer exists on `remote`,
+/// without fetching anything. Returns the pruned ref names.
+#[tauri::command]
+pub async fn cmd_prune_remote(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    remote: String,
+    repo_path: Option<String>,
+) -> Result<Vec<String>, String> {
+    let remote = remote.trim();
+    if remote.is_empty() || remote.starts_with('-') {
+        return Err(format!("Invalid remote name: '{}'", remote));
+    }
+
+    let resp = git_run(
         &state,
-        Some(path),
-        args,
+        repo_path,
+        &["remote", "prune", remote],
         TIMEOUT_NETWORK,
-        GitCommandType::Push,
     )
     .await
+    .map_err(|e| redact_secrets(&e))?;
+    emit_git_change_event(&app)?;
+    Ok(parse_pruned_refs(&resp.stdout))
 }
 
 #[tauri::command]
@@ -2313,7 +5332,7 @@ pub async fn cmd_git_set_upstream(
         Some(path),
         args,
         TIMEOUT_LOCAL,
-        GitCommandType::Other,
+        GitCommandType::Branch,
     )
     .await
 }
@@ -2323,21 +5342,32 @@ pub async fn cmd_get_file_history(
     state: State<'_, AppState>,
     file_path: String,
     limit: Option<u32>,
+    with_patch: Option<bool>,
     repo_path: Option<String>,
 ) -> Result<Vec<FileCommit>, String> {
     let path = resolve_repo_path(&state, repo_path)?;
     let limit = limit.unwrap_or(100);
+    let with_patch = with_patch.unwrap_or(false);
 
-    // git log --follow --format="%H|%an|%ad|%s" --date=short -n <limit> -- <file>
-    let args = vec![
+    // git log --follow --format="%H|%an|%ad|%s" --date=<format> -n <limit> -- <file>
+    // With patches, each record starts with a \x1e marker so the metadata line
+    // can be told apart from the diff that follows it.
+    let mut args = vec![
         "log".to_string(),
         "--follow".to_string(),
-        format!("--format=%H|%an|%ad|%s"),
-        "--date=short".to_string(),
+        if with_patch {
+            "--format=%x1e%H|%an|%ad|%s".to_string()
+        } else {
+            "--format=%H|%an|%ad|%s".to_string()
+        },
+        load_date_arg(&state, HISTORY_DATE_ARG)?.to_string(),
         format!("-n{}", limit),
-        "--".to_string(),
-        file_path,
     ];
+    if with_patch {
+        args.push("-p".to_string());
+    }
+    args.push("--".to_string());
+    args.push(file_path);
 
     let resp = state
         .git
@@ -2345,21 +5375,83 @@ pub async fn cmd_get_file_history(
         .await
         .map_err(|e| e.to_string())?;
 
-    let mut commits = Vec::new();
+    let commits = if with_patch {
+        parse_file_commits_with_patch(&resp.stdout)
+    } else {
+        resp.stdout
+            .lines()
+            .filter_map(parse_file_commit_line)
+            .collect()
+    };
 
-    for line in resp.stdout.lines() {
-        let parts: Vec<&str> = line.split('|').collect();
-        if parts.len() >= 4 {
-            commits.push(FileCommit {
-                hash: parts[0].to_string(),
-                author: parts[1].to_string(),
-                date: parts[2].to_string(),
-                message: parts[3..].join("|"), // Rejoin message in case it contained pipes
-            });
-        }
+    Ok(commits)
+}
+
+#[tauri::command]
+pub async fn cmd_get_line_history(
+    state: State<'_, AppState>,
+    file_path: String,
+    start_line: u32,
+    end_line: u32,
+    encoding: Option<String>,
+    repo_path: Option<String>,
+) -> Result<Vec<FileCommit>, String> {
+    if start_line == 0 || start_line > end_line {
+        return Err(format!(
+            "Invalid line range {}-{}: lines start at 1 and the start must not exceed the end",
+            start_line, end_line
+        ));
     }
+    let path = resolve_repo_path(&state, repo_path)?;
 
-    Ok(commits)
+    // git log -L<start>,<end>:<file> always emits the patch for the range
+    let args = vec![
+        "log".to_string(),
+        format!("-L{},{}:{}", start_line, end_line, file_path),
+        "--format=%x1e%H|%an|%ad|%s".to_string(),
+        load_date_arg(&state, HISTORY_DATE_ARG)?.to_string(),
+    ];
+    let resp = state
+        .git
+        .run_with_output_bytes(Path::new(&path), &args, TIMEOUT_LOCAL)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let stdout = {
+        let settings = state.settings.lock().map_err(|e| e.to_string())?;
+        crate::git::encoding::decode_bytes(&resp.stdout, Path::new(&file_path), &settings, encoding)
+    };
+
+    Ok(parse_file_commits_with_patch(&stdout))
+}
+
+/// Parse `git log -p` output where every record starts with `\x1e` followed
+/// by the `%H|%an|%ad|%s` line and then the patch for the file.
+fn parse_file_commits_with_patch(stdout: &str) -> Vec<FileCommit> {
+    stdout
+        .split('\x1e')
+        .filter(|record| !record.trim().is_empty())
+        .filter_map(|record| {
+            let (header, patch) = record.split_once('\n').unwrap_or((record, ""));
+            let mut commit = parse_file_commit_line(header)?;
+            commit.patch = parse_diff_output(patch).into_iter().next();
+            Some(commit)
+        })
+        .collect()
+}
+
+fn parse_file_commit_line(line: &str) -> Option<FileCommit> {
+    let parts: Vec<&str> = line.split('|').collect();
+    if parts.len() < 4 {
+        return None;
+    }
+    Some(FileCommit {
+        hash: parts[0].to_string(),
+        author: parts[1].to_string(),
+        date: parts[2].to_string(),
+        message: parts[3..].join("|"), // Rejoin message in case it contained pipes
+        patch: None,
+    })
 }
 
 #[tauri::command]
@@ -2403,6 +5495,174 @@ pub async fn cmd_search_repo_files(
     Ok(files)
 }
 
+/// Every file in HEAD as a nested tree, for the sidebar file browser.
+#[tauri::command]
+pub async fn cmd_get_tracked_file_tree(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<Vec<TreeNode>, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+
+    // A freshly initialised repo has no HEAD yet, and so no tracked files
+    if git_run(
+        &state,
+        Some(path.clone()),
+        &["rev-parse", "--verify", "-q", "HEAD"],
+        TIMEOUT_QUICK,
+    )
+    .await
+    .is_err()
+    {
+        return Ok(Vec::new());
+    }
+
+    let resp = git_run(
+        &state,
+        Some(path),
+        &["ls-tree", "-r", "-z", "--name-only", "HEAD"],
+        TIMEOUT_LOCAL,
+    )
+    .await?;
+
+    Ok(build_file_tree(
+        resp.stdout.split('\0').filter(|p| !p.is_empty()),
+    ))
+}
+
+/// Immediate children of one directory, so the file browser can expand
+/// lazily instead of loading the whole tree.
+#[tauri::command]
+pub async fn cmd_get_directory_contents(
+    state: State<'_, AppState>,
+    dir_path: String,
+    commit: Option<String>,
+    repo_path: Option<String>,
+) -> Result<Vec<DirectoryEntry>, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let commit = commit
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| "HEAD".to_string());
+    if commit.starts_with('-') {
+        return Err(format!("Invalid commit: {}", commit));
+    }
+    let dir = dir_path.trim().trim_matches('/').to_string();
+
+    let object = if dir.is_empty() {
+        format!("{}^{{tree}}", commit)
+    } else {
+        format!("{}:{}", commit, dir)
+    };
+    let object_type = git_run(
+        &state,
+        Some(path.clone()),
+        &["cat-file", "-t", &object],
+        TIMEOUT_QUICK,
+    )
+    .await
+    .map_err(|_| format!("'{}' does not exist at {}", dir, commit))?;
+    if object_type.stdout.trim() != "tree" {
+        return Err(format!("'{}' is not a directory", dir));
+    }
+
+    // The trailing slash lists the directory's children rather than the
+    // directory entry itself
+    let pathspec = if dir.is_empty() {
+        String::new()
+    } else {
+        format!("{}/", dir)
+    };
+    let mut args = vec!["ls-tree", "-l", "-z", commit.as_str()];
+    if !pathspec.is_empty() {
+        args.push("--");
+        args.push(&pathspec);
+    }
+    let resp = git_run(&state, Some(path), &args, TIMEOUT_LOCAL).await?;
+
+    Ok(resp
+        .stdout
+        .split('\0')
+        .filter_map(parse_ls_tree_long_entry)
+        .collect())
+}
+
+/// Parse one `git ls-tree -l` record: `<mode> <type> <hash> <size>\t<path>`,
+/// where size is `-` for anything but blobs.
+fn parse_ls_tree_long_entry(record: &str) -> Option<DirectoryEntry> {
+    let (meta, path) = record.split_once('\t')?;
+    let mut fields = meta.split_whitespace();
+    let _mode = fields.next()?;
+    let object_type = fields.next()?.to_string();
+    let _hash = fields.next()?;
+    let size = fields.next().and_then(|s| s.parse::<u64>().ok());
+
+    let name = path.rsplit('/').next().unwrap_or(path).to_string();
+    Some(DirectoryEntry {
+        name,
+        path: path.to_string(),
+        object_type,
+        size,
+    })
+}
+
+#[derive(Default)]
+struct DirBuilder {
+    dirs: HashMap<String, DirBuilder>,
+    files: Vec<String>,
+}
+
+/// Nest flat `a/b/c.txt` paths into a tree. Each level lists directories
+/// before files, both sorted case-insensitively.
+fn build_file_tree<'a>(paths: impl Iterator<Item = &'a str>) -> Vec<TreeNode> {
+    let mut root = DirBuilder::default();
+    for path in paths {
+        let mut parts: Vec<&str> = path.split('/').collect();
+        let Some(file_name) = parts.pop() else {
+            continue;
+        };
+        let dir = parts.into_iter().fold(&mut root, |dir, part| {
+            dir.dirs.entry(part.to_string()).or_default()
+        });
+        dir.files.push(file_name.to_string());
+    }
+    into_tree_nodes(root, "")
+}
+
+fn into_tree_nodes(dir: DirBuilder, prefix: &str) -> Vec<TreeNode> {
+    let join = |name: &str| {
+        if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{}", prefix, name)
+        }
+    };
+
+    let mut dirs: Vec<(String, DirBuilder)> = dir.dirs.into_iter().collect();
+    dirs.sort_by_key(|(name, _)| name.to_lowercase());
+    let mut files = dir.files;
+    files.sort_by_key(|name| name.to_lowercase());
+
+    let mut nodes = Vec::with_capacity(dirs.len() + files.len());
+    for (name, sub) in dirs {
+        let path = join(&name);
+        nodes.push(TreeNode {
+            children: into_tree_nodes(sub, &path),
+            name,
+            path,
+            is_dir: true,
+        });
+    }
+    for name in files {
+        nodes.push(TreeNode {
+            path: join(&name),
+            name,
+            is_dir: false,
+            children: Vec::new(),
+        });
+    }
+    nodes
+}
+
 // ---------------------------------------------------------------------------
 // Diff Commands
 // ---------------------------------------------------------------------------
@@ -2414,6 +5674,7 @@ pub async fn cmd_get_commit_diff(
     file_path: Option<String>,
     repo_path: Option<String>,
     encoding: Option<String>,
+    max_lines: Option<usize>,
 ) -> Result<CommitDiff, String> {
     let path = resolve_repo_path(&state, repo_path)?;
 
@@ -2452,7 +5713,14 @@ pub async fn cmd_get_commit_diff(
     };
 
     // 2. Parse output
-    let files = parse_diff_output(&decoded_stdout);
+    let mut files = parse_diff_output(&decoded_stdout);
+    // A huge patch only sends the file list; each file loads separately
+    if max_lines.is_some_and(|max| decoded_stdout.lines().count() > max) {
+        for file in &mut files {
+            file.truncated = !file.hunks.is_empty();
+            file.hunks = Vec::new();
+        }
+    }
 
     // 3. Get parent hash
     let parent_hash_args = vec!["rev-parse".to_string(), format!("{}^", commit_hash)];
@@ -2479,25 +5747,184 @@ pub async fn cmd_get_file_at_commit(
     file_path: String,
     repo_path: Option<String>,
     encoding: Option<String>,
-) -> Result<String, String> {
+) -> Result<FileContent, String> {
     let path = resolve_repo_path(&state, repo_path)?;
     let object = format!("{}:{}", commit_hash, file_path);
-    let args = vec!["show".to_string(), object];
+    let limit = load_file_view_limit(&state)?;
+    if let Some(size) = git_object_size(&state, &path, &object).await {
+        if size > limit {
+            return Ok(FileContent::too_large(size));
+        }
+    }
+
+    let args = vec!["show".to_string(), object.clone()];
     let resp = state
         .git
         .run_with_output_bytes(Path::new(&path), &args, TIMEOUT_LOCAL)
         .await
         .map_err(|e| e.to_string())?;
+    // An LFS pointer is tiny but the object it resolves to may not be
+    let content = resolve_lfs_content(&state, &path, &object, resp.stdout).await;
+    let size = content.len() as u64;
+    if size > limit {
+        return Ok(FileContent::too_large(size));
+    }
 
     let settings = state.settings.lock().map_err(|e| e.to_string())?;
-    Ok(crate::git::encoding::decode_bytes(
-        &resp.stdout,
-        Path::new(&file_path),
-        &settings,
-        encoding,
+    Ok(FileContent::text(
+        crate::git::encoding::decode_bytes(&content, Path::new(&file_path), &settings, encoding),
+        size,
     ))
 }
 
+/// Replace a file with its version from `commit_hash`, in both the working
+/// tree and the index, leaving the rest of the tree alone.
+#[tauri::command]
+pub async fn cmd_restore_file_from_commit(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    file_path: String,
+    commit_hash: String,
+    repo_path: Option<String>,
+) -> CommandResult<()> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    let commit_hash = commit_hash.trim();
+    if commit_hash.is_empty() || commit_hash.starts_with('-') {
+        return Err(CommandError::InvalidInput(format!(
+            "Invalid commit: '{}'",
+            commit_hash
+        )));
+    }
+
+    let exclusions = load_exclusion_patterns(&state, &r_path)?;
+    if is_excluded(&file_path, &exclusions) {
+        return Err(CommandError::InvalidInput(format!(
+            "File {} is excluded from git operations",
+            file_path
+        )));
+    }
+
+    let object = format!("{}:{}", commit_hash, file_path);
+    if git_object_size(&state, &r_path, &object).await.is_none() {
+        return Err(CommandError::InvalidInput(format!(
+            "File {} did not exist at commit {}",
+            file_path, commit_hash
+        )));
+    }
+
+    let args: Vec<String> = vec![
+        "checkout".into(),
+        commit_hash.to_string(),
+        "--".into(),
+        file_path,
+    ];
+    git_run_typed(&state, Some(r_path), args, TIMEOUT_LOCAL).await?;
+    emit_git_change_event(&app)?;
+    Ok(())
+}
+
+/// Pretty-print any object (blob, tree, commit or tag) by SHA or revision,
+/// for low-level inspection. Blobs have no path here, so only the encoding
+/// override or the default applies.
+#[tauri::command]
+pub async fn cmd_cat_file(
+    state: State<'_, AppState>,
+    object: String,
+    repo_path: Option<String>,
+    encoding: Option<String>,
+) -> Result<GitObject, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let object = object.trim();
+    if object.is_empty() || object.starts_with('-') {
+        return Err(format!("Invalid object: '{}'", object));
+    }
+
+    let type_args = vec!["cat-file".to_string(), "-t".to_string(), object.to_string()];
+    let object_type = state
+        .git
+        .run(Path::new(&path), &type_args, TIMEOUT_QUICK)
+        .await
+        .map_err(|e| e.to_string())?
+        .stdout
+        .trim()
+        .to_string();
+
+    let size = git_object_size(&state, &path, object).await.unwrap_or(0);
+    if size > load_file_view_limit(&state)? {
+        return Ok(GitObject {
+            object_type,
+            content: FileContent::too_large(size),
+        });
+    }
+
+    let args = vec!["cat-file".to_string(), "-p".to_string(), object.to_string()];
+    let resp = state
+        .git
+        .run_with_output_bytes(Path::new(&path), &args, TIMEOUT_LOCAL)
+        .await
+        .map_err(|e| e.to_string())?;
+    let content = {
+        let settings = state.settings.lock().map_err(|e| e.to_string())?;
+        crate::git::encoding::decode_bytes(&resp.stdout, Path::new(""), &settings, encoding)
+    };
+    Ok(GitObject {
+        object_type,
+        content: FileContent::text(content, size),
+    })
+}
+
+/// If `data` is a Git LFS pointer, fetch the real object through the LFS
+/// smudge filter. Falls back to the pointer text when that isn't possible
+/// (git-lfs not installed, object not downloaded, ...).
+async fn resolve_lfs_content(
+    state: &State<'_, AppState>,
+    repo_path: &str,
+    object: &str,
+    data: Vec<u8>,
+) -> Vec<u8> {
+    if crate::git::lfs::parse_pointer(&data).is_none() {
+        return data;
+    }
+
+    let args = vec![
+        "cat-file".to_string(),
+        "--filters".to_string(),
+        object.to_string(),
+    ];
+    match state
+        .git
+        .run_with_output_bytes(Path::new(repo_path), &args, TIMEOUT_NETWORK)
+        .await
+    {
+        Ok(resp) if crate::git::lfs::parse_pointer(&resp.stdout).is_none() => resp.stdout,
+        _ => data,
+    }
+}
+
+#[tauri::command]
+pub async fn cmd_get_lfs_pointer_info(
+    state: State<'_, AppState>,
+    file_path: String,
+    commit_hash: Option<String>,
+    repo_path: Option<String>,
+) -> Result<LfsPointerInfo, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let commit_hash = commit_hash.unwrap_or_default();
+    if commit_hash.trim_start().starts_with('-') {
+        return Err(format!("Invalid commit: '{}'", commit_hash));
+    }
+    // Without a commit, inspect the staged (index) version
+    let object = format!("{}:{}", commit_hash, file_path);
+    let args = vec!["cat-file".to_string(), "blob".to_string(), object];
+    let resp = state
+        .git
+        .run_with_output_bytes(Path::new(&path), &args, TIMEOUT_QUICK)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(crate::git::lfs::parse_pointer(&resp.stdout).unwrap_or_default())
+}
+
 fn parse_diff_file_path(line: &str) -> String {
     if let Some(idx) = line.find(" b/") {
         return line[idx + 3..].trim().to_string();
@@ -2594,6 +6021,7 @@ fn parse_diff_output(stdout: &str) -> Vec<DiffFile> {
                 path: parse_diff_file_path(line),
                 status: "M".to_string(),
                 hunks: Vec::new(),
+                truncated: false,
             });
             continue;
         }
@@ -2787,30 +6215,76 @@ pub async fn cmd_get_commit_file_diff(
     commit_hash: String,
     file_path: String,
     repo_path: Option<String>,
+    encoding: Option<String>,
 ) -> Result<GitCommandResult, String> {
     let path = resolve_repo_path(&state, repo_path)?;
 
-    // git show <commit> -- <path>
-    let mut command = std::process::Command::new(state.git.binary_path());
-    command
-        .args(&["show", &commit_hash, "--", &file_path])
-        .current_dir(&path);
-    hide_console_window(&mut command);
-
-    let output = command.output().map_err(|e| e.to_string())?;
+    // git show <commit> -- <path>; a failure is reported through `success`
+    let args = vec![
+        "show".to_string(),
+        commit_hash,
+        "--".to_string(),
+        file_path.clone(),
+    ];
+    let resp = state
+        .git
+        .run_with_output_bytes_unchecked(Path::new(&path), &args, TIMEOUT_LOCAL)
+        .await
+        .map_err(|e| e.to_string())?;
 
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let stdout = {
+        let settings = state.settings.lock().map_err(|e| e.to_string())?;
+        crate::git::encoding::decode_bytes(&resp.stdout, Path::new(&file_path), &settings, encoding)
+    };
 
     Ok(GitCommandResult {
-        success: output.status.success(),
+        success: resp.exit_code == 0,
         stdout,
-        stderr,
-        exit_code: output.status.code().unwrap_or(-1),
+        stderr: resp.stderr,
+        exit_code: resp.exit_code,
         command_type: GitCommandType::Other,
     })
 }
 
+/// One file's change in a commit, parsed into hunks and decoded with the
+/// file's encoding. `cmd_get_commit_file_diff` keeps the raw `git show` text.
+#[tauri::command]
+pub async fn cmd_get_commit_file_diff_parsed(
+    state: State<'_, AppState>,
+    commit_hash: String,
+    file_path: String,
+    repo_path: Option<String>,
+    encoding: Option<String>,
+) -> Result<DiffFile, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+
+    // Same as cmd_get_commit_diff: no commit header, merges against the first parent
+    let args = vec![
+        "show".to_string(),
+        "--format=".to_string(),
+        "--first-parent".to_string(),
+        "--patch".to_string(),
+        commit_hash.clone(),
+        "--".to_string(),
+        file_path.clone(),
+    ];
+    let resp = state
+        .git
+        .run_with_output_bytes(Path::new(&path), &args, TIMEOUT_LOCAL)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let stdout = {
+        let settings = state.settings.lock().map_err(|e| e.to_string())?;
+        crate::git::encoding::decode_bytes(&resp.stdout, Path::new(&file_path), &settings, encoding)
+    };
+
+    parse_diff_output(&stdout)
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("'{}' was not changed in commit {}", file_path, commit_hash))
+}
+
 // ---------------------------------------------------------------------------
 // Rebase Commands
 // ---------------------------------------------------------------------------
@@ -2833,6 +6307,18 @@ pub async fn cmd_rebase_start(
     rebase_commands::cmd_rebase_start_impl(app, state, base, repo_path).await
 }
 
+#[tauri::command]
+pub async fn cmd_rebase_onto(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    new_base: String,
+    upstream: String,
+    branch: Option<String>,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    rebase_commands::cmd_rebase_onto_impl(app, state, new_base, upstream, branch, repo_path).await
+}
+
 #[tauri::command]
 pub async fn cmd_rebase_interactive_prepare(
     state: State<'_, AppState>,
@@ -2853,6 +6339,24 @@ pub async fn cmd_rebase_interactive_apply(
     rebase_commands::cmd_rebase_interactive_apply_impl(app, state, base_commit, todo_items, repo_path).await
 }
 
+#[tauri::command]
+pub async fn cmd_get_rebase_todo(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<Vec<RebaseTodoItem>, String> {
+    rebase_commands::cmd_get_rebase_todo_impl(state, repo_path).await
+}
+
+#[tauri::command]
+pub async fn cmd_set_rebase_todo(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    items: Vec<RebaseTodoItem>,
+    repo_path: Option<String>,
+) -> Result<(), String> {
+    rebase_commands::cmd_set_rebase_todo_impl(app, state, items, repo_path).await
+}
+
 #[tauri::command]
 pub async fn cmd_rebase_continue(
     app: AppHandle,
@@ -2880,6 +6384,146 @@ pub async fn cmd_rebase_skip(
     rebase_commands::cmd_rebase_skip_impl(app, state, repo_path).await
 }
 
+// ---------------------------------------------------------------------------
+// Config Commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub async fn cmd_get_config(
+    state: State<'_, AppState>,
+    scope: config_commands::ConfigScope,
+    repo_path: Option<String>,
+) -> Result<HashMap<String, String>, String> {
+    config_commands::cmd_get_config_impl(state, scope, repo_path).await
+}
+
+#[tauri::command]
+pub async fn cmd_set_config(
+    state: State<'_, AppState>,
+    key: String,
+    value: String,
+    scope: config_commands::ConfigScope,
+    repo_path: Option<String>,
+) -> Result<(), String> {
+    config_commands::cmd_set_config_impl(state, key, value, scope, repo_path).await
+}
+
+/// Git aliases (`alias.*` config) by name.
+#[tauri::command]
+pub async fn cmd_get_aliases(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<HashMap<String, String>, String> {
+    config_commands::cmd_get_aliases_impl(state, repo_path).await
+}
+
+/// Run `git <alias> <args...>`. Shell (`!`) aliases are refused.
+#[tauri::command]
+pub async fn cmd_run_alias(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    alias: String,
+    args: Vec<String>,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    config_commands::cmd_run_alias_impl(app, state, alias, args, repo_path).await
+}
+
+// ---------------------------------------------------------------------------
+// Sparse Checkout Commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub async fn cmd_sparse_checkout_init(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    cone: bool,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    sparse_commands::cmd_sparse_checkout_init_impl(app, state, cone, repo_path).await
+}
+
+#[tauri::command]
+pub async fn cmd_sparse_checkout_set(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    patterns: Vec<String>,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    sparse_commands::cmd_sparse_checkout_set_impl(app, state, patterns, repo_path).await
+}
+
+#[tauri::command]
+pub async fn cmd_sparse_checkout_list(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<Vec<String>, String> {
+    sparse_commands::cmd_sparse_checkout_list_impl(state, repo_path).await
+}
+
+#[tauri::command]
+pub async fn cmd_sparse_checkout_disable(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    sparse_commands::cmd_sparse_checkout_disable_impl(app, state, repo_path).await
+}
+
+// ---------------------------------------------------------------------------
+// Hook Commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub async fn cmd_list_git_hooks(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<Vec<hook_commands::HookInfo>, String> {
+    hook_commands::cmd_list_git_hooks_impl(state, repo_path).await
+}
+
+#[tauri::command]
+pub async fn cmd_toggle_git_hook(
+    state: State<'_, AppState>,
+    name: String,
+    enable: bool,
+    repo_path: Option<String>,
+) -> Result<(), String> {
+    hook_commands::cmd_toggle_git_hook_impl(state, name, enable, repo_path).await
+}
+
+// ---------------------------------------------------------------------------
+// LFS Commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub async fn cmd_git_lfs_track(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    pattern: String,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    lfs_commands::cmd_git_lfs_track_impl(app, state, pattern, repo_path).await
+}
+
+#[tauri::command]
+pub async fn cmd_git_lfs_untrack(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    pattern: String,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    lfs_commands::cmd_git_lfs_untrack_impl(app, state, pattern, repo_path).await
+}
+
+#[tauri::command]
+pub async fn cmd_git_lfs_status(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<lfs_commands::LfsStatus, String> {
+    lfs_commands::cmd_git_lfs_status_impl(state, repo_path).await
+}
+
 // ---------------------------------------------------------------------------
 // Terminal Commands
 // ---------------------------------------------------------------------------
@@ -2909,3 +6553,475 @@ pub async fn cmd_terminal_stop(
 ) -> Result<(), String> {
     terminal_commands::cmd_terminal_stop_impl(state, repo_path).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_parse_pruned_refs() {
+        let output = "Pruning origin\nURL: git@github.com:o/r.git\n \
+                      * [pruned] origin/feature-a\n * [pruned] origin/fix/b\n";
+        assert_eq!(
+            parse_pruned_refs(output),
+            vec!["origin/feature-a", "origin/fix/b"]
+        );
+        assert!(parse_pruned_refs("").is_empty());
+    }
+
+    #[test]
+    fn test_find_children() {
+        let rev_list = "c3 b2\nm4 c3 x9\nx9 a1\n";
+        assert_eq!(find_children(rev_list, "c3"), vec!["m4"]);
+        assert!(find_children(rev_list, "m4").is_empty());
+    }
+
+    #[test]
+    fn test_parse_gpg_status() {
+        let good = "[GNUPG:] NEWSIG\n\
+                    [GNUPG:] GOODSIG 4AEE18F83AFDEB23 Jane Doe <jane@example.com>\n\
+                    [GNUPG:] VALIDSIG 5DE3E0509C47EA3CF04A42D34AEE18F83AFDEB23 2024-01-01\n";
+        assert_eq!(
+            parse_gpg_status(good),
+            CommitSignature {
+                signed: true,
+                valid: true,
+                signer: Some("Jane Doe <jane@example.com>".to_string()),
+                key_id: Some("4AEE18F83AFDEB23".to_string()),
+            }
+        );
+
+        let expired = "[GNUPG:] EXPKEYSIG 4AEE18F83AFDEB23 Jane Doe <jane@example.com>\n";
+        assert!(parse_gpg_status(expired).signed);
+        assert!(!parse_gpg_status(expired).valid);
+
+        let unknown_key = "[GNUPG:] ERRSIG 4AEE18F83AFDEB23 1 10 00 1700000000 9\n\
+                           [GNUPG:] NO_PUBKEY 4AEE18F83AFDEB23\n";
+        let signature = parse_gpg_status(unknown_key);
+        assert!(signature.signed && !signature.valid);
+        assert_eq!(signature.key_id.as_deref(), Some("4AEE18F83AFDEB23"));
+        assert_eq!(signature.signer, None);
+
+        assert_eq!(parse_gpg_status(""), CommitSignature::default());
+    }
+
+    #[test]
+    fn test_split_branch_refs() {
+        let output = "refs/heads/main\nrefs/heads/feature/x\n\
+                      refs/remotes/origin/HEAD\nrefs/remotes/origin/main\n";
+        let (branches, remote_branches) = split_branch_refs(output);
+        assert_eq!(branches, vec!["main", "feature/x"]);
+        assert_eq!(remote_branches, vec!["origin/main"]);
+    }
+
+    #[test]
+    fn test_build_unstage_args_uses_restore_on_modern_git() {
+        let args = build_unstage_args(&["src/main.rs".to_string()], true);
+        assert_eq!(args, vec!["restore", "--staged", "--", "src/main.rs"]);
+    }
+
+    #[test]
+    fn test_build_unstage_args_falls_back_to_reset_on_old_git() {
+        let paths = vec!["a.txt".to_string(), "dir/b.txt".to_string()];
+        let args = build_unstage_args(&paths, false);
+        assert_eq!(args, vec!["reset", "HEAD", "--", "a.txt", "dir/b.txt"]);
+    }
+
+    fn temp_repo(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("git-tools-{}-{}", name, Uuid::new_v4()));
+        fs::create_dir_all(dir.join("repo").join("src")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_resolve_git_dir_follows_worktree_gitdir_file() {
+        let dir = temp_repo("worktree");
+        let main_git = dir.join("repo").join(".git");
+        let worktree_git = main_git.join("worktrees").join("wt");
+        fs::create_dir_all(&worktree_git).unwrap();
+        fs::write(worktree_git.join("commondir"), "../..\n").unwrap();
+        let worktree = dir.join("wt");
+        fs::create_dir_all(&worktree).unwrap();
+        fs::write(worktree.join(".git"), "gitdir: ../repo/.git/worktrees/wt\n").unwrap();
+
+        assert_eq!(resolve_git_dir(&dir.join("repo")), main_git);
+        assert_eq!(resolve_common_git_dir(&dir.join("repo")), main_git);
+        let resolved = resolve_git_dir(&worktree);
+        assert_eq!(
+            fs::canonicalize(&resolved).unwrap(),
+            fs::canonicalize(&worktree_git).unwrap()
+        );
+        assert_eq!(
+            fs::canonicalize(resolve_common_git_dir(&worktree)).unwrap(),
+            fs::canonicalize(&main_git).unwrap()
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_path_within_repo_accepts_repo_files() {
+        let dir = temp_repo("inside");
+        let repo = dir.join("repo");
+        let repo_str = repo.to_str().unwrap();
+        let root = fs::canonicalize(&repo).unwrap();
+
+        assert_eq!(
+            resolve_path_within_repo(repo_str, "src/main.rs").unwrap(),
+            root.join("src").join("main.rs")
+        );
+        assert_eq!(
+            resolve_path_within_repo(repo_str, "src/../new/file.txt").unwrap(),
+            root.join("new").join("file.txt")
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_path_within_repo_rejects_parent_traversal() {
+        let dir = temp_repo("traversal");
+        let repo = dir.join("repo");
+        let repo_str = repo.to_str().unwrap();
+
+        assert!(resolve_path_within_repo(repo_str, "../../etc/passwd").is_err());
+        assert!(resolve_path_within_repo(repo_str, "src/../../outside.txt").is_err());
+        assert!(resolve_path_within_repo(repo_str, "/etc/passwd").is_err());
+        assert!(resolve_path_within_repo(repo_str, "..").is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_path_within_repo_rejects_symlink_escape() {
+        let dir = temp_repo("symlink");
+        let repo = dir.join("repo");
+        let outside = dir.join("outside");
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(outside.join("secret.txt"), "secret").unwrap();
+        std::os::unix::fs::symlink(&outside, repo.join("link")).unwrap();
+        std::os::unix::fs::symlink(outside.join("secret.txt"), repo.join("secret.txt")).unwrap();
+        let repo_str = repo.to_str().unwrap();
+
+        assert!(resolve_path_within_repo(repo_str, "link/secret.txt").is_err());
+        assert!(resolve_path_within_repo(repo_str, "link/new.txt").is_err());
+        assert!(resolve_path_within_repo(repo_str, "secret.txt").is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_path_within_repo_rejects_dangling_symlink() {
+        let dir = temp_repo("dangling");
+        let repo = dir.join("repo");
+        let outside = dir.join("outside");
+        std::os::unix::fs::symlink(outside.join("x"), repo.join("link")).unwrap();
+        std::os::unix::fs::symlink(&outside, repo.join("dir_link")).unwrap();
+        let repo_str = repo.to_str().unwrap();
+
+        assert!(resolve_path_within_repo(repo_str, "link").is_err());
+        assert!(resolve_path_within_repo(repo_str, "dir_link/new.txt").is_err());
+        assert!(!outside.exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_check_dir_writable_leaves_no_probe_behind() {
+        let dir = temp_repo("writable");
+        let repo = dir.join("repo");
+
+        assert!(check_dir_writable(&repo).is_ok());
+        assert_eq!(fs::read_dir(&repo).unwrap().count(), 1);
+        assert!(check_dir_writable(&repo.join("missing")).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_collect_backup_files_skips_ignored_paths() {
+        let dir = temp_repo("backup");
+        let src = dir.join("repo").join("src");
+        let modules = src.join("node_modules");
+        fs::write(src.join("new.rs"), "fn main() {}").unwrap();
+        fs::create_dir_all(modules.join("pkg")).unwrap();
+        fs::write(modules.join("pkg").join("index.js"), "x").unwrap();
+        let ignored = HashSet::from([modules]);
+
+        let mut files = Vec::new();
+        let mut total_bytes = 0;
+        let fits = collect_backup_files(&src, &ignored, &mut files, &mut total_bytes).unwrap();
+
+        assert!(fits);
+        assert_eq!(files, vec![src.join("new.rs")]);
+        assert_eq!(total_bytes, 12);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_is_valid_stash_ref() {
+        assert!(is_valid_stash_ref("stash@{0}"));
+        assert!(is_valid_stash_ref("stash@{12}"));
+        assert!(!is_valid_stash_ref("stash@{}"));
+        assert!(!is_valid_stash_ref("stash@{-1}"));
+        assert!(!is_valid_stash_ref("stash"));
+        assert!(!is_valid_stash_ref("--output=/tmp/x"));
+    }
+
+    #[test]
+    fn test_build_file_tree_nests_and_sorts_dirs_first() {
+        let paths = [
+            "README.md",
+            "src/main.rs",
+            "src/git/mod.rs",
+            "Cargo.toml",
+            "docs/a.md",
+        ];
+        let tree = build_file_tree(paths.into_iter());
+
+        let names: Vec<&str> = tree.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["docs", "src", "Cargo.toml", "README.md"]);
+
+        let src = &tree[1];
+        assert!(src.is_dir);
+        assert_eq!(src.path, "src");
+        assert_eq!(src.children[0].path, "src/git");
+        assert_eq!(src.children[0].children[0].path, "src/git/mod.rs");
+        assert_eq!(src.children[1].path, "src/main.rs");
+        assert!(!src.children[1].is_dir);
+    }
+
+    #[test]
+    fn test_parse_change_summary_counts_each_entry_once() {
+        let output = [
+            "1 M. N... 100644 100644 100644 aaaa aaaa staged.rs",
+            "1 MM N... 100644 100644 100644 aaaa aaaa both.rs",
+            "1 .M N... 100644 100644 100644 aaaa aaaa unstaged.rs",
+            "2 R. N... 100644 100644 100644 aaaa aaaa R100 new name.rs",
+            "old name.rs",
+            "u UU N... 100644 100644 100644 100644 aaaa bbbb cccc conflict.rs",
+            "? untracked.txt",
+            "? secret.env",
+            "",
+        ]
+        .join("\0");
+
+        let summary = parse_change_summary(&output, &["*.env".to_string()]);
+        assert_eq!(
+            summary,
+            ChangeSummary {
+                staged: 3,
+                unstaged: 2,
+                untracked: 1,
+                conflicted: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_merge_tree_conflicts_lists_paths_after_tree_oid() {
+        let tree = "91d8066f9e7874ada3da6d50e49c6f50be3df0dd";
+        let conflicted = format!("{}\0src/a.rs\0b.txt\0\0", tree);
+        assert_eq!(
+            parse_merge_tree_conflicts(&conflicted),
+            vec!["src/a.rs", "b.txt"]
+        );
+        assert!(parse_merge_tree_conflicts(&format!("{}\0", tree)).is_empty());
+    }
+
+    #[test]
+    fn parse_ahead_behind_reads_left_right_counts() {
+        assert_eq!(parse_ahead_behind("3\t1\n"), Some((3, 1)));
+        assert_eq!(parse_ahead_behind(""), None);
+    }
+
+    #[test]
+    fn parse_blame_line_info_reads_porcelain_headers() {
+        let output = [
+            "f813fa275ae40d604b24d0610f517204fba1003c 7 7 1",
+            "author Jane Doe",
+            "author-mail <jane@example.com>",
+            "author-time 1700000000",
+            "summary Fix the parser",
+            "filename src/lib.rs",
+            "\tlet x = 1;",
+        ]
+        .join("\n");
+
+        let info = parse_blame_line_info(&output).unwrap();
+        assert_eq!(info.commit_hash, "f813fa275ae40d604b24d0610f517204fba1003c");
+        assert_eq!(info.author, "Jane Doe");
+        assert_eq!(info.date, "1700000000");
+        assert_eq!(info.summary, "Fix the parser");
+        assert!(parse_blame_line_info("").is_none());
+    }
+
+    #[test]
+    fn parse_shortlog_contributors_merges_by_email() {
+        let output = [
+            "    10\tJane Doe <jane@example.com>",
+            "     4\tBob <bob@example.com>",
+            "     2\tjane doe <Jane@Example.com>",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            parse_shortlog_contributors(&output),
+            vec![
+                Contributor {
+                    name: "Jane Doe".to_string(),
+                    email: "jane@example.com".to_string(),
+                    commit_count: 12,
+                },
+                Contributor {
+                    name: "Bob".to_string(),
+                    email: "bob@example.com".to_string(),
+                    commit_count: 4,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_upstream_track_reads_ahead_and_behind() {
+        assert_eq!(parse_upstream_track("[ahead 2, behind 1]"), (2, 1));
+        assert_eq!(parse_upstream_track("[behind 3]"), (0, 3));
+        assert_eq!(parse_upstream_track("[gone]"), (0, 0));
+        assert_eq!(parse_upstream_track(""), (0, 0));
+    }
+
+    #[test]
+    fn parse_branch_info_line_handles_local_and_remote_refs() {
+        let local = "*\0refs/heads/main\0origin/main\0[ahead 1]\0abc123\0\
+                     2024-01-01T00:00:00+00:00\0Fix: a\0b";
+        assert_eq!(
+            parse_branch_info_line(local),
+            Some(BranchInfo {
+                name: "main".to_string(),
+                is_current: true,
+                upstream: Some("origin/main".to_string()),
+                last_commit_hash: "abc123".to_string(),
+                last_commit_date: "2024-01-01T00:00:00+00:00".to_string(),
+                last_commit_subject: "Fix: a\0b".to_string(),
+                ahead: 1,
+                behind: 0,
+                upstream_gone: false,
+            })
+        );
+
+        let gone = " \0refs/heads/done\0origin/done\0[gone]\0abc\0date\0subject";
+        assert!(parse_branch_info_line(gone).unwrap().upstream_gone);
+
+        let remote = " \0refs/remotes/origin/dev\0\0\0def456\0date\0subject";
+        let info = parse_branch_info_line(remote).unwrap();
+        assert_eq!(info.name, "remotes/origin/dev");
+        assert_eq!(info.upstream, None);
+        assert!(!info.is_current);
+
+        assert!(parse_branch_info_line(" \0refs/remotes/origin/HEAD\0\0\0x\0d\0s").is_none());
+    }
+
+    #[test]
+    fn arrange_branches_pins_current_and_filters() {
+        let branch = |name: &str, is_current: bool, ahead: u32| BranchInfo {
+            name: name.to_string(),
+            is_current,
+            upstream: None,
+            last_commit_hash: String::new(),
+            last_commit_date: String::new(),
+            last_commit_subject: String::new(),
+            ahead,
+            behind: 0,
+            upstream_gone: false,
+        };
+        let names = |sort: BranchSort, filter: Option<&str>| -> Vec<String> {
+            let branches = vec![
+                branch("feature/a", false, 1),
+                branch("main", true, 0),
+                branch("feature/b", false, 5),
+                branch("fix/c", false, 2),
+            ];
+            arrange_branches(branches, sort, filter)
+                .into_iter()
+                .map(|b| b.name)
+                .collect()
+        };
+
+        assert_eq!(
+            names(BranchSort::CommitDate, None),
+            vec!["main", "feature/a", "feature/b", "fix/c"]
+        );
+        assert_eq!(
+            names(BranchSort::AheadBehind, None),
+            vec!["main", "feature/b", "fix/c", "feature/a"]
+        );
+        // The current branch is filtered like any other
+        assert_eq!(
+            names(BranchSort::CommitDate, Some("FEATURE")),
+            vec!["feature/a", "feature/b"]
+        );
+        assert_eq!(
+            names(BranchSort::AheadBehind, Some("i")),
+            vec!["main", "fix/c"]
+        );
+    }
+
+    #[test]
+    fn parse_merged_branches_skips_current_and_protected() {
+        let output = "*main\n feature/done\n develop\n(HEAD detached at abc1234)\n fix/typo\n";
+        assert_eq!(
+            parse_merged_branches(output, "HEAD"),
+            vec!["feature/done", "fix/typo"]
+        );
+        assert_eq!(
+            parse_merged_branches(output, "fix/typo"),
+            vec!["feature/done"]
+        );
+    }
+
+    #[test]
+    fn is_excluded_negation_reincludes_earlier_match() {
+        let exclusions = vec!["build/*".to_string(), "!build/keep.txt".to_string()];
+        assert!(is_excluded("build/out.o", &exclusions));
+        assert!(!is_excluded("build/keep.txt", &exclusions));
+        assert!(!is_excluded("src/main.rs", &exclusions));
+    }
+
+    #[test]
+    fn is_excluded_last_matching_pattern_wins() {
+        let exclusions = vec![
+            "*.log".to_string(),
+            "!debug.log".to_string(),
+            "debug.log".to_string(),
+        ];
+        assert!(is_excluded("debug.log", &exclusions));
+
+        // A negation before any positive match has nothing to undo
+        let exclusions = vec!["!keep.txt".to_string(), "*.txt".to_string()];
+        assert!(is_excluded("keep.txt", &exclusions));
+        assert!(!is_excluded("keep.md", &exclusions));
+    }
+
+    #[test]
+    fn is_excluded_matches_directory_prefixes() {
+        let exclusions = vec!["node_modules".to_string()];
+        assert!(is_excluded("node_modules/a/b.js", &exclusions));
+        assert!(is_excluded("node_modules/", &exclusions));
+        assert!(is_excluded("web/node_modules/x.js", &exclusions));
+        assert!(!is_excluded("node_modules_backup/a.js", &exclusions));
+
+        let exclusions = vec!["dist/".to_string()];
+        assert!(is_excluded("dist/app.js", &exclusions));
+        assert!(is_excluded("dist/", &exclusions));
+        assert!(!is_excluded("dist", &exclusions));
+
+        let exclusions = vec!["/build".to_string()];
+        assert!(is_excluded("build/out.o", &exclusions));
+        assert!(!is_excluded("src/build/out.o", &exclusions));
+    }
+}
@@ -5,6 +5,7 @@ const GEMINI_MAX_DIFF_CHARS: usize = 40_000;
 const GEMINI_MAX_FILE_SUMMARY_CHARS: usize = 4_000;
 const GEMINI_LIST_MODELS_URL: &str = "https://generativelanguage.googleapis.com/v1beta/models";
 const GEMINI_MODELS_PAGE_SIZE: &str = "1000";
+const GEMINI_REQUEST_TIMEOUT_SECS: u64 = 120;
 
 #[derive(Debug, Deserialize)]
 struct GeminiModelsListResponse {
@@ -214,7 +215,7 @@ async fn call_gemini_api(token: &str, model: &str, prompt: &str) -> Result<Strin
     });
 
     let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(TIMEOUT_NETWORK))
+        .timeout(std::time::Duration::from_secs(GEMINI_REQUEST_TIMEOUT_SECS))
         .build()
         .map_err(|e| format!("Failed to initialize Gemini client: {}", e))?;
 
@@ -224,7 +225,7 @@ async fn call_gemini_api(token: &str, model: &str, prompt: &str) -> Result<Strin
         .json(&payload)
         .send()
         .await
-        .map_err(|e| format!("Failed to call Gemini API: {}", e))?;
+        .map_err(|e| redact_secrets(&format!("Failed to call Gemini API: {}", e)))?;
 
     let status = response.status();
     let body = response
@@ -233,7 +234,10 @@ async fn call_gemini_api(token: &str, model: &str, prompt: &str) -> Result<Strin
         .map_err(|e| format!("Failed to read Gemini response: {}", e))?;
 
     if !status.is_success() {
-        return Err(format!("Gemini API error ({}): {}", status, body));
+        return Err(redact_secrets(&format!(
+            "Gemini API error ({}): {}",
+            status, body
+        )));
     }
 
     let response_json: serde_json::Value =
@@ -361,6 +365,23 @@ fn extract_gemini_text(response_json: &serde_json::Value) -> Option<String> {
     }
 }
 
+/// The saved Gemini token from the OS keychain. Falls back to a plaintext
+/// token that `migrate_gemini_token` could not move yet.
+fn stored_gemini_token(state: &State<'_, AppState>) -> Result<String, String> {
+    let legacy = {
+        let settings = state.settings.lock().map_err(|e| e.to_string())?;
+        settings.gemini_api_token.clone()
+    };
+    let token = match legacy {
+        Some(token) => Some(token),
+        None => secrets::get_gemini_token()?,
+    };
+    token
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| "Gemini API token is missing. Set it in Settings first.".to_string())
+}
+
 pub async fn cmd_get_gemini_models_impl(
     state: State<'_, AppState>,
     token: Option<String>,
@@ -369,14 +390,9 @@ pub async fn cmd_get_gemini_models_impl(
         .map(|t| t.trim().to_string())
         .filter(|t| !t.is_empty());
 
-    let api_token = if let Some(t) = provided_token {
-        t
-    } else {
-        let settings = state.settings.lock().map_err(|e| e.to_string())?;
-        settings
-            .gemini_api_token
-            .clone()
-            .ok_or("Gemini API token is missing. Set it in Settings first.")?
+    let api_token = match provided_token {
+        Some(t) => t,
+        None => stored_gemini_token(&state)?,
     };
 
     if api_token.trim().is_empty() {
@@ -384,7 +400,7 @@ pub async fn cmd_get_gemini_models_impl(
     }
 
     let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(TIMEOUT_NETWORK))
+        .timeout(std::time::Duration::from_secs(GEMINI_REQUEST_TIMEOUT_SECS))
         .build()
         .map_err(|e| format!("Failed to initialize Gemini client: {}", e))?;
 
@@ -404,7 +420,7 @@ pub async fn cmd_get_gemini_models_impl(
         let response = request
             .send()
             .await
-            .map_err(|e| format!("Failed to call Gemini API: {}", e))?;
+            .map_err(|e| redact_secrets(&format!("Failed to call Gemini API: {}", e)))?;
 
         let status = response.status();
         let body = response
@@ -413,10 +429,10 @@ pub async fn cmd_get_gemini_models_impl(
             .map_err(|e| format!("Failed to read Gemini response: {}", e))?;
 
         if !status.is_success() {
-            return Err(format!(
+            return Err(redact_secrets(&format!(
                 "Gemini API error while listing models ({}): {}",
                 status, body
-            ));
+            )));
         }
 
         let parsed: GeminiModelsListResponse = serde_json::from_str(&body)
@@ -466,9 +482,8 @@ pub async fn cmd_generate_commit_message_impl(
 ) -> Result<String, String> {
     let path = resolve_repo_path(&state, repo_path)?;
 
-    let (token, model, global_prompt, repo_prompt) = {
+    let (model, global_prompt, repo_prompt) = {
         let settings = state.settings.lock().map_err(|e| e.to_string())?;
-        let token = settings.gemini_api_token.clone();
         let model = settings
             .gemini_model
             .clone()
@@ -477,10 +492,10 @@ pub async fn cmd_generate_commit_message_impl(
         let global_prompt = settings.global_commit_prompt.clone();
         let repo_prompt = settings.repo_commit_prompts.get(&path).cloned();
 
-        (token, model, global_prompt, repo_prompt)
+        (model, global_prompt, repo_prompt)
     };
 
-    let token = token.ok_or("Gemini API token is missing. Set it in Settings first.")?;
+    let token = stored_gemini_token(&state)?;
     let model = if model.trim().is_empty() {
         DEFAULT_GEMINI_MODEL.to_string()
     } else {
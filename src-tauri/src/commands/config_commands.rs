@@ -0,0 +1,228 @@
+use super::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfigScope {
+    Local,
+    Global,
+}
+
+impl ConfigScope {
+    fn flag(self) -> &'static str {
+        match self {
+            ConfigScope::Local => "--local",
+            ConfigScope::Global => "--global",
+        }
+    }
+}
+
+/// Accept `section.key` and `section.subsection.key`, the forms `git config`
+/// itself takes. Section and key names are alphanumeric plus `-`, and the key
+/// must start with a letter; the subsection may be anything but a newline.
+fn validate_config_key(key: &str) -> Result<(), String> {
+    let invalid = || format!("Invalid config key '{}': expected section.key", key);
+
+    let (section, rest) = key.split_once('.').ok_or_else(invalid)?;
+    let (subsection, name) = match rest.rsplit_once('.') {
+        Some((subsection, name)) => (Some(subsection), name),
+        None => (None, rest),
+    };
+
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '-';
+    let section_ok = !section.is_empty() && section.chars().all(is_name_char);
+    let name_ok =
+        name.starts_with(|c: char| c.is_ascii_alphabetic()) && name.chars().all(is_name_char);
+    let subsection_ok = subsection.is_none_or(|s| !s.is_empty() && !s.contains('\n'));
+
+    if section_ok && name_ok && subsection_ok {
+        Ok(())
+    } else {
+        Err(invalid())
+    }
+}
+
+/// Global config doesn't need a repository, but git still needs a working
+/// directory; fall back to the home directory when no repo is selected.
+fn config_cwd(
+    state: &State<'_, AppState>,
+    scope: ConfigScope,
+    repo_path: Option<String>,
+) -> Result<PathBuf, String> {
+    match (scope, resolve_repo_path(state, repo_path)) {
+        (_, Ok(path)) => Ok(PathBuf::from(path)),
+        (ConfigScope::Global, Err(_)) => Ok(home_dir().unwrap_or_else(std::env::temp_dir)),
+        (ConfigScope::Local, Err(e)) => Err(e),
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// Parse `git config --list --null`: entries are NUL-terminated and the key is
+/// separated from its value by the first newline (values may span lines).
+fn parse_config_list(output: &str) -> HashMap<String, String> {
+    output
+        .split('\0')
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry.split_once('\n') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            // A bare key (no `=` in the file) is boolean true
+            None => (entry.to_string(), "true".to_string()),
+        })
+        .collect()
+}
+
+pub async fn cmd_get_config_impl(
+    state: State<'_, AppState>,
+    scope: ConfigScope,
+    repo_path: Option<String>,
+) -> Result<HashMap<String, String>, String> {
+    let cwd = config_cwd(&state, scope, repo_path)?;
+    let args: Vec<String> = vec![
+        "config".into(),
+        "--list".into(),
+        "--null".into(),
+        scope.flag().into(),
+    ];
+
+    match state.git.run(&cwd, &args, TIMEOUT_QUICK).await {
+        Ok(resp) => Ok(parse_config_list(&resp.stdout)),
+        // No ~/.gitconfig yet is not an error, just an empty scope
+        Err(e) if e.to_string().contains("unable to read config file") => Ok(HashMap::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+pub async fn cmd_set_config_impl(
+    state: State<'_, AppState>,
+    key: String,
+    value: String,
+    scope: ConfigScope,
+    repo_path: Option<String>,
+) -> Result<(), String> {
+    let key = key.trim().to_string();
+    validate_config_key(&key)?;
+
+    let cwd = config_cwd(&state, scope, repo_path)?;
+    let args: Vec<String> = vec!["config".into(), scope.flag().into(), key, value];
+    state
+        .git
+        .run(&cwd, &args, TIMEOUT_QUICK)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Aliases from `git config --get-regexp ^alias\.`, keyed by name without the
+/// `alias.` prefix, across all config scopes.
+async fn load_aliases(
+    state: &State<'_, AppState>,
+    repo_path: &str,
+) -> Result<HashMap<String, String>, String> {
+    let args: Vec<String> = vec![
+        "config".into(),
+        "--null".into(),
+        "--get-regexp".into(),
+        r"^alias\.".into(),
+    ];
+    let resp = state
+        .git
+        .run_unchecked(Path::new(repo_path), &args, TIMEOUT_QUICK)
+        .await
+        .map_err(|e| e.to_string())?;
+    // Exit code 1 just means no aliases are defined
+    if resp.exit_code != 0 && resp.exit_code != 1 {
+        return Err(resp.stderr.trim().to_string());
+    }
+
+    Ok(parse_config_list(&resp.stdout)
+        .into_iter()
+        .filter_map(|(key, value)| Some((key.strip_prefix("alias.")?.to_string(), value)))
+        .collect())
+}
+
+pub async fn cmd_get_aliases_impl(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<HashMap<String, String>, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    load_aliases(&state, &path).await
+}
+
+/// Longest alias chain followed before giving up, like git's loop detection.
+const MAX_ALIAS_DEPTH: usize = 16;
+
+/// Follow `alias` through aliases that expand to other aliases, as git does,
+/// refusing the chain if any link runs through the shell. `!` aliases run
+/// outside git and our argument handling.
+fn check_alias_chain(aliases: &HashMap<String, String>, alias: &str) -> Result<(), String> {
+    let mut name = alias;
+    for _ in 0..MAX_ALIAS_DEPTH {
+        let Some(expansion) = aliases.get(name) else {
+            return Ok(());
+        };
+        if expansion.trim_start().starts_with('!') {
+            return Err(format!(
+                "'{}' runs the shell alias '{}' ({}) and can only be run from a terminal",
+                alias, name, expansion
+            ));
+        }
+        name = expansion.split_whitespace().next().unwrap_or_default();
+    }
+    Err(format!("'{}' expands through too many aliases", alias))
+}
+
+pub async fn cmd_run_alias_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    alias: String,
+    args: Vec<String>,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let alias = alias.trim();
+    let aliases = load_aliases(&state, &path).await?;
+    if !aliases.contains_key(alias) {
+        return Err(format!("'{}' is not a git alias", alias));
+    }
+    check_alias_chain(&aliases, alias)?;
+
+    let mut command_args = vec![alias.to_string()];
+    command_args.extend(args);
+    git_run_result_at_path_with_event(
+        &app,
+        &state,
+        &path,
+        command_args,
+        TIMEOUT_LOCAL,
+        GitCommandType::Other,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_alias_chain_refuses_shell_links() {
+        let aliases: HashMap<String, String> = [
+            ("st", "status -sb"),
+            ("a", "b --all"),
+            ("b", "!sh -c 'rm -rf ~'"),
+            ("loop", "loop"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        assert!(check_alias_chain(&aliases, "st").is_ok());
+        assert!(check_alias_chain(&aliases, "b").is_err());
+        let err = check_alias_chain(&aliases, "a").unwrap_err();
+        assert!(err.contains("'b'"));
+        assert!(check_alias_chain(&aliases, "loop").is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use crate::git::RebaseStepInfo;
 use std::collections::HashSet;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -8,6 +9,8 @@ pub struct GitOperationState {
     pub is_rebasing: bool,
     pub is_cherry_picking: bool,
     pub is_reverting: bool,
+    #[serde(default)]
+    pub is_bisecting: bool,
     pub has_conflicts: bool,
     pub conflict_paths: Vec<String>,
     pub ours_commit: Option<String>,
@@ -16,6 +19,41 @@ pub struct GitOperationState {
     pub theirs_branch: Option<String>,
 }
 
+/// The operation a repository is in the middle of. When several apply (a
+/// merge conflict while bisecting), the first one listed wins.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RepoOperation {
+    Clean,
+    Rebasing,
+    Merging,
+    CherryPicking,
+    Reverting,
+    Bisecting,
+}
+
+/// Everything the UI needs to render an in-progress operation, taken from
+/// one snapshot of the git directory and `git status`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoState {
+    pub operation: RepoOperation,
+    pub conflict_paths: Vec<String>,
+    /// Set while rebasing.
+    pub rebase_step: Option<RebaseStepInfo>,
+    /// Set while bisecting, once a bad commit is marked: revisions left to
+    /// test after the current one.
+    pub bisect_remaining: Option<u32>,
+}
+
+/// Which operation `cmd_abort_current_operation` aborted, and git's output.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AbortedOperation {
+    pub operation: RepoOperation,
+    pub result: GitCommandResult,
+}
+
 fn is_unmerged_status(status: &str) -> bool {
     matches!(status, "DD" | "AU" | "UD" | "UA" | "DU" | "AA" | "UU")
 }
@@ -62,14 +100,48 @@ fn collect_conflict_paths(porcelain_status: &str) -> Vec<String> {
     paths
 }
 
-fn detect_operation_flags(git_dir: &Path) -> (bool, bool, bool, bool) {
+fn detect_operation_flags(git_dir: &Path) -> (bool, bool, bool, bool, bool) {
     let is_merging = git_dir.join("MERGE_HEAD").exists();
     let is_rebasing = git_dir.join("REBASE_HEAD").exists()
         || git_dir.join("rebase-merge").exists()
         || git_dir.join("rebase-apply").exists();
     let is_cherry_picking = git_dir.join("CHERRY_PICK_HEAD").exists();
     let is_reverting = git_dir.join("REVERT_HEAD").exists();
-    (is_merging, is_rebasing, is_cherry_picking, is_reverting)
+    let is_bisecting = git_dir.join("BISECT_START").exists() || git_dir.join("BISECT_LOG").exists();
+    (
+        is_merging,
+        is_rebasing,
+        is_cherry_picking,
+        is_reverting,
+        is_bisecting,
+    )
+}
+
+/// The operation in progress in `git_dir`, by `RepoOperation` precedence.
+fn current_operation(git_dir: &Path) -> RepoOperation {
+    let (is_merging, is_rebasing, is_cherry_picking, is_reverting, is_bisecting) =
+        detect_operation_flags(git_dir);
+    if is_rebasing {
+        RepoOperation::Rebasing
+    } else if is_merging {
+        RepoOperation::Merging
+    } else if is_cherry_picking {
+        RepoOperation::CherryPicking
+    } else if is_reverting {
+        RepoOperation::Reverting
+    } else if is_bisecting {
+        RepoOperation::Bisecting
+    } else {
+        RepoOperation::Clean
+    }
+}
+
+/// `bisect_nr` from `git rev-list --bisect-vars`.
+fn parse_bisect_remaining(output: &str) -> Option<u32> {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("bisect_nr="))
+        .and_then(|n| n.trim().parse().ok())
 }
 
 pub async fn cmd_get_conflicts_impl(
@@ -98,6 +170,108 @@ pub async fn cmd_get_conflict_file_impl(
     Ok(ConflictFile { base, ours, theirs })
 }
 
+pub async fn cmd_get_conflict_merged_impl(
+    state: State<'_, AppState>,
+    path: String,
+    encoding: Option<String>,
+    repo_path: Option<String>,
+) -> Result<Vec<MergeRegion>, String> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    let full_path = resolve_path_within_repo(&r_path, &path)?;
+    let bytes = std::fs::read(&full_path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+    let settings = state.settings.lock().map_err(|e| e.to_string())?.clone();
+    let content = crate::git::encoding::decode_bytes(&bytes, Path::new(&path), &settings, encoding);
+
+    Ok(parse_merge_regions(&content))
+}
+
+#[derive(PartialEq)]
+enum MarkerSection {
+    Outside,
+    Ours,
+    Base,
+    Theirs,
+}
+
+fn is_conflict_marker(line: &str, marker: &str) -> bool {
+    let line = line.trim_end_matches(['\r', '\n']);
+    line.strip_prefix(marker)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+}
+
+/// Split a file containing `<<<<<<<` / `|||||||` / `=======` / `>>>>>>>`
+/// markers into unchanged text and conflict hunks. Line endings are kept so
+/// the regions can be joined back into the original file. An unterminated
+/// hunk is returned as unchanged text.
+fn parse_merge_regions(content: &str) -> Vec<MergeRegion> {
+    let mut regions = Vec::new();
+    let mut unchanged = String::new();
+    let mut raw_hunk = String::new();
+    let mut ours = String::new();
+    let mut base: Option<String> = None;
+    let mut theirs = String::new();
+    let mut section = MarkerSection::Outside;
+
+    for line in content.split_inclusive('\n') {
+        match section {
+            MarkerSection::Outside => {
+                if is_conflict_marker(line, "<<<<<<<") {
+                    raw_hunk.push_str(line);
+                    section = MarkerSection::Ours;
+                } else {
+                    unchanged.push_str(line);
+                }
+            }
+            MarkerSection::Ours => {
+                raw_hunk.push_str(line);
+                if is_conflict_marker(line, "|||||||") {
+                    base = Some(String::new());
+                    section = MarkerSection::Base;
+                } else if is_conflict_marker(line, "=======") {
+                    section = MarkerSection::Theirs;
+                } else {
+                    ours.push_str(line);
+                }
+            }
+            MarkerSection::Base => {
+                raw_hunk.push_str(line);
+                if is_conflict_marker(line, "=======") {
+                    section = MarkerSection::Theirs;
+                } else if let Some(base) = base.as_mut() {
+                    base.push_str(line);
+                }
+            }
+            MarkerSection::Theirs => {
+                if is_conflict_marker(line, ">>>>>>>") {
+                    if !unchanged.is_empty() {
+                        regions.push(MergeRegion::Unchanged(std::mem::take(&mut unchanged)));
+                    }
+                    regions.push(MergeRegion::Conflict {
+                        ours: std::mem::take(&mut ours),
+                        theirs: std::mem::take(&mut theirs),
+                        base: base.take(),
+                    });
+                    raw_hunk.clear();
+                    section = MarkerSection::Outside;
+                } else {
+                    raw_hunk.push_str(line);
+                    theirs.push_str(line);
+                }
+            }
+        }
+    }
+
+    if section != MarkerSection::Outside {
+        unchanged.push_str(&raw_hunk);
+    }
+    if !unchanged.is_empty() {
+        regions.push(MergeRegion::Unchanged(unchanged));
+    }
+
+    regions
+}
+
 async fn git_list_file_stages(
     executor: &crate::git::GitExecutor,
     repo: &Path,
@@ -196,15 +370,40 @@ pub async fn cmd_mark_resolved_impl(
     git_run_void_with_event(&app, &state, repo_path, args, TIMEOUT_LOCAL).await
 }
 
+pub async fn cmd_resolve_conflict_with_content_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+    content: String,
+    encoding: Option<String>,
+    repo_path: Option<String>,
+) -> Result<ResolveConflictResult, String> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    write_repo_file(&state, &r_path, &path, &content, encoding)?;
+
+    let args: Vec<String> = vec!["add".into(), "--".into(), path];
+    git_run_void_with_event(&app, &state, Some(r_path), args, TIMEOUT_LOCAL).await?;
+
+    // Saving with leftover markers is allowed, but the UI should warn about it
+    let has_conflict_markers = content.split_inclusive('\n').any(|line| {
+        ["<<<<<<<", "=======", ">>>>>>>"]
+            .iter()
+            .any(|marker| is_conflict_marker(line, marker))
+    });
+
+    Ok(ResolveConflictResult {
+        has_conflict_markers,
+    })
+}
+
 pub async fn cmd_check_conflict_state_impl(
     state: State<'_, AppState>,
     repo_path: Option<String>,
 ) -> Result<bool, String> {
     let path = resolve_repo_path(&state, repo_path)?;
-    let p = Path::new(&path);
-    let git_dir = p.join(".git");
+    let git_dir = resolve_git_dir(Path::new(&path));
 
-    let (is_merging, is_rebasing, is_cherry_picking, is_reverting) =
+    let (is_merging, is_rebasing, is_cherry_picking, is_reverting, _) =
         detect_operation_flags(&git_dir);
 
     if !is_merging && !is_rebasing && !is_cherry_picking && !is_reverting {
@@ -322,10 +521,9 @@ pub async fn cmd_get_operation_state_impl(
     repo_path: Option<String>,
 ) -> Result<GitOperationState, String> {
     let path = resolve_repo_path(&state, repo_path)?;
-    let p = Path::new(&path);
-    let git_dir = p.join(".git");
+    let git_dir = resolve_git_dir(Path::new(&path));
 
-    let (is_merging, is_rebasing, is_cherry_picking, is_reverting) =
+    let (is_merging, is_rebasing, is_cherry_picking, is_reverting, is_bisecting) =
         detect_operation_flags(&git_dir);
 
     let resp = git_run(
@@ -346,6 +544,7 @@ pub async fn cmd_get_operation_state_impl(
         is_rebasing,
         is_cherry_picking,
         is_reverting,
+        is_bisecting,
         has_conflicts,
         conflict_paths,
         ours_commit,
@@ -354,3 +553,87 @@ pub async fn cmd_get_operation_state_impl(
         theirs_branch,
     })
 }
+
+pub async fn cmd_get_repo_state_impl(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<RepoState, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let git_dir = resolve_git_dir(Path::new(&path));
+
+    let resp = git_run(
+        &state,
+        Some(path.clone()),
+        &["status", "--porcelain"],
+        TIMEOUT_LOCAL,
+    )
+    .await?;
+    let conflict_paths = collect_conflict_paths(&resp.stdout);
+
+    let operation = current_operation(&git_dir);
+
+    let rebase_step = if operation == RepoOperation::Rebasing {
+        super::rebase_commands::read_rebase_step(&state, &path, &git_dir).await
+    } else {
+        None
+    };
+
+    // `--bisect` walks refs/bisect/bad minus refs/bisect/good-*; it fails
+    // until a bad commit has been marked.
+    let bisect_remaining = if operation == RepoOperation::Bisecting {
+        git_run(
+            &state,
+            Some(path),
+            &["rev-list", "--bisect", "--bisect-vars"],
+            TIMEOUT_LOCAL,
+        )
+        .await
+        .ok()
+        .and_then(|r| parse_bisect_remaining(&r.stdout))
+    } else {
+        None
+    };
+
+    Ok(RepoState {
+        operation,
+        conflict_paths,
+        rebase_step,
+        bisect_remaining,
+    })
+}
+
+pub async fn cmd_abort_current_operation_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<AbortedOperation, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let operation = current_operation(&resolve_git_dir(Path::new(&path)));
+
+    let (args, command_type) = match operation {
+        RepoOperation::Clean => return Err("No operation to abort.".to_string()),
+        RepoOperation::Rebasing => (["rebase", "--abort"], GitCommandType::Rebase),
+        RepoOperation::Merging => (["merge", "--abort"], GitCommandType::Merge),
+        RepoOperation::CherryPicking => (["cherry-pick", "--abort"], GitCommandType::CherryPick),
+        RepoOperation::Reverting => (["revert", "--abort"], GitCommandType::Revert),
+        RepoOperation::Bisecting => (["bisect", "reset"], GitCommandType::Other),
+    };
+
+    let args = args.iter().map(|arg| arg.to_string()).collect();
+    let result =
+        git_run_result_with_event(&app, &state, Some(path), args, TIMEOUT_LOCAL, command_type)
+            .await?;
+    Ok(AbortedOperation { operation, result })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_bisect_remaining_reads_bisect_nr() {
+        let output = "bisect_rev='abc123'\nbisect_nr=6\nbisect_good=3\nbisect_bad=2\nbisect_all=14\nbisect_steps=3\n";
+        assert_eq!(parse_bisect_remaining(output), Some(6));
+        assert_eq!(parse_bisect_remaining(""), None);
+    }
+}
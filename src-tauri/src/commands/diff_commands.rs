@@ -7,6 +7,16 @@ pub struct StageLineSelection {
     pub new_line_number: Option<u32>,
 }
 
+/// A window of a file's lines, used to expand hidden context in the diff view.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffContext {
+    /// 1-based line number of `lines[0]`.
+    pub start_line: u32,
+    pub lines: Vec<String>,
+    pub total_lines: u32,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ParsedPatchLineKind {
     Add,
@@ -281,31 +291,112 @@ pub async fn cmd_get_diff_file_impl(
     staged: bool,
     encoding: Option<String>,
     repo_path: Option<String>,
-) -> Result<String, String> {
+) -> Result<FileContent, String> {
     let path = resolve_repo_path(&state, repo_path)?;
+    let stdout = fetch_file_diff(&state, &path, &file_path, staged).await?;
+
+    // The diff size is only known once git has produced it
+    let size = stdout.len() as u64;
+    if size > load_file_view_limit(&state)? {
+        return Ok(FileContent::too_large(size));
+    }
 
+    let settings = state.settings.lock().map_err(|e| e.to_string())?;
+    let content =
+        crate::git::encoding::decode_bytes(&stdout, Path::new(&file_path), &settings, encoding);
+
+    Ok(FileContent::text(content, size))
+}
+
+/// Raw `git diff [--cached] -- <file>` output.
+async fn fetch_file_diff(
+    state: &State<'_, AppState>,
+    repo_path: &str,
+    file_path: &str,
+    staged: bool,
+) -> Result<Vec<u8>, String> {
     let mut args = vec!["diff".to_string()];
     if staged {
         args.push("--cached".to_string());
     }
     args.push("--".to_string());
-    args.push(file_path.clone());
+    args.push(file_path.to_string());
 
     let resp = state
         .git
-        .run_with_output_bytes(Path::new(&path), &args, TIMEOUT_LOCAL)
+        .run_with_output_bytes(Path::new(repo_path), &args, TIMEOUT_LOCAL)
         .await
         .map_err(|e| e.to_string())?;
+    Ok(resp.stdout)
+}
 
-    let settings = state.settings.lock().map_err(|e| e.to_string())?;
-    let content = crate::git::encoding::decode_bytes(
-        &resp.stdout,
-        Path::new(&file_path),
-        &settings,
-        encoding,
-    );
+/// Payload of a `diff-chunk` event: one hunk of a streamed diff.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffChunkEvent {
+    pub stream_id: String,
+    pub file_path: String,
+    pub index: usize,
+    pub hunk: DiffHunk,
+}
+
+/// Payload of the `diff-done` event that ends a streamed diff. No chunks are
+/// sent when the diff is over the file view limit.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffDoneEvent {
+    pub stream_id: String,
+    pub file_path: String,
+    pub hunk_count: usize,
+    pub too_large: bool,
+    pub size: u64,
+}
+
+pub async fn cmd_get_diff_file_stream_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    stream_id: String,
+    file_path: String,
+    staged: bool,
+    encoding: Option<String>,
+    repo_path: Option<String>,
+) -> Result<DiffDoneEvent, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let stdout = fetch_file_diff(&state, &path, &file_path, staged).await?;
+
+    let size = stdout.len() as u64;
+    let too_large = size > load_file_view_limit(&state)?;
+    let mut hunk_count = 0;
+    if !too_large {
+        let decoded = {
+            let settings = state.settings.lock().map_err(|e| e.to_string())?;
+            crate::git::encoding::decode_bytes(&stdout, Path::new(&file_path), &settings, encoding)
+        };
+        let hunks = parse_diff_output(&decoded)
+            .into_iter()
+            .flat_map(|file| file.hunks);
+        for (index, hunk) in hunks.enumerate() {
+            let chunk = DiffChunkEvent {
+                stream_id: stream_id.clone(),
+                file_path: file_path.clone(),
+                index,
+                hunk,
+            };
+            app.emit("diff-chunk", chunk).map_err(|e| e.to_string())?;
+            hunk_count += 1;
+        }
+    }
 
-    Ok(content)
+    let done = DiffDoneEvent {
+        stream_id,
+        file_path,
+        hunk_count,
+        too_large,
+        size,
+    };
+    app.emit("diff-done", done.clone())
+        .map_err(|e| e.to_string())?;
+    Ok(done)
 }
 
 pub async fn cmd_get_file_base_content_impl(
@@ -314,14 +405,20 @@ pub async fn cmd_get_file_base_content_impl(
     staged: bool,
     encoding: Option<String>,
     repo_path: Option<String>,
-) -> Result<String, String> {
+) -> Result<FileContent, String> {
     let path = resolve_repo_path(&state, repo_path)?;
     let show_arg = if staged {
         format!("HEAD:{}", file_path)
     } else {
         format!(":{}", file_path)
     };
-    let args = vec!["show".to_string(), show_arg];
+    let limit = load_file_view_limit(&state)?;
+    if let Some(size) = git_object_size(&state, &path, &show_arg).await {
+        if size > limit {
+            return Ok(FileContent::too_large(size));
+        }
+    }
+    let args = vec!["show".to_string(), show_arg.clone()];
 
     match state
         .git
@@ -329,29 +426,104 @@ pub async fn cmd_get_file_base_content_impl(
         .await
     {
         Ok(resp) => {
+            let content = resolve_lfs_content(&state, &path, &show_arg, resp.stdout).await;
+            let size = content.len() as u64;
+            if size > limit {
+                return Ok(FileContent::too_large(size));
+            }
             let settings = state.settings.lock().map_err(|e| e.to_string())?;
-            Ok(crate::git::encoding::decode_bytes(
-                &resp.stdout,
-                Path::new(&file_path),
-                &settings,
-                encoding,
+            Ok(FileContent::text(
+                crate::git::encoding::decode_bytes(
+                    &content,
+                    Path::new(&file_path),
+                    &settings,
+                    encoding,
+                ),
+                size,
             ))
         }
-        Err(_) => Ok(String::new()),
+        Err(_) => Ok(FileContent::text(String::new(), 0)),
+    }
+}
+
+/// Lines `around_line ± context_lines`, clamped to the content's bounds so a
+/// request past end-of-file returns the tail instead of an error.
+fn context_window(content: &str, around_line: u32, context_lines: u32) -> DiffContext {
+    let all_lines: Vec<&str> = content.lines().collect();
+    let total_lines = all_lines.len() as u32;
+    if total_lines == 0 {
+        return DiffContext {
+            start_line: 1,
+            lines: Vec::new(),
+            total_lines,
+        };
+    }
+
+    let around = around_line.clamp(1, total_lines);
+    let start = around.saturating_sub(context_lines).max(1);
+    let end = around.saturating_add(context_lines).min(total_lines);
+    DiffContext {
+        start_line: start,
+        lines: all_lines[(start - 1) as usize..end as usize]
+            .iter()
+            .map(|line| line.to_string())
+            .collect(),
+        total_lines,
     }
 }
 
+/// Context from the file at `commit`, defaulting to HEAD.
+pub async fn cmd_get_diff_context_impl(
+    state: State<'_, AppState>,
+    file_path: String,
+    commit: Option<String>,
+    around_line: u32,
+    context_lines: u32,
+    encoding: Option<String>,
+    repo_path: Option<String>,
+) -> Result<DiffContext, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let commit = commit
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| "HEAD".to_string());
+    if commit.starts_with('-') {
+        return Err(format!("Invalid commit: {}", commit));
+    }
+
+    let show_arg = format!("{}:{}", commit, file_path);
+    let args = vec!["show".to_string(), show_arg.clone()];
+    let resp = state
+        .git
+        .run_with_output_bytes(Path::new(&path), &args, TIMEOUT_QUICK)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let content = resolve_lfs_content(&state, &path, &show_arg, resp.stdout).await;
+    let decoded = {
+        let settings = state.settings.lock().map_err(|e| e.to_string())?;
+        crate::git::encoding::decode_bytes(&content, Path::new(&file_path), &settings, encoding)
+    };
+    Ok(context_window(&decoded, around_line, context_lines))
+}
+
 pub async fn cmd_get_file_modified_content_impl(
     state: State<'_, AppState>,
     file_path: String,
     staged: bool,
     encoding: Option<String>,
     repo_path: Option<String>,
-) -> Result<String, String> {
+) -> Result<FileContent, String> {
     let path = resolve_repo_path(&state, repo_path)?;
+    let limit = load_file_view_limit(&state)?;
 
     if staged {
         let show_arg = format!(":{}", file_path);
+        if let Some(size) = git_object_size(&state, &path, &show_arg).await {
+            if size > limit {
+                return Ok(FileContent::too_large(size));
+            }
+        }
         let args = vec!["show".to_string(), show_arg];
         match state
             .git
@@ -360,28 +532,40 @@ pub async fn cmd_get_file_modified_content_impl(
         {
             Ok(resp) => {
                 let settings = state.settings.lock().map_err(|e| e.to_string())?;
-                Ok(crate::git::encoding::decode_bytes(
-                    &resp.stdout,
-                    Path::new(&file_path),
-                    &settings,
-                    encoding,
+                Ok(FileContent::text(
+                    crate::git::encoding::decode_bytes(
+                        &resp.stdout,
+                        Path::new(&file_path),
+                        &settings,
+                        encoding,
+                    ),
+                    resp.stdout.len() as u64,
                 ))
             }
-            Err(_) => Ok(String::new()),
+            Err(_) => Ok(FileContent::text(String::new(), 0)),
         }
     } else {
         let full_path = Path::new(&path).join(&file_path);
+        // Check the size first so a huge file is never read into memory
+        if let Ok(meta) = std::fs::metadata(&full_path) {
+            if meta.len() > limit {
+                return Ok(FileContent::too_large(meta.len()));
+            }
+        }
         match std::fs::read(&full_path) {
             Ok(bytes) => {
                 let settings = state.settings.lock().map_err(|e| e.to_string())?;
-                Ok(crate::git::encoding::decode_bytes(
-                    &bytes,
-                    Path::new(&file_path),
-                    &settings,
-                    encoding,
+                Ok(FileContent::text(
+                    crate::git::encoding::decode_bytes(
+                        &bytes,
+                        Path::new(&file_path),
+                        &settings,
+                        encoding,
+                    ),
+                    bytes.len() as u64,
                 ))
             }
-            Err(_) => Ok(String::new()),
+            Err(_) => Ok(FileContent::text(String::new(), 0)),
         }
     }
 }
@@ -395,10 +579,7 @@ pub async fn cmd_git_stage_line_impl(
 ) -> Result<(), String> {
     let r_path = resolve_repo_path(&state, repo_path)?;
 
-    let exclusions = {
-        let settings = state.settings.lock().map_err(|e| e.to_string())?;
-        settings.excluded_files.clone()
-    };
+    let exclusions = load_exclusion_patterns(&state, &r_path)?;
 
     if is_excluded(&path, &exclusions) {
         return Err(format!("File {} is excluded from git operations", path));
@@ -429,8 +610,9 @@ pub async fn cmd_git_stage_line_impl(
     let parsed = parse_unstaged_zero_context_diff(&diff_resp.stdout)?;
     let patch = build_stage_line_patch(&parsed, &line)?;
 
-    let temp_patch_path =
-        std::env::temp_dir().join(format!("git-tools-stage-line-{}.patch", Uuid::new_v4()));
+    let mut temp_files = TempFileGuard::default();
+    let temp_patch_path = temp_files
+        .track(std::env::temp_dir().join(format!("git-tools-stage-line-{}.patch", Uuid::new_v4())));
     std::fs::write(&temp_patch_path, patch.as_bytes())
         .map_err(|e| format!("Failed to write temporary patch file: {}", e))?;
 
@@ -442,14 +624,11 @@ pub async fn cmd_git_stage_line_impl(
         temp_patch_path.to_string_lossy().to_string(),
     ];
 
-    let apply_result = state
+    state
         .git
         .run(Path::new(&r_path), &apply_args, TIMEOUT_LOCAL)
-        .await;
-
-    let _ = std::fs::remove_file(&temp_patch_path);
-
-    apply_result.map_err(|e| e.to_string())?;
+        .await
+        .map_err(|e| e.to_string())?;
 
     emit_git_change_event(&app)?;
     Ok(())
@@ -464,10 +643,7 @@ pub async fn cmd_git_unstage_line_impl(
 ) -> Result<(), String> {
     let r_path = resolve_repo_path(&state, repo_path)?;
 
-    let exclusions = {
-        let settings = state.settings.lock().map_err(|e| e.to_string())?;
-        settings.excluded_files.clone()
-    };
+    let exclusions = load_exclusion_patterns(&state, &r_path)?;
 
     if is_excluded(&path, &exclusions) {
         return Err(format!("File {} is excluded from git operations", path));
@@ -499,8 +675,10 @@ pub async fn cmd_git_unstage_line_impl(
     let parsed = parse_unstaged_zero_context_diff(&diff_resp.stdout)?;
     let patch = build_stage_line_patch(&parsed, &line)?;
 
-    let temp_patch_path =
-        std::env::temp_dir().join(format!("git-tools-unstage-line-{}.patch", Uuid::new_v4()));
+    let mut temp_files = TempFileGuard::default();
+    let temp_patch_path = temp_files.track(
+        std::env::temp_dir().join(format!("git-tools-unstage-line-{}.patch", Uuid::new_v4())),
+    );
     std::fs::write(&temp_patch_path, patch.as_bytes())
         .map_err(|e| format!("Failed to write temporary patch file: {}", e))?;
 
@@ -513,15 +691,102 @@ pub async fn cmd_git_unstage_line_impl(
         temp_patch_path.to_string_lossy().to_string(),
     ];
 
-    let apply_result = state
+    state
         .git
         .run(Path::new(&r_path), &apply_args, TIMEOUT_LOCAL)
-        .await;
+        .await
+        .map_err(|e| e.to_string())?;
 
-    let _ = std::fs::remove_file(&temp_patch_path);
+    emit_git_change_event(&app)?;
+    Ok(())
+}
 
-    apply_result.map_err(|e| e.to_string())?;
+/// Whether `content` looks like something `git apply` accepts: a git diff, or
+/// a plain unified diff with `---`/`+++` headers followed by a hunk.
+fn looks_like_unified_diff(content: &str) -> bool {
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        if line.starts_with("diff --git ") {
+            return true;
+        }
+        if line.starts_with("--- ") {
+            let has_new_header = lines.next().is_some_and(|l| l.starts_with("+++ "));
+            let has_hunk = lines.next().is_some_and(|l| l.starts_with("@@ "));
+            if has_new_header && has_hunk {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+pub async fn cmd_git_apply_patch_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    patch_content: String,
+    cached: bool,
+    reverse: bool,
+    repo_path: Option<String>,
+) -> Result<(), String> {
+    if !looks_like_unified_diff(&patch_content) {
+        return Err("The content is not a unified diff".to_string());
+    }
+    let r_path = resolve_repo_path(&state, repo_path)?;
+
+    let mut temp_files = TempFileGuard::default();
+    let temp_patch_path = temp_files
+        .track(std::env::temp_dir().join(format!("git-tools-apply-{}.patch", Uuid::new_v4())));
+    std::fs::write(&temp_patch_path, patch_content.as_bytes())
+        .map_err(|e| format!("Failed to write temporary patch file: {}", e))?;
+
+    let mut apply_args: Vec<String> = vec!["apply".into()];
+    if cached {
+        apply_args.push("--cached".into());
+    }
+    if reverse {
+        apply_args.push("--reverse".into());
+    }
+    apply_args.push("--whitespace=nowarn".into());
+    apply_args.push(temp_patch_path.to_string_lossy().to_string());
+
+    // Run unchecked so the rejected hunks git reports reach the user verbatim
+    let resp = state
+        .git
+        .run_unchecked(Path::new(&r_path), &apply_args, TIMEOUT_LOCAL)
+        .await
+        .map_err(|e| e.to_string())?;
+    if resp.exit_code != 0 {
+        return Err(format!(
+            "Patch does not apply cleanly:\n{}",
+            resp.stderr.trim()
+        ));
+    }
 
     emit_git_change_event(&app)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_git_and_plain_unified_diffs() {
+        let git_diff = "diff --git a/src/main.rs b/src/main.rs\nindex 1111111..2222222 100644\n--- a/src/main.rs\n+++ b/src/main.rs\n@@ -1 +1 @@\n-old\n+new\n";
+        assert!(looks_like_unified_diff(git_diff));
+
+        // `diff -u` output, after a mail header
+        let plain_diff = "Subject: fix\n\n--- a.txt\t2024-05-01\n+++ b.txt\t2024-05-02\n@@ -1,2 +1,2 @@\n a\n-b\n+c\n";
+        assert!(looks_like_unified_diff(plain_diff));
+    }
+
+    #[test]
+    fn rejects_text_that_is_not_a_diff() {
+        assert!(!looks_like_unified_diff(""));
+        assert!(!looks_like_unified_diff("just some notes\n"));
+        // A markdown rule followed by a list is not a file header
+        assert!(!looks_like_unified_diff("--- \n+++ bold\ntext\n"));
+        assert!(!looks_like_unified_diff("--- a.txt\n@@ -1 +1 @@\n"));
+        assert!(!looks_like_unified_diff("@@ -1 +1 @@\n-a\n+b\n"));
+    }
+}
@@ -0,0 +1,144 @@
+use super::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HookInfo {
+    pub name: String,
+    pub enabled: bool,
+    pub is_sample: bool,
+}
+
+const SAMPLE_SUFFIX: &str = ".sample";
+const DISABLED_SUFFIX: &str = ".disabled";
+
+/// Resolve the directory git actually runs hooks from, honouring a
+/// `core.hooksPath` override (relative values are relative to the repo root).
+async fn resolve_hooks_dir(state: &State<'_, AppState>, repo_path: &str) -> PathBuf {
+    let configured = git_run(
+        state,
+        Some(repo_path.to_string()),
+        &["config", "--get", "core.hooksPath"],
+        TIMEOUT_QUICK,
+    )
+    .await
+    .ok()
+    .map(|resp| resp.stdout.trim().to_string())
+    .filter(|value| !value.is_empty());
+
+    match configured {
+        Some(value) => Path::new(repo_path).join(value),
+        None => resolve_common_git_dir(Path::new(repo_path)).join("hooks"),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|meta| meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "windows")]
+fn is_executable(_path: &Path) -> bool {
+    // Git for Windows runs hooks through its bundled shell regardless of mode bits
+    true
+}
+
+fn validate_hook_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\'])
+        && !name.ends_with(SAMPLE_SUFFIX)
+        && !name.ends_with(DISABLED_SUFFIX);
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid hook name: {}", name))
+    }
+}
+
+pub async fn cmd_list_git_hooks_impl(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<Vec<HookInfo>, String> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    let hooks_dir = resolve_hooks_dir(&state, &r_path).await;
+
+    let entries = match std::fs::read_dir(&hooks_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read hooks directory: {}", e)),
+    };
+
+    let mut hooks = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let file_name = entry.file_name().to_string_lossy().to_string();
+
+        let hook = if let Some(name) = file_name.strip_suffix(SAMPLE_SUFFIX) {
+            HookInfo {
+                name: name.to_string(),
+                enabled: false,
+                is_sample: true,
+            }
+        } else if let Some(name) = file_name.strip_suffix(DISABLED_SUFFIX) {
+            HookInfo {
+                name: name.to_string(),
+                enabled: false,
+                is_sample: false,
+            }
+        } else {
+            HookInfo {
+                enabled: is_executable(&path),
+                name: file_name,
+                is_sample: false,
+            }
+        };
+        hooks.push(hook);
+    }
+
+    hooks.sort_by(|a, b| a.name.cmp(&b.name).then(a.is_sample.cmp(&b.is_sample)));
+    Ok(hooks)
+}
+
+pub async fn cmd_toggle_git_hook_impl(
+    state: State<'_, AppState>,
+    name: String,
+    enable: bool,
+    repo_path: Option<String>,
+) -> Result<(), String> {
+    validate_hook_name(&name)?;
+
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    let hooks_dir = resolve_hooks_dir(&state, &r_path).await;
+
+    let active = hooks_dir.join(&name);
+    let disabled = hooks_dir.join(format!("{}{}", name, DISABLED_SUFFIX));
+    let (from, to) = if enable {
+        (disabled, active)
+    } else {
+        (active, disabled)
+    };
+
+    if !from.is_file() {
+        return Err(format!(
+            "Hook '{}' is already {}",
+            name,
+            if enable { "enabled" } else { "disabled" }
+        ));
+    }
+    if to.exists() {
+        return Err(format!(
+            "Cannot toggle hook '{}': {} already exists",
+            name,
+            to.display()
+        ));
+    }
+
+    std::fs::rename(&from, &to).map_err(|e| format!("Failed to toggle hook '{}': {}", name, e))
+}
@@ -0,0 +1,214 @@
+use super::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LfsFileStatus {
+    pub path: String,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LfsStatus {
+    /// Patterns from `.gitattributes` handled by LFS (`git lfs track`).
+    pub tracked_patterns: Vec<String>,
+    /// Files currently stored in LFS (`git lfs ls-files`).
+    pub tracked_files: Vec<String>,
+    /// LFS files with pending changes (`git lfs status --porcelain`).
+    pub modified_files: Vec<LfsFileStatus>,
+}
+
+const LFS_NOT_INSTALLED: &str =
+    "Git LFS not installed. Install it from https://git-lfs.com and run `git lfs install`.";
+
+/// Make sure the `git lfs` subcommand exists before running anything else,
+/// so users get a clear message instead of "'lfs' is not a git command".
+async fn ensure_lfs_installed(state: &State<'_, AppState>, repo_path: &str) -> Result<(), String> {
+    match git_run(
+        state,
+        Some(repo_path.to_string()),
+        &["lfs", "version"],
+        TIMEOUT_QUICK,
+    )
+    .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) if e.contains("is not a git command") => Err(LFS_NOT_INSTALLED.to_string()),
+        Err(e) => Err(e),
+    }
+}
+
+fn validate_lfs_pattern(pattern: &str) -> Result<String, String> {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Err("LFS pattern cannot be empty".to_string());
+    }
+    if pattern.starts_with('-') {
+        return Err(format!("Invalid LFS pattern: {}", pattern));
+    }
+    Ok(pattern.to_string())
+}
+
+/// Parse the "Listing tracked patterns" section of `git lfs track`:
+///
+/// ```text
+/// Listing tracked patterns
+///     *.psd (.gitattributes)
+/// Listing excluded patterns
+/// ```
+fn parse_tracked_patterns(output: &str) -> Vec<String> {
+    let mut patterns = Vec::new();
+    let mut in_tracked = false;
+    for line in output.lines() {
+        if line.starts_with("Listing tracked patterns") {
+            in_tracked = true;
+            continue;
+        }
+        if line.starts_with("Listing") {
+            in_tracked = false;
+            continue;
+        }
+        if !in_tracked {
+            continue;
+        }
+        let entry = line.trim();
+        let pattern = match entry.rfind(" (") {
+            Some(idx) if entry.ends_with(')') => &entry[..idx],
+            _ => entry,
+        };
+        if !pattern.is_empty() {
+            patterns.push(pattern.to_string());
+        }
+    }
+    patterns
+}
+
+/// Parse `git lfs status --porcelain`, which uses the `XY path` short format.
+fn parse_lfs_porcelain(output: &str) -> Vec<LfsFileStatus> {
+    output
+        .lines()
+        .filter(|line| line.len() > 3)
+        .map(|line| LfsFileStatus {
+            status: line[..2].trim().to_string(),
+            path: line[3..].trim().to_string(),
+        })
+        .collect()
+}
+
+pub async fn cmd_git_lfs_track_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    pattern: String,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    ensure_lfs_installed(&state, &r_path).await?;
+
+    let pattern = validate_lfs_pattern(&pattern)?;
+    let args: Vec<String> = vec!["lfs".into(), "track".into(), pattern];
+    git_run_result_with_event(
+        &app,
+        &state,
+        Some(r_path),
+        args,
+        TIMEOUT_LOCAL,
+        GitCommandType::Other,
+    )
+    .await
+}
+
+pub async fn cmd_git_lfs_untrack_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    pattern: String,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    ensure_lfs_installed(&state, &r_path).await?;
+
+    let pattern = validate_lfs_pattern(&pattern)?;
+    let args: Vec<String> = vec!["lfs".into(), "untrack".into(), pattern];
+    git_run_result_with_event(
+        &app,
+        &state,
+        Some(r_path),
+        args,
+        TIMEOUT_LOCAL,
+        GitCommandType::Other,
+    )
+    .await
+}
+
+pub async fn cmd_git_lfs_status_impl(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<LfsStatus, String> {
+    let r_path = resolve_repo_path(&state, repo_path)?;
+    ensure_lfs_installed(&state, &r_path).await?;
+    let repo = Some(r_path);
+
+    let track = git_run(&state, repo.clone(), &["lfs", "track"], TIMEOUT_LOCAL).await?;
+    let files = git_run(
+        &state,
+        repo.clone(),
+        &["lfs", "ls-files", "-n"],
+        TIMEOUT_LOCAL,
+    )
+    .await?;
+    let status = git_run(
+        &state,
+        repo,
+        &["lfs", "status", "--porcelain"],
+        TIMEOUT_LOCAL,
+    )
+    .await?;
+
+    Ok(LfsStatus {
+        tracked_patterns: parse_tracked_patterns(&track.stdout),
+        tracked_files: files
+            .stdout
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect(),
+        modified_files: parse_lfs_porcelain(&status.stdout),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tracked_patterns() {
+        for (output, expected) in [
+            ("", vec![]),
+            ("Listing tracked patterns\n", vec![]),
+            (
+                "Listing tracked patterns\n    *.psd (.gitattributes)\n    assets/**/*.bin (assets/.gitattributes)\nListing excluded patterns\n    *.txt (.gitattributes)\n",
+                vec!["*.psd", "assets/**/*.bin"],
+            ),
+            (
+                "Listing tracked patterns\r\n    *.zip (.gitattributes)\r\n",
+                vec!["*.zip"],
+            ),
+            (
+                "Listing tracked patterns\n    my (draft).psd\n",
+                vec!["my (draft).psd"],
+            ),
+        ] {
+            assert_eq!(parse_tracked_patterns(output), expected, "{:?}", output);
+        }
+    }
+
+    #[test]
+    fn test_parse_lfs_porcelain() {
+        let parsed = parse_lfs_porcelain(" M assets/logo.psd\nA  video.mp4\r\n\n??\n");
+        let pairs: Vec<(&str, &str)> = parsed
+            .iter()
+            .map(|entry| (entry.status.as_str(), entry.path.as_str()))
+            .collect();
+        assert_eq!(pairs, vec![("M", "assets/logo.psd"), ("A", "video.mp4")]);
+    }
+}
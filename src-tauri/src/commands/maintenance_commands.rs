@@ -0,0 +1,315 @@
+use super::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FsckResult {
+    /// fsck succeeded and reported no problems.
+    pub healthy: bool,
+    /// Unreferenced objects as `<type> <sha>`, e.g. `commit 1a2b...`; dangling
+    /// commits can be recovered by pointing a branch at them.
+    pub dangling: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GcResult {
+    /// Size of the git directory in bytes before and after the run.
+    pub size_before: u64,
+    pub size_after: u64,
+    /// Set when lock files showed another git process at work; gc leaves
+    /// locked refs alone, so less may have been reclaimed.
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoSize {
+    pub git_dir_bytes: u64,
+    /// Everything outside `.git`, including untracked and ignored files.
+    pub working_tree_bytes: u64,
+    /// Loose plus packed objects.
+    pub object_count: u64,
+    pub pack_count: u64,
+}
+
+/// Report a line of git's stderr as a `git-progress` event.
+fn emit_progress(app: &AppHandle, command: &str, line: &str) {
+    let _ = app.emit("git-progress", json!({ "command": command, "line": line }));
+}
+
+/// Sort `fsck` output into dangling objects and problems. Progress meters
+/// share stderr with the errors, so only `error:`/`fatal:` lines are kept
+/// from there; on stdout anything that isn't a dangling object or a notice
+/// (such as `missing blob ...` or `broken link from ...`) is a problem.
+fn parse_fsck_output(stdout: &str, stderr: &str) -> (Vec<String>, Vec<String>) {
+    let mut dangling = Vec::new();
+    let mut errors = Vec::new();
+    for line in stdout.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if let Some(object) = line.strip_prefix("dangling ") {
+            dangling.push(object.to_string());
+        } else if !line.starts_with("notice:") {
+            errors.push(line.to_string());
+        }
+    }
+    errors.extend(
+        stderr
+            .lines()
+            .map(str::trim)
+            .filter(|l| l.starts_with("error") || l.starts_with("fatal"))
+            .map(str::to_string),
+    );
+    (dangling, errors)
+}
+
+pub async fn cmd_git_fsck_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<FsckResult, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    // stderr is a pipe, so progress has to be asked for
+    let args: Vec<String> = vec!["fsck".into(), "--full".into(), "--progress".into()];
+
+    // A corrupt repository makes fsck exit non-zero, which the executor turns
+    // into an error; collect the output here so it can still be parsed
+    let stdout = std::sync::Mutex::new(String::new());
+    let stderr = std::sync::Mutex::new(String::new());
+    let result = state
+        .git
+        .run_streaming(
+            Path::new(&path),
+            &args,
+            Vec::new(),
+            TIMEOUT_NETWORK,
+            |stream, line| {
+                let collected = if stream == "stdout" { &stdout } else { &stderr };
+                if let Ok(mut collected) = collected.lock() {
+                    collected.push_str(line);
+                    collected.push('\n');
+                }
+                if stream == "stderr" {
+                    emit_progress(&app, "fsck", line);
+                }
+            },
+        )
+        .await;
+
+    let succeeded = match result {
+        Ok(_) => true,
+        Err(GitError::CommandError(_)) => false,
+        Err(e) => return Err(e.to_string()),
+    };
+    let stdout = stdout.into_inner().map_err(|e| e.to_string())?;
+    let stderr = stderr.into_inner().map_err(|e| e.to_string())?;
+    let (dangling, errors) = parse_fsck_output(&stdout, &stderr);
+    Ok(FsckResult {
+        healthy: succeeded && errors.is_empty(),
+        dangling,
+        errors,
+    })
+}
+
+/// Total size of the files under `dir`; unreadable entries count as zero.
+/// Entries named `.git` are skipped, so a working tree's total leaves out
+/// the repository's (and any submodule's) metadata.
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_name() != ".git")
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// `dir_size` on a blocking thread; large trees take a while to walk.
+async fn dir_size_blocking(dir: PathBuf) -> Result<u64, String> {
+    tokio::task::spawn_blocking(move || dir_size(&dir))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Lock files git holds while writing the index or refs, relative to the
+/// git directory.
+fn find_lock_files(git_dir: &Path, common_dir: &Path) -> Vec<String> {
+    let mut locks = vec![
+        git_dir.join("index.lock"),
+        git_dir.join("HEAD.lock"),
+        common_dir.join("packed-refs.lock"),
+        common_dir.join("gc.pid"),
+    ];
+    collect_ref_locks(&common_dir.join("refs"), &mut locks);
+    locks
+        .into_iter()
+        .filter(|lock| lock.is_file())
+        .map(|lock| {
+            lock.strip_prefix(common_dir)
+                .unwrap_or(&lock)
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect()
+}
+
+fn collect_ref_locks(dir: &Path, locks: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_ref_locks(&path, locks);
+        } else if path.extension().is_some_and(|ext| ext == "lock") {
+            locks.push(path);
+        }
+    }
+}
+
+/// `gc` has no `--progress` flag and only shows progress on a terminal, so
+/// the events carry whatever it writes to stderr.
+pub async fn cmd_git_gc_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    aggressive: bool,
+    repo_path: Option<String>,
+) -> Result<GcResult, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let git_dir = resolve_git_dir(Path::new(&path));
+    let common_dir = resolve_common_git_dir(Path::new(&path));
+
+    let locks = find_lock_files(&git_dir, &common_dir);
+    let warning = (!locks.is_empty()).then(|| {
+        format!(
+            "Another git operation appears to be in progress ({} locked)",
+            locks.join(", ")
+        )
+    });
+
+    let size_before = dir_size_blocking(common_dir.clone()).await?;
+    let mut args: Vec<String> = vec!["gc".into()];
+    if aggressive {
+        args.push("--aggressive".into());
+    }
+    state
+        .git
+        .run_streaming(
+            Path::new(&path),
+            &args,
+            Vec::new(),
+            TIMEOUT_NETWORK,
+            |stream, line| {
+                if stream == "stderr" {
+                    emit_progress(&app, "gc", line);
+                }
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    emit_git_change_event(&app)?;
+
+    Ok(GcResult {
+        size_before,
+        size_after: dir_size_blocking(common_dir).await?,
+        warning,
+    })
+}
+
+/// Pull (object count, pack count) out of `count-objects -v`.
+fn parse_count_objects(output: &str) -> (u64, u64) {
+    let mut objects = 0;
+    let mut packs = 0;
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(": ") else {
+            continue;
+        };
+        let value: u64 = value.trim().parse().unwrap_or(0);
+        match key {
+            "count" | "in-pack" => objects += value,
+            "packs" => packs = value,
+            _ => {}
+        }
+    }
+    (objects, packs)
+}
+
+pub async fn cmd_get_repo_size_impl(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<RepoSize, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let args: Vec<String> = vec!["count-objects".into(), "-v".into()];
+    let resp = state
+        .git
+        .run(Path::new(&path), &args, TIMEOUT_LOCAL)
+        .await
+        .map_err(|e| e.to_string())?;
+    let (object_count, pack_count) = parse_count_objects(&resp.stdout);
+
+    // Linked worktrees keep their objects in the common git dir
+    let common_dir = resolve_common_git_dir(Path::new(&path));
+    Ok(RepoSize {
+        git_dir_bytes: dir_size_blocking(common_dir).await?,
+        working_tree_bytes: dir_size_blocking(PathBuf::from(&path)).await?,
+        object_count,
+        pack_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fsck_output() {
+        let stdout = "notice: HEAD points to an unborn branch (main)\n\
+                      dangling commit 1a2b3c\n\
+                      dangling blob 4d5e6f\n\
+                      missing blob 7a8b9c\n";
+        let stderr = "Checking object directories: 100% (256/256), done.\n\
+                      error: 0badc0de: object corrupt or missing\n";
+        let (dangling, errors) = parse_fsck_output(stdout, stderr);
+        assert_eq!(dangling, vec!["commit 1a2b3c", "blob 4d5e6f"]);
+        assert_eq!(
+            errors,
+            vec![
+                "missing blob 7a8b9c",
+                "error: 0badc0de: object corrupt or missing"
+            ]
+        );
+
+        let (dangling, errors) = parse_fsck_output("", "Checking objects: 100% (10/10), done.\n");
+        assert!(dangling.is_empty() && errors.is_empty());
+    }
+
+    #[test]
+    fn test_parse_count_objects() {
+        let output = "count: 12\nsize: 48\nin-pack: 3400\npacks: 2\nsize-pack: 1024\n\
+                      prune-packable: 0\ngarbage: 0\nsize-garbage: 0\n";
+        assert_eq!(parse_count_objects(output), (3412, 2));
+    }
+
+    #[test]
+    fn test_find_lock_files() {
+        let git_dir = std::env::temp_dir().join(format!("git-tools-locks-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(git_dir.join("refs/heads/feature")).unwrap();
+        std::fs::write(git_dir.join("index.lock"), "").unwrap();
+        std::fs::write(git_dir.join("refs/heads/feature/x.lock"), "").unwrap();
+        std::fs::write(git_dir.join("refs/heads/main"), "").unwrap();
+
+        let mut locks = find_lock_files(&git_dir, &git_dir);
+        locks.sort();
+        let ref_lock = Path::new("refs")
+            .join("heads")
+            .join("feature")
+            .join("x.lock");
+        assert_eq!(locks, vec!["index.lock".into(), ref_lock.to_string_lossy()]);
+
+        std::fs::remove_dir_all(&git_dir).unwrap();
+    }
+}
@@ -9,7 +9,7 @@ async fn git_run_rebase(
     state: &State<'_, AppState>,
     repo_path: &str,
     args: &[String],
-    timeout: u64,
+    timeout: Timeout,
 ) -> Result<GitCommandResult, String> {
     match state
         .git
@@ -57,7 +57,7 @@ async fn git_run_rebase_with_env(
     repo_path: &str,
     args: &[String],
     envs: Vec<(String, String)>,
-    timeout: u64,
+    timeout: Timeout,
 ) -> Result<GitCommandResult, String> {
     match state
         .git
@@ -104,8 +104,7 @@ pub async fn cmd_get_rebase_status_impl(
     repo_path: Option<String>,
 ) -> Result<FullRebaseStatus, String> {
     let path = resolve_repo_path(&state, repo_path)?;
-    let p = Path::new(&path);
-    let git_dir = p.join(".git");
+    let git_dir = resolve_git_dir(Path::new(&path));
 
     let rebase_merge = git_dir.join("rebase-merge");
     let rebase_apply = git_dir.join("rebase-apply");
@@ -129,62 +128,79 @@ pub async fn cmd_get_rebase_status_impl(
         RebaseStatus::InProgress
     };
 
-    let mut step_info = None;
+    let step_info = read_rebase_step(&state, &path, &git_dir).await;
     let mut onto_branch = None;
     let mut upstream_branch = None;
 
     if rebase_merge.exists() {
-        let current = read_git_file(&git_dir, "rebase-merge/msg-num")
+        onto_branch = read_git_file(&git_dir, "rebase-merge/onto");
+        upstream_branch = read_git_file(&git_dir, "rebase-merge/head-name")
+            .and_then(|s| s.strip_prefix("refs/heads/").map(|b| b.to_string()));
+    }
+
+    Ok(FullRebaseStatus {
+        status,
+        step: step_info,
+        onto_branch,
+        upstream_branch,
+    })
+}
+
+/// Step N of M for an in-progress rebase, from `rebase-merge` (interactive
+/// and merge backends) or `rebase-apply` (am backend). `None` when neither
+/// directory exists.
+pub async fn read_rebase_step(
+    state: &State<'_, AppState>,
+    repo_path: &str,
+    git_dir: &Path,
+) -> Option<RebaseStepInfo> {
+    if git_dir.join("rebase-merge").exists() {
+        let current = read_git_file(git_dir, "rebase-merge/msg-num")
             .and_then(|s| s.parse::<usize>().ok())
             .unwrap_or(0);
-        let total = read_git_file(&git_dir, "rebase-merge/end")
+        let total = read_git_file(git_dir, "rebase-merge/end")
             .and_then(|s| s.parse::<usize>().ok())
             .unwrap_or(0);
-        let commit_hash = read_git_file(&git_dir, "rebase-merge/stopped-sha")
-            .unwrap_or_default();
-        
+        let commit_hash = read_git_file(git_dir, "rebase-merge/stopped-sha").unwrap_or_default();
+
         let commit_message = if !commit_hash.is_empty() {
-             git_run(&state, Some(path.clone()), &["log", "-1", "--format=%s", &commit_hash], TIMEOUT_QUICK)
-                .await
-                .ok()
-                .map(|r| r.stdout.trim().to_string())
-                .unwrap_or_default()
+            git_run(
+                state,
+                Some(repo_path.to_string()),
+                &["log", "-1", "--format=%s", &commit_hash],
+                TIMEOUT_QUICK,
+            )
+            .await
+            .ok()
+            .map(|r| r.stdout.trim().to_string())
+            .unwrap_or_default()
         } else {
             String::new()
         };
 
-        step_info = Some(RebaseStepInfo {
+        Some(RebaseStepInfo {
             current,
             total,
             commit_hash,
             commit_message,
-        });
-
-        onto_branch = read_git_file(&git_dir, "rebase-merge/onto");
-        upstream_branch = read_git_file(&git_dir, "rebase-merge/head-name")
-            .and_then(|s| s.strip_prefix("refs/heads/").map(|b| b.to_string()));
-    } else if rebase_apply.exists() {
-        let current = read_git_file(&git_dir, "rebase-apply/next")
+        })
+    } else if git_dir.join("rebase-apply").exists() {
+        let current = read_git_file(git_dir, "rebase-apply/next")
             .and_then(|s| s.parse::<usize>().ok())
             .unwrap_or(0);
-        let total = read_git_file(&git_dir, "rebase-apply/last")
+        let total = read_git_file(git_dir, "rebase-apply/last")
             .and_then(|s| s.parse::<usize>().ok())
             .unwrap_or(0);
-        
-        step_info = Some(RebaseStepInfo {
+
+        Some(RebaseStepInfo {
             current,
             total,
             commit_hash: String::new(),
             commit_message: String::new(),
-        });
+        })
+    } else {
+        None
     }
-
-    Ok(FullRebaseStatus {
-        status,
-        step: step_info,
-        onto_branch,
-        upstream_branch,
-    })
 }
 
 async fn cmd_check_conflict_state_internal(
@@ -234,6 +250,43 @@ pub async fn cmd_rebase_start_impl(
     Ok(result)
 }
 
+/// Transplant `upstream..branch` (or `upstream..HEAD`) onto `new_base`.
+pub async fn cmd_rebase_onto_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    new_base: String,
+    upstream: String,
+    branch: Option<String>,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let branch = branch.map(|b| b.trim().to_string()).filter(|b| !b.is_empty());
+
+    // Resolve every ref up front: a typo would otherwise surface as a
+    // confusing rebase failure
+    let mut refs = vec![new_base.trim(), upstream.trim()];
+    if let Some(b) = &branch {
+        refs.push(b.as_str());
+    }
+    for r in refs {
+        if r.is_empty() || r.starts_with('-') {
+            return Err(format!("Invalid ref: '{}'", r));
+        }
+        let rev = format!("{}^{{commit}}", r);
+        git_run(&state, Some(path.clone()), &["rev-parse", "--verify", "-q", &rev], TIMEOUT_QUICK)
+            .await
+            .map_err(|_| format!("'{}' does not resolve to a commit", r))?;
+    }
+
+    let mut args = vec!["rebase".into(), "--onto".into(), new_base.trim().to_string(), upstream.trim().to_string()];
+    if let Some(b) = branch {
+        args.push(b);
+    }
+    let result = git_run_rebase(&state, &path, &args, TIMEOUT_LOCAL).await?;
+    let _ = emit_git_change_event(&app);
+    Ok(result)
+}
+
 pub async fn cmd_rebase_interactive_prepare_impl(
     state: State<'_, AppState>,
     base_commit: String,
@@ -250,6 +303,7 @@ pub async fn cmd_rebase_interactive_prepare_impl(
                 action: "pick".to_string(),
                 hash: parts[0].to_string(),
                 message: parts[1].to_string(),
+                new_message: None,
             });
         }
     }
@@ -257,6 +311,101 @@ pub async fn cmd_rebase_interactive_prepare_impl(
     Ok(items)
 }
 
+/// Write an editor script to `path` and make it executable.
+fn write_editor_script(path: &Path, content: &str) -> Result<(), String> {
+    std::fs::write(path, content).map_err(|e| e.to_string())?;
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Build the `GIT_EDITOR` script used for `reword`/`squash` steps.
+///
+/// Git doesn't tell the editor which commit it is editing, so the script reads
+/// the last line of `rebase-merge/done` (the step being executed), resolves its
+/// hash and copies `<message_dir>/<full hash>` over the message file. Without
+/// a stored message the file is left alone and git's default is used.
+fn message_editor_script(message_dir: &Path) -> String {
+    #[cfg(target_os = "windows")]
+    {
+        let dir = message_dir.to_string_lossy().replace("/", "\\");
+        format!(
+            "@echo off\r\n\
+             setlocal\r\n\
+             for /f \"usebackq delims=\" %%p in (`git rev-parse --git-path rebase-merge/done`) do set DONE=%%p\r\n\
+             set HASH=\r\n\
+             for /f \"usebackq eol=# tokens=2\" %%h in (\"%DONE%\") do set HASH=%%h\r\n\
+             if \"%HASH%\"==\"\" exit /b 0\r\n\
+             for /f \"usebackq delims=\" %%f in (`git rev-parse %HASH%`) do set FULL=%%f\r\n\
+             if exist \"{dir}\\%FULL%\" copy /y \"{dir}\\%FULL%\" %1 >nul\r\n\
+             exit /b 0\r\n",
+            dir = dir
+        )
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        format!(
+            "#!/bin/sh\n\
+             done_file=$(git rev-parse --git-path rebase-merge/done)\n\
+             hash=$(grep -v '^#' \"$done_file\" | tail -n 1 | awk '{{print $2}}')\n\
+             [ -n \"$hash\" ] || exit 0\n\
+             full=$(git rev-parse \"$hash\" 2>/dev/null) || exit 0\n\
+             if [ -f \"{dir}/$full\" ]; then cp \"{dir}/$full\" \"$1\"; fi\n",
+            dir = message_dir.to_string_lossy()
+        )
+    }
+}
+
+const TODO_ACTIONS: &[&str] = &["pick", "reword", "edit", "squash", "fixup", "drop"];
+
+/// Check a todo list before handing it to git, so mistakes are reported up
+/// front instead of as a half-finished rebase. `range` holds the full hashes
+/// of `base..HEAD`; todo hashes may be abbreviated.
+fn validate_rebase_todo(items: &[RebaseTodoItem], range: &[String]) -> Result<(), String> {
+    if items.is_empty() {
+        return Err("The rebase todo list is empty".to_string());
+    }
+
+    for (index, item) in items.iter().enumerate() {
+        if !TODO_ACTIONS.contains(&item.action.as_str()) {
+            return Err(format!(
+                "Unknown rebase action '{}' at line {}",
+                item.action,
+                index + 1
+            ));
+        }
+
+        let hash = item.hash.trim().to_lowercase();
+        let in_range = hash.len() >= 4
+            && hash.chars().all(|c| c.is_ascii_hexdigit())
+            && range.iter().any(|full| full.starts_with(&hash));
+        if !in_range {
+            return Err(format!(
+                "Commit '{}' at line {} is not part of the commits being rebased",
+                item.hash,
+                index + 1
+            ));
+        }
+    }
+
+    // squash/fixup meld into the previous commit, so there must be one
+    if let Some(first) = items.iter().find(|item| item.action != "drop") {
+        if matches!(first.action.as_str(), "squash" | "fixup") {
+            return Err(format!(
+                "Cannot {} '{}': there is no previous commit to combine it with",
+                first.action, first.hash
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 pub async fn cmd_rebase_interactive_apply_impl(
     app: AppHandle,
     state: State<'_, AppState>,
@@ -265,16 +414,43 @@ pub async fn cmd_rebase_interactive_apply_impl(
     repo_path: Option<String>,
 ) -> Result<GitCommandResult, String> {
     let path = resolve_repo_path(&state, repo_path)?;
-    
-    // Create the todo content
+
+    let range_arg = format!("{}..HEAD", base_commit);
+    let range = git_run(&state, Some(path.clone()), &["rev-list", &range_arg], TIMEOUT_LOCAL).await?;
+    let range: Vec<String> = range.stdout.lines().map(|l| l.trim().to_lowercase()).collect();
+    validate_rebase_todo(&todo_items, &range)?;
+
+    let temp_dir = std::env::temp_dir();
+    let mut temp_files = TempFileGuard::default();
+
+    // New messages for reword/squash steps, keyed by the full commit hash so
+    // the editor script can find them whatever abbreviation git uses
+    let mut new_messages: Vec<(String, String)> = Vec::new();
+    for item in &todo_items {
+        let Some(message) = item.new_message.as_ref().filter(|m| !m.trim().is_empty()) else {
+            continue;
+        };
+        if !matches!(item.action.as_str(), "reword" | "squash") {
+            continue;
+        }
+        let rev = format!("{}^{{commit}}", item.hash);
+        let resp = git_run(&state, Some(path.clone()), &["rev-parse", "--verify", &rev], TIMEOUT_QUICK).await?;
+        new_messages.push((resp.stdout.trim().to_string(), message.clone()));
+    }
+    let needs_message_editor = todo_items
+        .iter()
+        .any(|item| matches!(item.action.as_str(), "reword" | "squash"));
+
+    // Create the todo content. Only the first line of the subject goes on
+    // the todo line; git ignores it anyway
     let mut todo_content = String::new();
-    for item in todo_items {
-        todo_content.push_str(&format!("{} {} {}\n", item.action, item.hash, item.message));
+    for item in &todo_items {
+        let subject = item.message.lines().next().unwrap_or("");
+        todo_content.push_str(&format!("{} {} {}\n", item.action, item.hash, subject));
     }
 
     // Write the todo content to a temporary file
-    let temp_dir = std::env::temp_dir();
-    let todo_file = temp_dir.join(format!("git-rebase-todo-{}", uuid::Uuid::new_v4()));
+    let todo_file = temp_files.track(temp_dir.join(format!("git-rebase-todo-{}", uuid::Uuid::new_v4())));
     std::fs::write(&todo_file, todo_content).map_err(|e| e.to_string())?;
 
     // Create a script that replaces the todo file git provides with our one
@@ -286,32 +462,43 @@ pub async fn cmd_rebase_interactive_apply_impl(
     let script_file = temp_dir.join(format!("git-rebase-editor-{}", uuid::Uuid::new_v4()));
     #[cfg(target_os = "windows")]
     let script_file = script_file.with_extension("bat");
-    
-    std::fs::write(&script_file, &script_content).map_err(|e| e.to_string())?;
+    let script_file = temp_files.track(script_file);
 
-    #[cfg(not(target_os = "windows"))]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        std::fs::set_permissions(&script_file, std::fs::Permissions::from_mode(0o755)).map_err(|e| e.to_string())?;
+    write_editor_script(&script_file, &script_content)?;
+
+    let mut envs = vec![("GIT_SEQUENCE_EDITOR".to_string(), script_file.to_string_lossy().to_string())];
+
+    // reword/squash open the commit message editor. Point it at a script that
+    // substitutes the user's message, or at `true` to accept git's default
+    let message_dir = temp_files.track(temp_dir.join(format!("git-rebase-messages-{}", uuid::Uuid::new_v4())));
+    let message_script = temp_dir.join(format!("git-rebase-msg-editor-{}", uuid::Uuid::new_v4()));
+    #[cfg(target_os = "windows")]
+    let message_script = message_script.with_extension("bat");
+    let message_script = temp_files.track(message_script);
+
+    if needs_message_editor && !new_messages.is_empty() {
+        std::fs::create_dir_all(&message_dir).map_err(|e| e.to_string())?;
+        for (hash, message) in &new_messages {
+            std::fs::write(message_dir.join(hash), message).map_err(|e| e.to_string())?;
+        }
+        write_editor_script(&message_script, &message_editor_script(&message_dir))?;
+        envs.push(("GIT_EDITOR".to_string(), message_script.to_string_lossy().to_string()));
+    } else {
+        envs.push(("GIT_EDITOR".to_string(), "true".to_string()));
     }
 
-    let env_name = "GIT_SEQUENCE_EDITOR";
-    let env_value = script_file.to_string_lossy().to_string();
     let args = vec!["rebase".into(), "-i".into(), base_commit];
-    
+
+    // `temp_files` cleans up when this returns. An `edit` stop or a conflict
+    // ends this run early; `cmd_rebase_continue` keeps git's default message
+    // for any reword/squash still pending.
     let result = git_run_rebase_with_env(
         &state,
         &path,
         &args,
-        vec![(env_name.to_string(), env_value)],
+        envs,
         TIMEOUT_LOCAL,
-    ).await;
-        
-    // Cleanup temp files regardless of result
-    let _ = std::fs::remove_file(&todo_file);
-    let _ = std::fs::remove_file(&script_file);
-
-    let result = result?;
+    ).await?;
     let _ = emit_git_change_event(&app);
     Ok(result)
 }
@@ -356,3 +543,239 @@ pub async fn cmd_rebase_skip_impl(
     let _ = emit_git_change_event(&app);
     Ok(result)
 }
+
+// ---------------------------------------------------------------------------
+// Editing the todo of a paused rebase
+// ---------------------------------------------------------------------------
+
+/// Git's one-letter todo command abbreviations.
+const TODO_ABBREVIATIONS: &[(&str, &str)] = &[
+    ("p", "pick"),
+    ("r", "reword"),
+    ("e", "edit"),
+    ("s", "squash"),
+    ("f", "fixup"),
+    ("d", "drop"),
+    ("x", "exec"),
+    ("b", "break"),
+    ("l", "label"),
+    ("t", "reset"),
+    ("m", "merge"),
+    ("u", "update-ref"),
+];
+
+/// Todo commands that don't name a commit; their arguments are kept verbatim
+/// in `message` so they survive a read/write round trip.
+const TODO_OTHER_ACTIONS: &[&str] = &["exec", "break", "label", "reset", "merge", "update-ref", "noop"];
+
+/// Parse the remaining steps of `git-rebase-todo`, skipping comments.
+fn parse_rebase_todo(content: &str) -> Vec<RebaseTodoItem> {
+    let mut items = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let mut action = TODO_ABBREVIATIONS
+            .iter()
+            .find(|(short, _)| *short == command)
+            .map(|(_, full)| full.to_string())
+            .unwrap_or_else(|| command.to_string());
+        let mut rest = rest.trim();
+
+        if !TODO_ACTIONS.contains(&action.as_str()) {
+            items.push(RebaseTodoItem {
+                action,
+                hash: String::new(),
+                message: rest.to_string(),
+                new_message: None,
+            });
+            continue;
+        }
+
+        // `fixup -C <hash>` / `fixup -c <hash>` use the fixup's own message
+        if action == "fixup" {
+            if let Some(flag) = ["-C", "-c"].into_iter().find(|f| rest.starts_with(&format!("{} ", f))) {
+                action = format!("fixup {}", flag);
+                rest = rest[flag.len()..].trim_start();
+            }
+        }
+
+        let (hash, message) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        items.push(RebaseTodoItem {
+            action,
+            hash: hash.to_string(),
+            message: message.trim().to_string(),
+            new_message: None,
+        });
+    }
+    items
+}
+
+/// Render todo items back into git's format, rejecting anything git would
+/// refuse to parse.
+fn format_rebase_todo(items: &[RebaseTodoItem]) -> Result<String, String> {
+    let mut content = String::new();
+    for (index, item) in items.iter().enumerate() {
+        let action = item.action.trim();
+        let (base, flag) = match action.split_once(' ') {
+            Some((base, flag)) => (base, Some(flag)),
+            None => (action, None),
+        };
+
+        let line = if TODO_ACTIONS.contains(&base) {
+            if flag.is_some() && !(base == "fixup" && matches!(flag, Some("-C" | "-c"))) {
+                return Err(format!("Unknown rebase action '{}' at line {}", action, index + 1));
+            }
+            let hash = item.hash.trim();
+            if hash.len() < 4 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(format!("Invalid commit '{}' at line {}", hash, index + 1));
+            }
+            let subject = item.message.lines().next().unwrap_or("").trim();
+            format!("{} {} {}", action, hash, subject)
+        } else if TODO_OTHER_ACTIONS.contains(&action) {
+            let argument = item.message.trim();
+            if argument.contains('\n') {
+                return Err(format!("'{}' at line {} must fit on one line", action, index + 1));
+            }
+            let takes_argument = !matches!(action, "break" | "noop");
+            if takes_argument == argument.is_empty() {
+                return Err(format!(
+                    "'{}' at line {} {}",
+                    action,
+                    index + 1,
+                    if takes_argument { "needs an argument" } else { "takes no argument" }
+                ));
+            }
+            format!("{} {}", action, argument)
+        } else {
+            return Err(format!("Unknown rebase action '{}' at line {}", action, index + 1));
+        };
+
+        content.push_str(line.trim_end());
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+fn rebase_todo_path(repo_path: &str) -> Result<PathBuf, String> {
+    let rebase_merge = resolve_git_dir(Path::new(repo_path)).join("rebase-merge");
+    if !rebase_merge.is_dir() {
+        return Err("No interactive rebase in progress".to_string());
+    }
+    Ok(rebase_merge.join("git-rebase-todo"))
+}
+
+pub async fn cmd_get_rebase_todo_impl(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<Vec<RebaseTodoItem>, String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let todo_path = rebase_todo_path(&path)?;
+    match std::fs::read_to_string(&todo_path) {
+        Ok(content) => Ok(parse_rebase_todo(&content)),
+        // The last step is running: nothing left to edit
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("Failed to read rebase todo: {}", e)),
+    }
+}
+
+pub async fn cmd_set_rebase_todo_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    items: Vec<RebaseTodoItem>,
+    repo_path: Option<String>,
+) -> Result<(), String> {
+    let path = resolve_repo_path(&state, repo_path)?;
+    let todo_path = rebase_todo_path(&path)?;
+    let content = format_rebase_todo(&items)?;
+    std::fs::write(&todo_path, content).map_err(|e| format!("Failed to write rebase todo: {}", e))?;
+    let _ = emit_git_change_event(&app);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(action: &str, hash: &str) -> RebaseTodoItem {
+        RebaseTodoItem {
+            action: action.to_string(),
+            hash: hash.to_string(),
+            message: String::new(),
+            new_message: None,
+        }
+    }
+
+    fn range() -> Vec<String> {
+        vec![
+            "aaaa1111aaaa1111aaaa1111aaaa1111aaaa1111".to_string(),
+            "bbbb2222bbbb2222bbbb2222bbbb2222bbbb2222".to_string(),
+            "cccc3333cccc3333cccc3333cccc3333cccc3333".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_validate_rebase_todo_accepts_valid_sequence() {
+        let items = vec![item("pick", "aaaa111"), item("squash", "bbbb222"), item("reword", "cccc333")];
+        assert!(validate_rebase_todo(&items, &range()).is_ok());
+    }
+
+    #[test]
+    fn test_validate_rebase_todo_rejects_leading_squash_or_fixup() {
+        let items = vec![item("squash", "aaaa111"), item("pick", "bbbb222")];
+        assert!(validate_rebase_todo(&items, &range()).is_err());
+
+        let items = vec![item("drop", "aaaa111"), item("fixup", "bbbb222")];
+        assert!(validate_rebase_todo(&items, &range()).is_err());
+    }
+
+    #[test]
+    fn test_validate_rebase_todo_rejects_hash_outside_range() {
+        let items = vec![item("pick", "aaaa111"), item("pick", "dddd444")];
+        assert!(validate_rebase_todo(&items, &range()).is_err());
+
+        // Too short to identify a commit unambiguously
+        let items = vec![item("pick", "aa")];
+        assert!(validate_rebase_todo(&items, &range()).is_err());
+    }
+
+    #[test]
+    fn test_parse_rebase_todo_expands_abbreviations_and_keeps_other_commands() {
+        let content = "# Rebase abc..def\n\
+                       p aaaa111 First\n\
+                       fixup -C bbbb222 Second\n\
+                       x cargo test\n\
+                       break\n";
+        let items = parse_rebase_todo(content);
+        assert_eq!(items.len(), 4);
+        assert_eq!((items[0].action.as_str(), items[0].hash.as_str()), ("pick", "aaaa111"));
+        assert_eq!(items[0].message, "First");
+        assert_eq!((items[1].action.as_str(), items[1].hash.as_str()), ("fixup -C", "bbbb222"));
+        assert_eq!((items[2].action.as_str(), items[2].message.as_str()), ("exec", "cargo test"));
+        assert_eq!(items[3].action, "break");
+
+        let written = format_rebase_todo(&items).unwrap();
+        assert_eq!(
+            written,
+            "pick aaaa111 First\nfixup -C bbbb222 Second\nexec cargo test\nbreak\n"
+        );
+    }
+
+    #[test]
+    fn test_format_rebase_todo_rejects_malformed_items() {
+        assert!(format_rebase_todo(&[item("pick", "not-a-hash")]).is_err());
+        assert!(format_rebase_todo(&[item("frobnicate", "aaaa111")]).is_err());
+        assert!(format_rebase_todo(&[item("pick -C", "aaaa111")]).is_err());
+        assert!(format_rebase_todo(&[item("exec", "")]).is_err());
+    }
+
+    #[test]
+    fn test_validate_rebase_todo_rejects_unknown_action_and_empty_list() {
+        let items = vec![item("pick", "aaaa111"), item("merge", "bbbb222")];
+        assert!(validate_rebase_todo(&items, &range()).is_err());
+        assert!(validate_rebase_todo(&[], &range()).is_err());
+    }
+}
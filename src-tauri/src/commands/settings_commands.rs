@@ -25,17 +25,76 @@ pub fn cmd_add_repo_impl(
     settings.repos.push(RepoEntry {
         id: id.clone(),
         name,
-        path,
+        path: path.clone(),
     });
 
     if !settings.open_repo_ids.contains(&id) {
-        settings.open_repo_ids.push(id);
+        settings.open_repo_ids.push(id.clone());
     }
 
-    save_settings(&app_handle, &settings)?;
+    save_settings(&app_handle, &mut settings)?;
+    crate::watcher::watch_repo(&app_handle, id, path);
     Ok(settings.clone())
 }
 
+pub async fn cmd_git_init_impl(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+    initial_branch: Option<String>,
+) -> Result<AppSettings, String> {
+    let trimmed_path = path.trim();
+    if trimmed_path.is_empty() {
+        return Err("No repository path provided".to_string());
+    }
+
+    let path_buf = PathBuf::from(trimmed_path);
+    if path_buf.join(".git").exists() {
+        return Err("Path is already a git repository".to_string());
+    }
+    std::fs::create_dir_all(&path_buf)
+        .map_err(|e| format!("Failed to create directory {}: {}", path_buf.display(), e))?;
+
+    // Default to `main` so new repositories don't start on `master`.
+    let branch = initial_branch
+        .as_deref()
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+        .unwrap_or("main")
+        .to_string();
+    if branch.starts_with('-') {
+        return Err("Branch name cannot start with '-'".to_string());
+    }
+
+    // `init -b` needs git 2.28; older versions get HEAD pointed at the branch
+    let steps: Vec<Vec<String>> = if state.git.supports_init_initial_branch().await {
+        vec![vec!["init".into(), "-b".into(), branch]]
+    } else {
+        vec![
+            vec!["init".into()],
+            vec![
+                "symbolic-ref".into(),
+                "HEAD".into(),
+                format!("refs/heads/{}", branch),
+            ],
+        ]
+    };
+    for args in steps {
+        state
+            .git
+            .run(&path_buf, &args, TIMEOUT_LOCAL)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let name = path_buf
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| trimmed_path.to_string());
+
+    cmd_add_repo_impl(app_handle, state, name, trimmed_path.to_string())
+}
+
 pub fn cmd_remove_repo_impl(
     app_handle: AppHandle,
     state: State<AppState>,
@@ -45,6 +104,11 @@ pub fn cmd_remove_repo_impl(
 
     settings.repos.retain(|r| r.id != id);
     settings.open_repo_ids.retain(|r_id| *r_id != id);
+    let _ = state.watchers.stop(&id);
+    settings.repo_excluded_files.remove(&id);
+    if settings.repo_env.remove(&id).is_some() {
+        state.sync_repo_envs(&settings);
+    }
 
     if let Some(active_id) = &settings.active_repo_id {
         if active_id == &id {
@@ -52,7 +116,7 @@ pub fn cmd_remove_repo_impl(
         }
     }
 
-    save_settings(&app_handle, &settings)?;
+    save_settings(&app_handle, &mut settings)?;
     Ok(settings.clone())
 }
 
@@ -70,10 +134,11 @@ pub fn cmd_set_active_repo_impl(
     settings.active_repo_id = Some(id.clone());
 
     if !settings.open_repo_ids.contains(&id) {
-        settings.open_repo_ids.push(id);
+        settings.open_repo_ids.push(id.clone());
+        watch_open_repo(&app_handle, &settings, id);
     }
 
-    save_settings(&app_handle, &settings)?;
+    save_settings(&app_handle, &mut settings)?;
     Ok(settings.clone())
 }
 
@@ -89,13 +154,21 @@ pub fn cmd_open_repo_impl(
     }
 
     if !settings.open_repo_ids.contains(&id) {
-        settings.open_repo_ids.push(id);
-        save_settings(&app_handle, &settings)?;
+        settings.open_repo_ids.push(id.clone());
+        watch_open_repo(&app_handle, &settings, id);
+        save_settings(&app_handle, &mut settings)?;
     }
 
     Ok(settings.clone())
 }
 
+/// Start the watcher of a repository that was just opened.
+fn watch_open_repo(app_handle: &AppHandle, settings: &AppSettings, id: String) {
+    if let Some(repo) = settings.repos.iter().find(|r| r.id == id) {
+        crate::watcher::watch_repo(app_handle, id, repo.path.clone());
+    }
+}
+
 pub fn cmd_close_repo_impl(
     app_handle: AppHandle,
     state: State<AppState>,
@@ -118,11 +191,12 @@ pub fn cmd_close_repo_impl(
         }
 
         let _ = state.terminal.stop_session(&id);
+        let _ = state.watchers.stop(&id);
         if let Some(repo) = settings.repos.iter().find(|r| r.id == id) {
             let _ = state.terminal.stop_session(&repo.path);
         }
 
-        save_settings(&app_handle, &settings)?;
+        save_settings(&app_handle, &mut settings)?;
     }
 
     Ok(settings.clone())
@@ -144,7 +218,29 @@ pub fn cmd_set_excluded_files_impl(
 ) -> Result<AppSettings, String> {
     let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
     settings.excluded_files = exclusions;
-    save_settings(&app_handle, &settings)?;
+    save_settings(&app_handle, &mut settings)?;
+    Ok(settings.clone())
+}
+
+pub fn cmd_set_repo_excluded_files_impl(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    repo_id: String,
+    exclusions: Vec<String>,
+) -> Result<AppSettings, String> {
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+
+    if !settings.repos.iter().any(|r| r.id == repo_id) {
+        return Err("Repository ID not found".to_string());
+    }
+
+    if exclusions.is_empty() {
+        settings.repo_excluded_files.remove(&repo_id);
+    } else {
+        settings.repo_excluded_files.insert(repo_id, exclusions);
+    }
+
+    save_settings(&app_handle, &mut settings)?;
     Ok(settings.clone())
 }
 
@@ -162,7 +258,99 @@ pub fn cmd_set_repo_filter_impl(
         settings.repo_filters.insert(repo_id, filter);
     }
 
-    save_settings(&app_handle, &settings)?;
+    save_settings(&app_handle, &mut settings)?;
+    Ok(settings.clone())
+}
+
+pub fn cmd_set_repo_env_impl(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    repo_id: String,
+    env: HashMap<String, String>,
+) -> Result<AppSettings, String> {
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+
+    if !settings.repos.iter().any(|r| r.id == repo_id) {
+        return Err("Repository ID not found".to_string());
+    }
+
+    let mut vars = HashMap::new();
+    for (key, value) in env {
+        let key = key.trim().to_string();
+        if key.is_empty() {
+            continue;
+        }
+        if key.contains('=') || key.contains('\0') || value.contains('\0') {
+            return Err(format!("Invalid environment variable: {}", key));
+        }
+        if PROTECTED_ENV_VARS
+            .iter()
+            .any(|protected| protected.eq_ignore_ascii_case(&key))
+        {
+            return Err(format!("{} is managed by the app and cannot be set", key));
+        }
+        vars.insert(key, value);
+    }
+
+    if vars.is_empty() {
+        settings.repo_env.remove(&repo_id);
+    } else {
+        settings.repo_env.insert(repo_id, vars);
+    }
+
+    state.sync_repo_envs(&settings);
+    save_settings(&app_handle, &mut settings)?;
+    Ok(settings.clone())
+}
+
+pub fn cmd_set_require_confirmation_impl(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    enabled: bool,
+) -> Result<AppSettings, String> {
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.require_confirmation = enabled;
+    save_settings(&app_handle, &mut settings)?;
+    Ok(settings.clone())
+}
+
+pub fn cmd_set_timeouts_impl(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    timeouts: GitTimeouts,
+) -> Result<AppSettings, String> {
+    timeouts.validate()?;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.timeouts = timeouts;
+    state.sync_timeouts(&settings);
+    save_settings(&app_handle, &mut settings)?;
+    Ok(settings.clone())
+}
+
+pub fn cmd_set_date_format_impl(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    date_format: Option<DateFormat>,
+) -> Result<AppSettings, String> {
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.date_format = date_format;
+    save_settings(&app_handle, &mut settings)?;
+    Ok(settings.clone())
+}
+
+pub fn cmd_set_max_file_view_bytes_impl(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    max_bytes: Option<u64>,
+) -> Result<AppSettings, String> {
+    if max_bytes == Some(0) {
+        return Err("The file size limit must be greater than zero".to_string());
+    }
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    settings.max_file_view_bytes = max_bytes;
+    save_settings(&app_handle, &mut settings)?;
     Ok(settings.clone())
 }
 
@@ -172,13 +360,16 @@ pub fn cmd_set_gemini_api_token_impl(
     token: String,
 ) -> Result<AppSettings, String> {
     let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
-    let trimmed = token.trim().to_string();
-    settings.gemini_api_token = if trimmed.is_empty() {
+    let trimmed = token.trim();
+    let token = if trimmed.is_empty() {
         None
     } else {
         Some(trimmed)
     };
-    save_settings(&app_handle, &settings)?;
+    secrets::set_gemini_token(token)?;
+    settings.gemini_token_set = token.is_some();
+    settings.gemini_api_token = None;
+    save_settings(&app_handle, &mut settings)?;
     Ok(settings.clone())
 }
 
@@ -194,7 +385,7 @@ pub fn cmd_set_gemini_model_impl(
     } else {
         Some(trimmed)
     };
-    save_settings(&app_handle, &settings)?;
+    save_settings(&app_handle, &mut settings)?;
     Ok(settings.clone())
 }
 
@@ -210,7 +401,7 @@ pub fn cmd_set_global_commit_prompt_impl(
     } else {
         Some(trimmed)
     };
-    save_settings(&app_handle, &settings)?;
+    save_settings(&app_handle, &mut settings)?;
     Ok(settings.clone())
 }
 
@@ -227,6 +418,49 @@ pub fn cmd_set_repo_commit_prompt_impl(
     } else {
         settings.repo_commit_prompts.insert(repo_path, trimmed);
     }
-    save_settings(&app_handle, &settings)?;
+    save_settings(&app_handle, &mut settings)?;
     Ok(settings.clone())
 }
+
+/// Outcome of `cmd_import_settings`.
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsImport {
+    pub settings: AppSettings,
+    /// Paths of imported repositories that don't exist on this machine.
+    pub skipped_repos: Vec<String>,
+}
+
+pub fn cmd_export_settings_impl(state: State<AppState>, path: String) -> Result<(), String> {
+    let exported = {
+        let settings = state.settings.lock().map_err(|e| e.to_string())?;
+        settings.exportable()
+    };
+    let content = serde_json::to_string_pretty(&exported).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+pub fn cmd_import_settings_impl(
+    app_handle: AppHandle,
+    state: State<AppState>,
+    path: String,
+) -> Result<SettingsImport, String> {
+    let content =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let imported =
+        parse_settings(&content).map_err(|e| format!("{} is not a settings file: {}", path, e))?;
+
+    let mut settings = state.settings.lock().map_err(|e| e.to_string())?;
+    // Same check as `cmd_add_repo`
+    let skipped_repos = settings.merge_imported(imported, |repo_path| {
+        Path::new(repo_path).join(".git").exists()
+    });
+
+    state.sync_repo_envs(&settings);
+    state.sync_timeouts(&settings);
+    save_settings(&app_handle, &mut settings)?;
+    Ok(SettingsImport {
+        settings: settings.clone(),
+        skipped_repos,
+    })
+}
@@ -0,0 +1,107 @@
+use super::*;
+
+const SPARSE_CHECKOUT_UNSUPPORTED: &str =
+    "Sparse checkout needs git 2.25 or newer. Update git to use this feature.";
+
+async fn ensure_sparse_checkout_supported(state: &State<'_, AppState>) -> Result<(), String> {
+    if state.git.supports_sparse_checkout().await {
+        Ok(())
+    } else {
+        Err(SPARSE_CHECKOUT_UNSUPPORTED.to_string())
+    }
+}
+
+/// Run a `sparse-checkout` subcommand that changes which files are checked
+/// out, then notify the UI.
+async fn run_sparse_checkout(
+    app: &AppHandle,
+    state: &State<'_, AppState>,
+    repo_path: Option<String>,
+    args: Vec<String>,
+) -> Result<GitCommandResult, String> {
+    ensure_sparse_checkout_supported(state).await?;
+    let mut command_args: Vec<String> = vec!["sparse-checkout".into()];
+    command_args.extend(args);
+    git_run_result_with_event(
+        app,
+        state,
+        repo_path,
+        command_args,
+        TIMEOUT_LOCAL,
+        GitCommandType::Other,
+    )
+    .await
+}
+
+pub async fn cmd_sparse_checkout_init_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    cone: bool,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    // Newer git defaults to cone mode, so non-cone has to be explicit
+    let mode = if cone { "--cone" } else { "--no-cone" };
+    let args: Vec<String> = vec!["init".into(), mode.into()];
+    run_sparse_checkout(&app, &state, repo_path, args).await
+}
+
+pub async fn cmd_sparse_checkout_set_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    patterns: Vec<String>,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    let patterns: Vec<String> = patterns
+        .iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if patterns.is_empty() {
+        return Err("No sparse-checkout patterns given".to_string());
+    }
+    if let Some(pattern) = patterns.iter().find(|p| p.starts_with('-')) {
+        return Err(format!("Invalid sparse-checkout pattern: '{}'", pattern));
+    }
+
+    let mut args: Vec<String> = vec!["set".into()];
+    args.extend(patterns);
+    run_sparse_checkout(&app, &state, repo_path, args).await
+}
+
+/// The active sparse-checkout patterns; empty when the worktree isn't sparse.
+pub async fn cmd_sparse_checkout_list_impl(
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<Vec<String>, String> {
+    ensure_sparse_checkout_supported(&state).await?;
+    let path = resolve_repo_path(&state, repo_path)?;
+    let args: Vec<String> = vec!["sparse-checkout".into(), "list".into()];
+    let resp = state
+        .git
+        .run_unchecked(Path::new(&path), &args, TIMEOUT_QUICK)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if resp.exit_code != 0 {
+        // "fatal: this worktree is not sparse"
+        if resp.stderr.contains("not sparse") {
+            return Ok(Vec::new());
+        }
+        return Err(resp.stderr.trim().to_string());
+    }
+    Ok(resp
+        .stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+pub async fn cmd_sparse_checkout_disable_impl(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    repo_path: Option<String>,
+) -> Result<GitCommandResult, String> {
+    run_sparse_checkout(&app, &state, repo_path, vec!["disable".into()]).await
+}
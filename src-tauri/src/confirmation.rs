@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+/// How long a confirmation token stays valid after it was issued.
+const CONFIRMATION_TTL: Duration = Duration::from_secs(60);
+
+/// Destructive operations that can be guarded by a confirmation token.
+pub const DESTRUCTIVE_OPERATIONS: &[&str] = &["discard_changes", "reset_hard", "clean"];
+
+struct PendingConfirmation {
+    operation: String,
+    paths: Vec<String>,
+    expires_at: Instant,
+}
+
+/// Issues short-lived, single-use tokens bound to one exact destructive
+/// operation, so the UI has to ask for confirmation before data is lost.
+pub struct ConfirmationManager {
+    pending: Mutex<HashMap<String, PendingConfirmation>>,
+    ttl: Duration,
+}
+
+/// One destructive operation about to run, with the token confirming it.
+pub struct ConfirmationRequest<'a> {
+    pub token: Option<&'a str>,
+    pub operation: &'a str,
+    pub paths: &'a [String],
+}
+
+impl ConfirmationManager {
+    pub fn new() -> Self {
+        Self::with_ttl(CONFIRMATION_TTL)
+    }
+
+    fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            pending: Mutex::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    /// Issue a token for `operation` on `paths`.
+    pub fn issue(&self, operation: &str, paths: &[String]) -> Result<String, String> {
+        if !DESTRUCTIVE_OPERATIONS.contains(&operation) {
+            return Err(format!("Unknown destructive operation: {}", operation));
+        }
+
+        let mut pending = self.pending.lock().map_err(|e| e.to_string())?;
+        let now = Instant::now();
+        pending.retain(|_, entry| entry.expires_at > now);
+
+        let token = Uuid::new_v4().to_string();
+        pending.insert(
+            token.clone(),
+            PendingConfirmation {
+                operation: operation.to_string(),
+                paths: normalize_paths(paths),
+                expires_at: now + self.ttl,
+            },
+        );
+        Ok(token)
+    }
+
+    /// Check every request, then consume all of their tokens. Nothing is
+    /// consumed unless all of them are valid, so one bad token doesn't cost
+    /// the user the confirmations they already gave.
+    pub fn consume_all(&self, requests: &[ConfirmationRequest<'_>]) -> Result<(), String> {
+        let mut pending = self.pending.lock().map_err(|e| e.to_string())?;
+        let now = Instant::now();
+        let mut tokens = Vec::with_capacity(requests.len());
+        for request in requests {
+            let token = request
+                .token
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .ok_or_else(|| {
+                    format!(
+                        "Confirmation required: '{}' is a destructive operation",
+                        request.operation
+                    )
+                })?;
+            let entry = pending
+                .get(token)
+                .filter(|_| !tokens.contains(&token))
+                .ok_or("Invalid or already used confirmation token")?;
+
+            if entry.expires_at <= now {
+                return Err("Confirmation token has expired".to_string());
+            }
+            if entry.operation != request.operation || entry.paths != normalize_paths(request.paths)
+            {
+                return Err("Confirmation token does not match the requested operation".to_string());
+            }
+            tokens.push(token);
+        }
+
+        for token in tokens {
+            pending.remove(token);
+        }
+        Ok(())
+    }
+}
+
+fn normalize_paths(paths: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = paths
+        .iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(items: &[&str]) -> Vec<String> {
+        items.iter().map(|p| p.to_string()).collect()
+    }
+
+    impl ConfirmationManager {
+        fn consume(
+            &self,
+            token: Option<&str>,
+            operation: &str,
+            paths: &[String],
+        ) -> Result<(), String> {
+            self.consume_all(&[ConfirmationRequest {
+                token,
+                operation,
+                paths,
+            }])
+        }
+    }
+
+    #[test]
+    fn token_is_single_use() {
+        let manager = ConfirmationManager::new();
+        let token = manager
+            .issue("discard_changes", &paths(&["a.txt"]))
+            .unwrap();
+
+        assert!(manager
+            .consume(Some(&token), "discard_changes", &paths(&["a.txt"]))
+            .is_ok());
+        assert!(manager
+            .consume(Some(&token), "discard_changes", &paths(&["a.txt"]))
+            .is_err());
+    }
+
+    #[test]
+    fn token_is_bound_to_operation_and_paths() {
+        let manager = ConfirmationManager::new();
+        let token = manager
+            .issue("discard_changes", &paths(&["a.txt", "b.txt"]))
+            .unwrap();
+
+        assert!(manager
+            .consume(Some(&token), "clean", &paths(&["a.txt", "b.txt"]))
+            .is_err());
+        assert!(manager
+            .consume(Some(&token), "discard_changes", &paths(&["a.txt"]))
+            .is_err());
+        // Order, duplicates and surrounding whitespace don't matter
+        assert!(manager
+            .consume(
+                Some(&token),
+                "discard_changes",
+                &paths(&[" b.txt", "a.txt", "b.txt"])
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn token_expires_after_ttl() {
+        let manager = ConfirmationManager::with_ttl(Duration::ZERO);
+        let token = manager.issue("reset_hard", &paths(&["HEAD~1"])).unwrap();
+        assert_eq!(
+            manager.consume(Some(&token), "reset_hard", &paths(&["HEAD~1"])),
+            Err("Confirmation token has expired".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_token_and_unknown_operation_are_rejected() {
+        let manager = ConfirmationManager::new();
+        assert!(manager.consume(None, "clean", &paths(&["dir"])).is_err());
+        assert!(manager
+            .consume(Some(" "), "clean", &paths(&["dir"]))
+            .is_err());
+        assert!(manager.issue("rm_rf", &paths(&["dir"])).is_err());
+    }
+
+    #[test]
+    fn consume_all_keeps_every_token_when_one_is_invalid() {
+        let manager = ConfirmationManager::new();
+        let discard = manager
+            .issue("discard_changes", &paths(&["a.txt"]))
+            .unwrap();
+        let clean = manager.issue("clean", &paths(&["build"])).unwrap();
+        let discard_paths = paths(&["a.txt"]);
+        let clean_paths = paths(&["build"]);
+        let request = |token, operation, paths| ConfirmationRequest {
+            token,
+            operation,
+            paths,
+        };
+
+        let wrong_clean = paths(&["dist"]);
+        assert!(manager
+            .consume_all(&[
+                request(Some(&discard), "discard_changes", &discard_paths),
+                request(Some(&clean), "clean", &wrong_clean),
+            ])
+            .is_err());
+        assert!(manager
+            .consume_all(&[
+                request(Some(&discard), "discard_changes", &discard_paths),
+                request(Some(&clean), "clean", &clean_paths),
+            ])
+            .is_ok());
+        assert!(manager
+            .consume(Some(&clean), "clean", &clean_paths)
+            .is_err());
+    }
+}
@@ -66,10 +66,23 @@ pub fn encode_string(
     content.as_bytes().to_vec()
 }
 
+/// Decodes git output written in `label` (an `i18n.logOutputEncoding` value).
+/// A UTF-8 or UTF-16 byte-order mark takes precedence over the label; with
+/// neither, or an unknown label, falls back to UTF-8 lossy.
+pub fn decode_output(data: &[u8], label: Option<&str>) -> String {
+    let encoding = Encoding::for_bom(data)
+        .map(|(encoding, _)| encoding)
+        .or_else(|| label.and_then(|l| Encoding::for_label(l.trim().as_bytes())));
+
+    match encoding {
+        Some(encoding) => encoding.decode(data).0.into_owned(),
+        None => String::from_utf8_lossy(data).to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
 
     #[test]
     fn test_resolve_encoding() {
@@ -105,12 +118,29 @@ mod tests {
         // Windows-1252 encoded "café" (E9 is é)
         let data = vec![0x63, 0x61, 0x66, 0xE9];
 
-        let decoded = decode_bytes(&data, Path::new("test.txt"), &settings);
+        let decoded = decode_bytes(&data, Path::new("test.txt"), &settings, None);
         assert_eq!(decoded, "café");
 
         // UTF-8 (default)
         let data_utf8 = "café".as_bytes();
-        let decoded_utf8 = decode_bytes(data_utf8, Path::new("other.rs"), &settings);
+        let decoded_utf8 = decode_bytes(data_utf8, Path::new("other.rs"), &settings, None);
         assert_eq!(decoded_utf8, "café");
     }
+
+    #[test]
+    fn test_decode_output() {
+        // Shift_JIS "テスト"
+        let sjis = [0x83, 0x65, 0x83, 0x58, 0x83, 0x67];
+        assert_eq!(decode_output(&sjis, Some("Shift_JIS")), "テスト");
+
+        // UTF-16LE with BOM, no label configured
+        let utf16 = [0xFF, 0xFE, 0x6C, 0x00, 0x6F, 0x00, 0x67, 0x00];
+        assert_eq!(decode_output(&utf16, None), "log");
+
+        assert_eq!(decode_output("café".as_bytes(), None), "café");
+        assert_eq!(
+            decode_output("café".as_bytes(), Some("no-such-encoding")),
+            "café"
+        );
+    }
 }
@@ -0,0 +1,209 @@
+use crate::models::{GraphCommit, GraphEdge, GraphRow};
+
+/// An active lane after a row: the commit it is waiting for and the lane in
+/// that row its line starts from.
+struct LaneSource {
+    slot: usize,
+    from: usize,
+    hash: String,
+}
+
+/// Assign every commit a lane and compute the edges between consecutive rows.
+///
+/// `commits` must list children before their parents (`git log --date-order`
+/// or `--topo-order`). Each lane holds the hash of the commit it leads to: a
+/// commit takes the leftmost lane waiting for it (or the first free one),
+/// its first parent continues in that lane and any other parent joins the
+/// lane already waiting for it or opens a new one. Lanes are not compacted,
+/// so a line keeps its column until it reaches its commit.
+pub fn layout_graph(commits: Vec<GraphCommit>) -> Vec<GraphRow> {
+    let mut lanes: Vec<Option<String>> = Vec::new();
+    let mut placed: Vec<(usize, Vec<LaneSource>)> = Vec::with_capacity(commits.len());
+
+    for commit in &commits {
+        let waiting = |hash: &str, lanes: &[Option<String>]| {
+            lanes.iter().position(|h| h.as_deref() == Some(hash))
+        };
+        let lane = waiting(&commit.hash, &lanes)
+            .or_else(|| lanes.iter().position(Option::is_none))
+            .unwrap_or_else(|| {
+                lanes.push(None);
+                lanes.len() - 1
+            });
+
+        // Every line that was heading for this commit ends here
+        for slot in lanes.iter_mut() {
+            if slot.as_deref() == Some(commit.hash.as_str()) {
+                *slot = None;
+            }
+        }
+
+        let mut sources: Vec<LaneSource> = lanes
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, hash)| {
+                hash.clone().map(|hash| LaneSource {
+                    slot,
+                    from: slot,
+                    hash,
+                })
+            })
+            .collect();
+
+        for (i, parent) in commit.parents.iter().enumerate() {
+            let slot = if i == 0 {
+                lane
+            } else if let Some(slot) = waiting(parent, &lanes) {
+                slot
+            } else {
+                lanes.iter().position(Option::is_none).unwrap_or_else(|| {
+                    lanes.push(None);
+                    lanes.len() - 1
+                })
+            };
+            lanes[slot] = Some(parent.clone());
+            sources.push(LaneSource {
+                slot,
+                from: lane,
+                hash: parent.clone(),
+            });
+        }
+
+        while lanes.last().is_some_and(Option::is_none) {
+            lanes.pop();
+        }
+        placed.push((lane, sources));
+    }
+
+    let next_rows: Vec<Option<(usize, String)>> = placed
+        .iter()
+        .zip(&commits)
+        .skip(1)
+        .map(|((lane, _), commit)| Some((*lane, commit.hash.clone())))
+        .chain(std::iter::once(None))
+        .collect();
+
+    commits
+        .into_iter()
+        .zip(placed)
+        .zip(next_rows)
+        .map(|((commit, (lane, sources)), next)| {
+            let mut edges: Vec<GraphEdge> = Vec::new();
+            for source in sources {
+                let to = match &next {
+                    Some((next_lane, next_hash)) if *next_hash == source.hash => *next_lane,
+                    _ => source.slot,
+                };
+                let edge = GraphEdge {
+                    from_lane: source.from as u32,
+                    to_lane: to as u32,
+                };
+                if !edges.contains(&edge) {
+                    edges.push(edge);
+                }
+            }
+            GraphRow {
+                commit,
+                lane: lane as u32,
+                edges,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(hash: &str, parents: &[&str]) -> GraphCommit {
+        GraphCommit {
+            hash: hash.to_string(),
+            parents: parents.iter().map(|p| p.to_string()).collect(),
+            refs: Vec::new(),
+            author: String::new(),
+            date: String::new(),
+            subject: String::new(),
+        }
+    }
+
+    fn edge(from_lane: u32, to_lane: u32) -> GraphEdge {
+        GraphEdge { from_lane, to_lane }
+    }
+
+    fn summary(rows: &[GraphRow]) -> Vec<(u32, Vec<GraphEdge>)> {
+        rows.iter().map(|r| (r.lane, r.edges.clone())).collect()
+    }
+
+    #[test]
+    fn linear_history_stays_in_one_lane() {
+        let rows = layout_graph(vec![
+            commit("c", &["b"]),
+            commit("b", &["a"]),
+            commit("a", &[]),
+        ]);
+        assert_eq!(
+            summary(&rows),
+            vec![(0, vec![edge(0, 0)]), (0, vec![edge(0, 0)]), (0, vec![])]
+        );
+    }
+
+    #[test]
+    fn merge_opens_and_closes_a_lane() {
+        // m merges c into b; both branch off a
+        let rows = layout_graph(vec![
+            commit("m", &["b", "c"]),
+            commit("b", &["a"]),
+            commit("c", &["a"]),
+            commit("a", &[]),
+        ]);
+        assert_eq!(
+            summary(&rows),
+            vec![
+                (0, vec![edge(0, 0), edge(0, 1)]),
+                (0, vec![edge(1, 1), edge(0, 0)]),
+                (1, vec![edge(0, 0), edge(1, 0)]),
+                (0, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn separate_tips_converge_on_common_parent() {
+        let rows = layout_graph(vec![
+            commit("x", &["a"]),
+            commit("y", &["a"]),
+            commit("a", &[]),
+        ]);
+        assert_eq!(
+            summary(&rows),
+            vec![
+                (0, vec![edge(0, 0)]),
+                (1, vec![edge(0, 0), edge(1, 0)]),
+                (0, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn octopus_merge_and_lane_reuse() {
+        let rows = layout_graph(vec![
+            commit("o", &["a", "b", "c"]),
+            commit("a", &[]),
+            commit("b", &[]),
+            commit("t", &["c"]),
+            commit("c", &[]),
+        ]);
+        assert_eq!(
+            summary(&rows),
+            vec![
+                (0, vec![edge(0, 0), edge(0, 1), edge(0, 2)]),
+                (0, vec![edge(1, 1), edge(2, 2)]),
+                (1, vec![edge(2, 2)]),
+                // The new tip reuses the lane freed by a, and c is drawn in
+                // the leftmost of the two lanes waiting for it
+                (0, vec![edge(2, 0), edge(0, 0)]),
+                (0, vec![]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use crate::git::types::LfsPointerInfo;
+
+/// Every Git LFS pointer file starts with this line.
+const POINTER_PREFIX: &[u8] = b"version https://git-lfs.github.com/spec/";
+
+/// Pointer files are a few lines of text; anything bigger is real content.
+const MAX_POINTER_SIZE: usize = 1024;
+
+/// Parses `data` as a Git LFS pointer file, returning `None` for regular content.
+pub fn parse_pointer(data: &[u8]) -> Option<LfsPointerInfo> {
+    if data.len() > MAX_POINTER_SIZE || !data.starts_with(POINTER_PREFIX) {
+        return None;
+    }
+
+    let text = std::str::from_utf8(data).ok()?;
+    let mut oid = None;
+    let mut size = None;
+    for line in text.lines() {
+        if let Some(value) = line.strip_prefix("oid ") {
+            oid = Some(value.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("size ") {
+            size = value.trim().parse::<u64>().ok();
+        }
+    }
+
+    Some(LfsPointerInfo {
+        is_lfs_pointer: true,
+        oid,
+        size,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OID: &str = "sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393";
+
+    #[test]
+    fn parse_pointer_reads_oid_and_size() {
+        let lf = format!("version https://git-lfs.github.com/spec/v1\noid {OID}\nsize 12345\n");
+        let crlf = lf.replace('\n', "\r\n");
+
+        for data in [lf, crlf] {
+            let info = parse_pointer(data.as_bytes()).expect("pointer");
+            assert!(info.is_lfs_pointer);
+            assert_eq!(info.oid.as_deref(), Some(OID));
+            assert_eq!(info.size, Some(12345));
+        }
+    }
+
+    #[test]
+    fn parse_pointer_rejects_regular_content() {
+        let oversized = format!(
+            "version https://git-lfs.github.com/spec/v1\noid {OID}\nsize 1\n{}",
+            "x".repeat(MAX_POINTER_SIZE)
+        );
+        for data in [
+            b"fn main() {}\n".as_slice(),
+            b"".as_slice(),
+            b"\xff\xfe binary".as_slice(),
+            oversized.as_bytes(),
+        ] {
+            assert!(parse_pointer(data).is_none());
+        }
+    }
+
+    #[test]
+    fn parse_pointer_leaves_missing_fields_empty() {
+        let data = b"version https://git-lfs.github.com/spec/v1\nsize abc\n";
+        let info = parse_pointer(data).expect("pointer");
+        assert!(info.is_lfs_pointer);
+        assert_eq!(info.oid, None);
+        assert_eq!(info.size, None);
+    }
+}
@@ -1,4 +1,8 @@
 pub mod encoding;
+pub mod graph;
+pub mod lfs;
+pub mod oplog;
+pub mod redact;
 pub mod service;
 pub mod types;
 
@@ -0,0 +1,112 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::git::redact::redact_arg;
+
+/// How many git invocations the operation log keeps before dropping the oldest.
+pub const OPERATION_LOG_CAPACITY: usize = 500;
+
+/// One git invocation, as recorded by `GitExecutor`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OperationLogEntry {
+    /// Start time in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    pub args: Vec<String>,
+    pub cwd: String,
+    /// `None` when the process timed out or could not be read.
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+}
+
+/// Bounded in-memory history of git commands, newest last.
+pub struct OperationLog {
+    entries: Mutex<VecDeque<OperationLogEntry>>,
+    capacity: usize,
+}
+
+impl OperationLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    /// Append an entry, redacting secrets from its arguments first.
+    pub fn record(&self, mut entry: OperationLogEntry) {
+        entry.args = entry.args.iter().map(|arg| redact_arg(arg)).collect();
+        if let Ok(mut entries) = self.entries.lock() {
+            if entries.len() == self.capacity {
+                entries.pop_front();
+            }
+            entries.push_back(entry);
+        }
+    }
+
+    /// The most recent `limit` entries (all of them when `None`), newest first.
+    pub fn recent(&self, limit: Option<usize>) -> Vec<OperationLogEntry> {
+        let Ok(entries) = self.entries.lock() else {
+            return Vec::new();
+        };
+        entries
+            .iter()
+            .rev()
+            .take(limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(args: &[&str]) -> OperationLogEntry {
+        OperationLogEntry {
+            timestamp: 0,
+            args: args.iter().map(|a| a.to_string()).collect(),
+            cwd: "/repo".to_string(),
+            exit_code: Some(0),
+            duration_ms: 1,
+        }
+    }
+
+    #[test]
+    fn keeps_only_the_newest_entries() {
+        let log = OperationLog::new(2);
+        log.record(entry(&["status"]));
+        log.record(entry(&["fetch"]));
+        log.record(entry(&["pull"]));
+
+        let args: Vec<String> = log
+            .recent(None)
+            .into_iter()
+            .map(|e| e.args[0].clone())
+            .collect();
+        assert_eq!(args, vec!["pull", "fetch"]);
+        assert_eq!(log.recent(Some(1)).len(), 1);
+    }
+
+    #[test]
+    fn redacts_secrets_in_args() {
+        let log = OperationLog::new(2);
+        log.record(entry(&[
+            "-c",
+            "http.extraHeader=Authorization: Bearer abc",
+            "fetch",
+            "https://user:pw@github.com/o/r.git",
+        ]));
+
+        assert_eq!(
+            log.recent(None)[0].args,
+            vec![
+                "-c",
+                "http.extraHeader=***",
+                "fetch",
+                "https://***@github.com/o/r.git"
+            ]
+        );
+    }
+}
@@ -1,15 +1,100 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
-use std::time::{Duration, Instant};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
 use tokio::process::Command;
 
-use crate::git::types::{DiagnosticInfo, GitError, GitResponse, GitResponseBytes, GitResult};
+use crate::git::oplog::{OperationLog, OperationLogEntry};
+use crate::git::redact::redact_secrets;
+use crate::git::types::{
+    DiagnosticInfo, GitError, GitResponse, GitResponseBytes, GitResult, GitVersion,
+};
+
+/// How long a git command may run. `GitExecutor` turns a tier into the
+/// user's configured seconds (see `GitTimeouts`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timeout {
+    Local,
+    Network,
+    Quick,
+}
 
 /// Timeout tiers for different command categories.
-pub const TIMEOUT_LOCAL: u64 = 30;
-pub const TIMEOUT_NETWORK: u64 = 120;
-pub const TIMEOUT_QUICK: u64 = 15;
+pub const TIMEOUT_LOCAL: Timeout = Timeout::Local;
+pub const TIMEOUT_NETWORK: Timeout = Timeout::Network;
+pub const TIMEOUT_QUICK: Timeout = Timeout::Quick;
+
+/// Default seconds for each tier.
+const DEFAULT_LOCAL_SECS: u64 = 30;
+const DEFAULT_NETWORK_SECS: u64 = 120;
+const DEFAULT_QUICK_SECS: u64 = 15;
+
+/// Upper bound for a configured timeout; anything longer is indistinguishable
+/// from a hang to the user.
+const MAX_TIMEOUT_SECS: u64 = 3600;
+
+/// User-configured seconds for each timeout tier, persisted in settings.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct GitTimeouts {
+    pub local: u64,
+    pub network: u64,
+    pub quick: u64,
+}
+
+impl Default for GitTimeouts {
+    fn default() -> Self {
+        Self {
+            local: DEFAULT_LOCAL_SECS,
+            network: DEFAULT_NETWORK_SECS,
+            quick: DEFAULT_QUICK_SECS,
+        }
+    }
+}
+
+impl GitTimeouts {
+    /// Every tier must be between 1 second and `MAX_TIMEOUT_SECS`.
+    pub fn validate(&self) -> Result<(), String> {
+        for (category, secs) in [
+            ("local", self.local),
+            ("network", self.network),
+            ("quick", self.quick),
+        ] {
+            if secs == 0 || secs > MAX_TIMEOUT_SECS {
+                return Err(format!(
+                    "Invalid {} timeout: {}s (must be between 1 and {})",
+                    category, secs, MAX_TIMEOUT_SECS
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Variables the executor sets on every command to keep git non-interactive
+/// and its output parseable; per-repo environments may not override them.
+pub const PROTECTED_ENV_VARS: &[&str] = &[
+    "GIT_TERMINAL_PROMPT",
+    "GCM_INTERACTIVE",
+    "LC_ALL",
+    "GIT_OPTIONAL_LOCKS",
+    "GIT_PAGER",
+];
+
+/// First git release with `git switch` and `git restore`.
+const SWITCH_RESTORE_MIN_VERSION: GitVersion = GitVersion::new(2, 23, 0);
+
+/// First git release with the `git sparse-checkout` command.
+const SPARSE_CHECKOUT_MIN_VERSION: GitVersion = GitVersion::new(2, 25, 0);
+
+/// First git release with `git init --initial-branch`.
+const INIT_INITIAL_BRANCH_MIN_VERSION: GitVersion = GitVersion::new(2, 28, 0);
+
+/// First git release with `git merge-tree --write-tree`.
+const MERGE_TREE_WRITE_TREE_MIN_VERSION: GitVersion = GitVersion::new(2, 38, 0);
 
 /// Unified async git executor.
 ///
@@ -18,12 +103,91 @@ pub const TIMEOUT_QUICK: u64 = 15;
 /// protection and never blocks the Tauri IPC thread.
 pub struct GitExecutor {
     git_binary: PathBuf,
+    /// Extra environment variables injected into every command run inside a
+    /// given repository (e.g. `GIT_SSH_COMMAND`, `HTTP_PROXY`).
+    repo_envs: RwLock<HashMap<PathBuf, Vec<(String, String)>>>,
+    /// Cached result of `git --version`, filled on first use.
+    version: RwLock<Option<GitVersion>>,
+    /// Configured timeout per tier.
+    timeouts: RwLock<GitTimeouts>,
+    /// Every repository command run is recorded here for the debug panel.
+    operation_log: Arc<OperationLog>,
 }
 
 impl GitExecutor {
     /// Create a new executor with a pre-resolved git binary path.
-    pub fn new(git_binary: PathBuf) -> Self {
-        Self { git_binary }
+    pub fn new(git_binary: PathBuf, operation_log: Arc<OperationLog>) -> Self {
+        Self {
+            git_binary,
+            repo_envs: RwLock::new(HashMap::new()),
+            version: RwLock::new(None),
+            timeouts: RwLock::new(GitTimeouts::default()),
+            operation_log,
+        }
+    }
+
+    /// Add a finished (or timed out, `exit_code == None`) command to the
+    /// operation log.
+    fn record_operation(
+        &self,
+        repo_path: &Path,
+        args: &[String],
+        exit_code: Option<i32>,
+        start: Instant,
+    ) {
+        let duration = start.elapsed();
+        let timestamp = SystemTime::now()
+            .checked_sub(duration)
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        self.operation_log.record(OperationLogEntry {
+            timestamp,
+            args: args.to_vec(),
+            cwd: repo_path.display().to_string(),
+            exit_code,
+            duration_ms: duration.as_millis() as u64,
+        });
+    }
+
+    /// Replace the configured timeouts.
+    pub fn set_timeouts(&self, timeouts: GitTimeouts) {
+        if let Ok(mut guard) = self.timeouts.write() {
+            *guard = timeouts;
+        }
+    }
+
+    /// Seconds configured for `timeout`'s tier.
+    fn effective_timeout(&self, timeout: Timeout) -> u64 {
+        let timeouts = self.timeouts.read().map(|g| *g).unwrap_or_default();
+        match timeout {
+            Timeout::Local => timeouts.local,
+            Timeout::Network => timeouts.network,
+            Timeout::Quick => timeouts.quick,
+        }
+    }
+
+    /// Replace the per-repository environment table.
+    pub fn set_repo_envs(&self, envs: HashMap<PathBuf, Vec<(String, String)>>) {
+        let envs = envs
+            .into_iter()
+            .map(|(path, vars)| (repo_env_key(&path), vars))
+            .collect();
+        if let Ok(mut guard) = self.repo_envs.write() {
+            *guard = envs;
+        }
+    }
+
+    /// Environment variables configured for the repository at `repo_path`.
+    /// They are applied before the executor's own variables, so a repo entry
+    /// can never override [`PROTECTED_ENV_VARS`].
+    fn repo_envs_for(&self, repo_path: &Path) -> Vec<(String, String)> {
+        let key = repo_env_key(repo_path);
+        self.repo_envs
+            .read()
+            .ok()
+            .and_then(|guard| guard.get(&key).cloned())
+            .unwrap_or_default()
     }
 
     // ------------------------------------------------------------------
@@ -131,15 +295,101 @@ impl GitExecutor {
         &self,
         repo_path: &Path,
         args: &[String],
-        timeout_secs: u64,
+        timeout: Timeout,
+    ) -> GitResult<GitResponse> {
+        self.run_capped(repo_path, args, timeout, None).await
+    }
+
+    /// Like `run`, but stops reading after `max_output_bytes` of stdout (cut
+    /// back to the last complete line) and kills git, setting
+    /// `GitResponse::truncated`. For commands with unbounded output such as
+    /// `log`; `None` reads everything.
+    pub async fn run_capped(
+        &self,
+        repo_path: &Path,
+        args: &[String],
+        timeout: Timeout,
+        max_output_bytes: Option<usize>,
+    ) -> GitResult<GitResponse> {
+        let resp = self
+            .run_unchecked_capped(repo_path, args, timeout, max_output_bytes)
+            .await?;
+        // A killed process exits non-zero, but the output up to the cap is fine
+        if resp.exit_code == 0 || resp.truncated {
+            return Ok(resp);
+        }
+        let GitResponse {
+            stdout,
+            stderr,
+            exit_code,
+            ..
+        } = resp;
+        let args_display = redact_secrets(&args.join(" "));
+
+        // Parse well-known error patterns
+        if stderr.contains("not a git repository") {
+            return Err(GitError::NotARepo(repo_path.display().to_string()));
+        }
+        if stderr.contains("CONFLICT") || stdout.contains("CONFLICT") {
+            return Err(GitError::MergeConflict);
+        }
+
+        Err(GitError::CommandError(format!(
+            "git {} failed (exit {}): {}",
+            args_display, exit_code, stderr
+        )))
+    }
+
+    /// Like `run`, but a non-zero exit is returned as a normal response rather
+    /// than an error, for commands whose exit code carries a result (e.g.
+    /// `merge-tree` exits 1 on conflicts). Timeouts and spawn failures are
+    /// still errors.
+    pub async fn run_unchecked(
+        &self,
+        repo_path: &Path,
+        args: &[String],
+        timeout: Timeout,
+    ) -> GitResult<GitResponse> {
+        self.run_unchecked_capped(repo_path, args, timeout, None)
+            .await
+    }
+
+    async fn run_unchecked_capped(
+        &self,
+        repo_path: &Path,
+        args: &[String],
+        timeout: Timeout,
+        max_output_bytes: Option<usize>,
     ) -> GitResult<GitResponse> {
+        let output = self
+            .run_raw(repo_path, args, timeout, max_output_bytes)
+            .await?;
+        Ok(GitResponse {
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: output.stderr,
+            exit_code: output.exit_code,
+            duration_ms: output.duration_ms,
+            truncated: output.truncated,
+        })
+    }
+
+    /// Spawn git and collect its output, shared by `run` and
+    /// `run_with_output_bytes`. Only timeouts and spawn/IO failures are errors.
+    async fn run_raw(
+        &self,
+        repo_path: &Path,
+        args: &[String],
+        timeout: Timeout,
+        max_output_bytes: Option<usize>,
+    ) -> GitResult<GitResponseBytes> {
+        let timeout_secs = self.effective_timeout(timeout);
         // Validate repo path
         if !repo_path.exists() || !repo_path.is_dir() {
             return Err(GitError::InvalidRepoPath(repo_path.display().to_string()));
         }
 
         let start = Instant::now();
-        let args_display = args.join(" ");
+        let args_display = redact_secrets(&args.join(" "));
         println!(
             "[GIT START] git {} | cwd: {} | timeout: {}s",
             args_display,
@@ -150,6 +400,7 @@ impl GitExecutor {
         let mut cmd = Command::new(&self.git_binary);
         cmd.current_dir(repo_path)
             .args(args)
+            .envs(self.repo_envs_for(repo_path))
             .env("GIT_TERMINAL_PROMPT", "0")
             .env("GCM_INTERACTIVE", "never")
             .env("LC_ALL", "C")
@@ -157,7 +408,8 @@ impl GitExecutor {
             .env("GIT_PAGER", "")
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
 
         // Prevent console window flash on Windows
         #[cfg(target_os = "windows")]
@@ -167,16 +419,30 @@ impl GitExecutor {
         }
 
         // Spawn the child process
-        let child = cmd
+        let mut child = cmd
             .spawn()
             .map_err(|e| GitError::IoError(format!("Failed to spawn git: {}", e)))?;
+        let stdout_pipe = child.stdout.take();
+        let stderr_pipe = child.stderr.take();
+
+        // Await with timeout. Once stdout hits the cap its pipe is closed, so
+        // git fails its next write and stderr reaches EOF too.
+        let work = async {
+            let (stdout, stderr) = tokio::join!(
+                read_pipe_capped(stdout_pipe, max_output_bytes),
+                read_pipe_capped(stderr_pipe, None)
+            );
+            let (stdout, truncated) = stdout?;
+            if truncated {
+                let _ = child.start_kill();
+            }
+            let status = child.wait().await?;
+            Ok::<_, std::io::Error>((stdout, stderr?.0, status, truncated))
+        };
 
-        // Await with timeout
-        let output =
-            match tokio::time::timeout(Duration::from_secs(timeout_secs), child.wait_with_output())
-                .await
-            {
-                Ok(Ok(output)) => output,
+        let (stdout, stderr, status, truncated) =
+            match tokio::time::timeout(Duration::from_secs(timeout_secs), work).await {
+                Ok(Ok(result)) => result,
                 Ok(Err(e)) => {
                     return Err(GitError::IoError(format!("git process IO error: {}", e)));
                 }
@@ -186,45 +452,33 @@ impl GitExecutor {
                         "[GIT TIMEOUT] git {} (after {}s)",
                         args_display, timeout_secs
                     );
+                    self.record_operation(repo_path, args, None, start);
                     return Err(GitError::Timeout(timeout_secs));
                 }
             };
 
         let duration = start.elapsed();
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        let exit_code = output.status.code().unwrap_or(-1);
+        let stderr = String::from_utf8_lossy(&stderr).to_string();
+        let exit_code = status.code().unwrap_or(-1);
+        self.record_operation(repo_path, args, Some(exit_code), start);
 
         println!(
-            "[GIT END] exit={} | {}ms | stdout={}b stderr={}b | git {}",
+            "[GIT END] exit={} | {}ms | stdout={}b{} stderr={}b | git {}",
             exit_code,
             duration.as_millis(),
             stdout.len(),
+            if truncated { " (truncated)" } else { "" },
             stderr.len(),
             args_display,
         );
 
-        if output.status.success() {
-            return Ok(GitResponse {
-                stdout,
-                stderr,
-                exit_code,
-                duration_ms: duration.as_millis() as u64,
-            });
-        }
-
-        // Parse well-known error patterns
-        if stderr.contains("not a git repository") {
-            return Err(GitError::NotARepo(repo_path.display().to_string()));
-        }
-        if stderr.contains("CONFLICT") || stdout.contains("CONFLICT") {
-            return Err(GitError::MergeConflict);
-        }
-
-        Err(GitError::CommandError(format!(
-            "git {} failed (exit {}): {}",
-            args_display, exit_code, stderr
-        )))
+        Ok(GitResponseBytes {
+            stdout,
+            stderr,
+            exit_code,
+            duration_ms: duration.as_millis() as u64,
+            truncated,
+        })
     }
 
     /// Run a git command asynchronously with environment variables and timeout protection.
@@ -233,25 +487,28 @@ impl GitExecutor {
         repo_path: &Path,
         args: &[String],
         envs: Vec<(String, String)>,
-        timeout_secs: u64,
+        timeout: Timeout,
     ) -> GitResult<GitResponse> {
+        let timeout_secs = self.effective_timeout(timeout);
         if !repo_path.exists() || !repo_path.is_dir() {
             return Err(GitError::InvalidRepoPath(repo_path.display().to_string()));
         }
 
         let start = Instant::now();
-        let args_display = args.join(" ");
+        let args_display = redact_secrets(&args.join(" "));
         println!(
             "[GIT START] git {} | cwd: {} | envs: {:?} | timeout: {}s",
             args_display,
             repo_path.display(),
-            envs,
+            // Values may hold credentials; the names are enough to debug with
+            envs.iter().map(|(key, _)| key).collect::<Vec<_>>(),
             timeout_secs
         );
 
         let mut cmd = Command::new(&self.git_binary);
         cmd.current_dir(repo_path)
             .args(args)
+            .envs(self.repo_envs_for(repo_path))
             .env("GIT_TERMINAL_PROMPT", "0")
             .env("GCM_INTERACTIVE", "never")
             .env("LC_ALL", "C")
@@ -281,6 +538,7 @@ impl GitExecutor {
                     return Err(GitError::IoError(format!("git process IO error: {}", e)));
                 }
                 Err(_) => {
+                    self.record_operation(repo_path, args, None, start);
                     return Err(GitError::Timeout(timeout_secs));
                 }
             };
@@ -289,6 +547,7 @@ impl GitExecutor {
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
         let exit_code = output.status.code().unwrap_or(-1);
+        self.record_operation(repo_path, args, Some(exit_code), start);
 
         if output.status.success() {
             return Ok(GitResponse {
@@ -296,6 +555,7 @@ impl GitExecutor {
                 stderr,
                 exit_code,
                 duration_ms: duration.as_millis() as u64,
+                truncated: false,
             });
         }
 
@@ -305,22 +565,31 @@ impl GitExecutor {
         )))
     }
 
-    /// Run a git command and return stdout as raw bytes.
-    pub async fn run_with_output_bytes(
+    /// Run a git command with extra environment variables, reporting every
+    /// line of stdout/stderr to `on_line` as soon as it is produced.
+    ///
+    /// `on_line` receives `"stdout"` or `"stderr"` and the line without its
+    /// terminator. The full output is still collected into the response.
+    pub async fn run_streaming<F>(
         &self,
         repo_path: &Path,
         args: &[String],
-        timeout_secs: u64,
-    ) -> GitResult<GitResponseBytes> {
-        // Validate repo path
+        envs: Vec<(String, String)>,
+        timeout: Timeout,
+        on_line: F,
+    ) -> GitResult<GitResponse>
+    where
+        F: Fn(&'static str, &str) + Send + Sync,
+    {
+        let timeout_secs = self.effective_timeout(timeout);
         if !repo_path.exists() || !repo_path.is_dir() {
             return Err(GitError::InvalidRepoPath(repo_path.display().to_string()));
         }
 
         let start = Instant::now();
-        let args_display = args.join(" ");
+        let args_display = redact_secrets(&args.join(" "));
         println!(
-            "[GIT START (BYTES)] git {} | cwd: {} | timeout: {}s",
+            "[GIT START] git {} | cwd: {} | streaming | timeout: {}s",
             args_display,
             repo_path.display(),
             timeout_secs
@@ -329,14 +598,17 @@ impl GitExecutor {
         let mut cmd = Command::new(&self.git_binary);
         cmd.current_dir(repo_path)
             .args(args)
+            .envs(self.repo_envs_for(repo_path))
             .env("GIT_TERMINAL_PROMPT", "0")
             .env("GCM_INTERACTIVE", "never")
             .env("LC_ALL", "C")
             .env("GIT_OPTIONAL_LOCKS", "0")
             .env("GIT_PAGER", "")
+            .envs(envs)
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
 
         #[cfg(target_os = "windows")]
         {
@@ -344,15 +616,25 @@ impl GitExecutor {
             cmd.creation_flags(CREATE_NO_WINDOW);
         }
 
-        let child = cmd
+        let mut child = cmd
             .spawn()
             .map_err(|e| GitError::IoError(format!("Failed to spawn git: {}", e)))?;
+        let stdout_pipe = child.stdout.take();
+        let stderr_pipe = child.stderr.take();
+
+        // Dropping the child on timeout kills it (kill_on_drop)
+        let work = async {
+            let (stdout, stderr, status) = tokio::join!(
+                read_pipe_lines(stdout_pipe, "stdout", &on_line),
+                read_pipe_lines(stderr_pipe, "stderr", &on_line),
+                child.wait()
+            );
+            Ok::<_, std::io::Error>((stdout?, stderr?, status?))
+        };
 
-        let output =
-            match tokio::time::timeout(Duration::from_secs(timeout_secs), child.wait_with_output())
-                .await
-            {
-                Ok(Ok(output)) => output,
+        let (stdout, stderr, status) =
+            match tokio::time::timeout(Duration::from_secs(timeout_secs), work).await {
+                Ok(Ok(result)) => result,
                 Ok(Err(e)) => {
                     return Err(GitError::IoError(format!("git process IO error: {}", e)));
                 }
@@ -361,49 +643,87 @@ impl GitExecutor {
                         "[GIT TIMEOUT] git {} (after {}s)",
                         args_display, timeout_secs
                     );
+                    self.record_operation(repo_path, args, None, start);
                     return Err(GitError::Timeout(timeout_secs));
                 }
             };
 
         let duration = start.elapsed();
-        let stdout_bytes = output.stdout;
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        let exit_code = output.status.code().unwrap_or(-1);
-
-        println!(
-            "[GIT END] exit={} | {}ms | stdout={}b stderr={}b | git {}",
-            exit_code,
-            duration.as_millis(),
-            stdout_bytes.len(),
-            stderr.len(),
-            args_display,
-        );
+        let exit_code = status.code().unwrap_or(-1);
+        self.record_operation(repo_path, args, Some(exit_code), start);
 
-        if output.status.success() {
-            return Ok(GitResponseBytes {
-                stdout: stdout_bytes,
+        if status.success() {
+            return Ok(GitResponse {
+                stdout,
                 stderr,
                 exit_code,
                 duration_ms: duration.as_millis() as u64,
+                truncated: false,
             });
         }
 
-        if stderr.contains("not a git repository") {
+        Err(GitError::CommandError(format!(
+            "git {} failed (exit {}): {}",
+            args_display, exit_code, stderr
+        )))
+    }
+
+    /// Run a git command and return stdout as raw bytes.
+    pub async fn run_with_output_bytes(
+        &self,
+        repo_path: &Path,
+        args: &[String],
+        timeout: Timeout,
+    ) -> GitResult<GitResponseBytes> {
+        self.run_with_output_bytes_capped(repo_path, args, timeout, None)
+            .await
+    }
+
+    /// `run_unchecked` for raw stdout: a non-zero exit is returned as a normal
+    /// response.
+    pub async fn run_with_output_bytes_unchecked(
+        &self,
+        repo_path: &Path,
+        args: &[String],
+        timeout: Timeout,
+    ) -> GitResult<GitResponseBytes> {
+        self.run_raw(repo_path, args, timeout, None).await
+    }
+
+    /// `run_with_output_bytes` with the output cap of `run_capped`.
+    pub async fn run_with_output_bytes_capped(
+        &self,
+        repo_path: &Path,
+        args: &[String],
+        timeout: Timeout,
+        max_output_bytes: Option<usize>,
+    ) -> GitResult<GitResponseBytes> {
+        let output = self
+            .run_raw(repo_path, args, timeout, max_output_bytes)
+            .await?;
+        if output.exit_code == 0 || output.truncated {
+            return Ok(output);
+        }
+
+        if output.stderr.contains("not a git repository") {
             return Err(GitError::NotARepo(repo_path.display().to_string()));
         }
-        if stderr.contains("CONFLICT") {
+        if output.stderr.contains("CONFLICT") {
             return Err(GitError::MergeConflict);
         }
 
         Err(GitError::CommandError(format!(
             "git {} failed (exit {}): {}",
-            args_display, exit_code, stderr
+            redact_secrets(&args.join(" ")),
+            output.exit_code,
+            output.stderr
         )))
     }
 
     /// Run a git command that does not require a repository directory.
     /// Used for diagnostics (`git --version`).
-    pub async fn run_bare(&self, args: &[String], timeout_secs: u64) -> GitResult<GitResponse> {
+    pub async fn run_bare(&self, args: &[String], timeout: Timeout) -> GitResult<GitResponse> {
+        let timeout_secs = self.effective_timeout(timeout);
         let start = Instant::now();
 
         let mut cmd = Command::new(&self.git_binary);
@@ -441,6 +761,7 @@ impl GitExecutor {
             stderr,
             exit_code,
             duration_ms: duration.as_millis() as u64,
+            truncated: false,
         })
     }
 
@@ -457,18 +778,238 @@ impl GitExecutor {
             Ok(r) => Some(r.stdout.trim().to_string()),
             Err(_) => None,
         };
+        let parsed_version = version.as_deref().and_then(GitVersion::parse);
+        if let (Some(parsed), Ok(mut cached)) = (parsed_version, self.version.write()) {
+            *cached = Some(parsed);
+        }
 
         DiagnosticInfo {
             git_version: version,
+            parsed_version,
             git_path: self.git_binary.display().to_string(),
             path_env: std::env::var("PATH").unwrap_or_default(),
             platform: std::env::consts::OS.to_string(),
         }
     }
 
+    /// The installed git version, queried once and then cached.
+    /// `None` when the version could not be determined.
+    pub async fn version(&self) -> Option<GitVersion> {
+        if let Some(version) = self.version.read().ok().and_then(|cached| *cached) {
+            return Some(version);
+        }
+
+        let resp = self
+            .run_bare(&["--version".to_string()], TIMEOUT_QUICK)
+            .await
+            .ok()?;
+        let version = GitVersion::parse(&resp.stdout)?;
+        if let Ok(mut cached) = self.version.write() {
+            *cached = Some(version);
+        }
+        Some(version)
+    }
+
+    /// Whether `git switch` is available. Assumes a modern git when the
+    /// version is unknown.
+    pub async fn supports_switch(&self) -> bool {
+        self.version()
+            .await
+            .is_none_or(|v| v >= SWITCH_RESTORE_MIN_VERSION)
+    }
+
+    /// Whether `git restore` is available. Assumes a modern git when the
+    /// version is unknown.
+    pub async fn supports_restore(&self) -> bool {
+        self.version()
+            .await
+            .is_none_or(|v| v >= SWITCH_RESTORE_MIN_VERSION)
+    }
+
+    /// Whether `git sparse-checkout` is available. Assumes a modern git when
+    /// the version is unknown.
+    pub async fn supports_sparse_checkout(&self) -> bool {
+        self.version()
+            .await
+            .is_none_or(|v| v >= SPARSE_CHECKOUT_MIN_VERSION)
+    }
+
+    /// Whether `git init -b` is available. Assumes an old git when the version
+    /// is unknown, since the fallback works everywhere.
+    pub async fn supports_init_initial_branch(&self) -> bool {
+        self.version()
+            .await
+            .is_some_and(|v| v >= INIT_INITIAL_BRANCH_MIN_VERSION)
+    }
+
+    /// Whether `git merge-tree --write-tree` is available. Unlike the checks
+    /// above this assumes an old git when the version is unknown, since the
+    /// fallback still works there.
+    pub async fn supports_merge_tree_write_tree(&self) -> bool {
+        self.version()
+            .await
+            .is_some_and(|v| v >= MERGE_TREE_WRITE_TREE_MIN_VERSION)
+    }
+
     /// Return a reference to the resolved binary path.
     #[allow(dead_code)]
     pub fn binary_path(&self) -> &Path {
         &self.git_binary
     }
 }
+
+/// Read `pipe` to the end, or until more than `limit` bytes have arrived.
+/// In that case the data is cut back to the last complete line within the
+/// limit, `true` is returned and the pipe is closed by dropping it.
+/// Normalised lookup key for the per-repo environment table, so `C:\repo`
+/// and `C:\repo\` (or a symlinked path) find the same entry.
+fn repo_env_key(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.components().collect())
+}
+
+async fn read_pipe_capped<R>(
+    pipe: Option<R>,
+    limit: Option<usize>,
+) -> std::io::Result<(Vec<u8>, bool)>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut data = Vec::new();
+    let Some(mut pipe) = pipe else {
+        return Ok((data, false));
+    };
+
+    let mut buf = [0u8; 8192];
+    loop {
+        let n = pipe.read(&mut buf).await?;
+        if n == 0 {
+            return Ok((data, false));
+        }
+        data.extend_from_slice(&buf[..n]);
+        if let Some(limit) = limit.filter(|&limit| data.len() > limit) {
+            let cut = data[..limit]
+                .iter()
+                .rposition(|&b| b == b'\n')
+                .map_or(limit, |i| i + 1);
+            data.truncate(cut);
+            return Ok((data, true));
+        }
+    }
+}
+
+/// Read `pipe` to the end, passing each line to `on_line` as it arrives. A
+/// lone `\r` also ends a line, so progress meters that redraw in place are
+/// reported on every update; `\r\n` counts as a single break.
+async fn read_pipe_lines<R, F>(
+    pipe: Option<R>,
+    stream: &'static str,
+    on_line: &F,
+) -> std::io::Result<String>
+where
+    R: AsyncRead + Unpin,
+    F: Fn(&'static str, &str),
+{
+    let mut collected = Vec::new();
+    let Some(pipe) = pipe else {
+        return Ok(String::new());
+    };
+
+    let mut reader = BufReader::new(pipe);
+    let mut line = Vec::new();
+    let mut after_cr = false;
+    loop {
+        let chunk = reader.fill_buf().await?;
+        if chunk.is_empty() {
+            break;
+        }
+        for &byte in chunk {
+            match byte {
+                b'\n' if after_cr => {}
+                b'\n' | b'\r' => {
+                    on_line(stream, &String::from_utf8_lossy(&line));
+                    line.clear();
+                }
+                _ => line.push(byte),
+            }
+            after_cr = byte == b'\r';
+        }
+        collected.extend_from_slice(chunk);
+        let len = chunk.len();
+        reader.consume(len);
+    }
+    if !line.is_empty() {
+        on_line(stream, &String::from_utf8_lossy(&line));
+    }
+    Ok(String::from_utf8_lossy(&collected).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_envs_for_ignores_trailing_separators() {
+        let dir = std::env::temp_dir();
+        let executor = GitExecutor::new(PathBuf::from("git"), Arc::new(OperationLog::new(1)));
+        let mut with_slash = dir.clone().into_os_string();
+        with_slash.push(std::path::MAIN_SEPARATOR_STR);
+
+        executor.set_repo_envs(HashMap::from([(
+            PathBuf::from(&with_slash),
+            vec![("HTTP_PROXY".to_string(), "http://proxy".to_string())],
+        )]));
+
+        assert_eq!(executor.repo_envs_for(&dir).len(), 1);
+        assert_eq!(executor.repo_envs_for(Path::new(&with_slash)).len(), 1);
+        assert!(executor
+            .repo_envs_for(Path::new("/definitely/not/a/repo"))
+            .is_empty());
+    }
+
+    #[test]
+    fn repo_env_key_trims_missing_paths_without_touching_disk() {
+        assert_eq!(
+            repo_env_key(Path::new("/no/such/repo/")),
+            PathBuf::from("/no/such/repo")
+        );
+    }
+
+    #[tokio::test]
+    async fn read_pipe_capped_cuts_at_last_complete_line() {
+        let output: &[u8] = b"first\nsecond\nthird\n";
+
+        let (data, truncated) = read_pipe_capped(Some(output), None).await.unwrap();
+        assert_eq!(data, output);
+        assert!(!truncated);
+
+        let (data, truncated) = read_pipe_capped(Some(output), Some(15)).await.unwrap();
+        assert_eq!(data, b"first\nsecond\n");
+        assert!(truncated);
+
+        // A single line longer than the cap is cut mid-line
+        let (data, truncated) = read_pipe_capped(Some(output), Some(3)).await.unwrap();
+        assert_eq!(data, b"fir");
+        assert!(truncated);
+    }
+
+    #[tokio::test]
+    async fn read_pipe_lines_splits_on_carriage_returns() {
+        let output: &[u8] = b"Checking objects:  50%\rChecking objects: 100%, done.\r\nlast";
+        let lines = std::sync::Mutex::new(Vec::new());
+        let collected = read_pipe_lines(Some(output), "stderr", &|_, line: &str| {
+            lines.lock().unwrap().push(line.to_string());
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(collected.as_bytes(), output);
+        assert_eq!(
+            *lines.lock().unwrap(),
+            vec![
+                "Checking objects:  50%",
+                "Checking objects: 100%, done.",
+                "last"
+            ]
+        );
+    }
+}
@@ -7,6 +7,9 @@ pub struct GitResponse {
     pub stderr: String,
     pub exit_code: i32,
     pub duration_ms: u64,
+    /// stdout was cut off at the caller's output cap (see `run_capped`).
+    #[serde(default)]
+    pub truncated: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -15,6 +18,7 @@ pub struct GitResponseBytes {
     pub stderr: String,
     pub exit_code: i32,
     pub duration_ms: u64,
+    pub truncated: bool,
 }
 
 #[derive(Error, Debug, Serialize)]
@@ -52,6 +56,119 @@ impl From<std::io::Error> for GitError {
     }
 }
 
+/// Error returned by Tauri commands.
+///
+/// Mirrors the `GitError` variants (same serde shape) so the frontend can tell
+/// a timeout from a merge conflict, and adds app-level failures that don't
+/// come from git itself.
+#[derive(Error, Debug, Serialize)]
+#[serde(tag = "type", content = "message")]
+pub enum CommandError {
+    #[error("Not a git repository: {0}")]
+    NotARepo(String),
+
+    #[error("Git command failed: {0}")]
+    #[serde(rename = "CommandError")]
+    GitCommandFailed(String),
+
+    #[error("Merge conflict detected")]
+    MergeConflict,
+
+    #[error("IO error: {0}")]
+    IoError(String),
+
+    #[error("Git binary not found: {0}")]
+    GitNotFound(String),
+
+    #[error("Command timed out after {0} seconds")]
+    Timeout(u64),
+
+    #[error("Invalid repository path: {0}")]
+    InvalidRepoPath(String),
+
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+
+    /// `user.name`/`user.email` are not configured, so git refused to commit.
+    /// Fixed by setting them with `cmd_set_config`.
+    #[error("{0}")]
+    MissingIdentity(String),
+
+    #[error("{0}")]
+    App(String),
+
+    #[error("Unknown error: {0}")]
+    Unknown(String),
+}
+
+impl From<GitError> for CommandError {
+    fn from(err: GitError) -> Self {
+        match err {
+            GitError::NotARepo(path) => CommandError::NotARepo(path),
+            GitError::CommandError(msg) if is_auth_failure(&msg) => {
+                CommandError::AuthenticationFailed(msg)
+            }
+            GitError::CommandError(msg) => CommandError::GitCommandFailed(msg),
+            GitError::MergeConflict => CommandError::MergeConflict,
+            GitError::IoError(msg) => CommandError::IoError(msg),
+            GitError::GitNotFound(msg) => CommandError::GitNotFound(msg),
+            GitError::Timeout(secs) => CommandError::Timeout(secs),
+            GitError::InvalidRepoPath(path) => CommandError::InvalidRepoPath(path),
+            GitError::Unknown(msg) => CommandError::Unknown(msg),
+        }
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(msg: String) -> Self {
+        CommandError::App(msg)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(msg: &str) -> Self {
+        CommandError::App(msg.to_string())
+    }
+}
+
+pub type CommandResult<T> = Result<T, CommandError>;
+
+/// Stderr fragments git and common credential helpers emit when a network
+/// operation fails because no usable credentials are available.
+const AUTH_FAILURE_PATTERNS: &[&str] = &[
+    "authentication failed",
+    "could not read username",
+    "could not read password",
+    "terminal prompts disabled",
+    "permission denied (publickey",
+    "invalid username or password",
+    "http basic: access denied",
+    "the requested url returned error: 401",
+    "the requested url returned error: 403",
+];
+
+/// Stderr fragments git emits when committing without `user.name`/`user.email`.
+const MISSING_IDENTITY_PATTERNS: &[&str] = &["please tell me who you are", "empty ident name"];
+
+pub const MISSING_IDENTITY_MESSAGE: &str = "Git doesn't know who you are. Set user.name and user.email in the repository or global config, then commit again.";
+
+pub fn is_missing_identity(stderr: &str) -> bool {
+    let lower = stderr.to_lowercase();
+    MISSING_IDENTITY_PATTERNS
+        .iter()
+        .any(|pattern| lower.contains(pattern))
+}
+
+pub fn is_auth_failure(stderr: &str) -> bool {
+    let lower = stderr.to_lowercase();
+    AUTH_FAILURE_PATTERNS
+        .iter()
+        .any(|pattern| lower.contains(pattern))
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ConflictFile {
     pub base: String,
@@ -59,9 +176,77 @@ pub struct ConflictFile {
     pub theirs: String,
 }
 
+/// One region of a working-tree file that still contains conflict markers.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum MergeRegion {
+    Unchanged(String),
+    /// `base` is only present when the file was written with the `diff3` or
+    /// `zdiff3` conflict style.
+    Conflict {
+        ours: String,
+        theirs: String,
+        base: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveConflictResult {
+    /// The written content still contains conflict markers.
+    pub has_conflict_markers: bool,
+}
+
+/// Details of a Git LFS pointer file (`oid` is e.g. `sha256:<hex>`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LfsPointerInfo {
+    pub is_lfs_pointer: bool,
+    pub oid: Option<String>,
+    pub size: Option<u64>,
+}
+
+/// Parsed `git --version` output.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GitVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl GitVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parse output such as `git version 2.39.2.windows.1` or
+    /// `git version 2.37.1 (Apple Git-137.1)`.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let version = raw
+            .split_whitespace()
+            .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))?;
+        let mut parts = version.split('.').map(|part| {
+            part.chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect::<String>()
+                .parse::<u32>()
+                .ok()
+        });
+        let major = parts.next()??;
+        let minor = parts.next().flatten().unwrap_or(0);
+        let patch = parts.next().flatten().unwrap_or(0);
+        Some(Self::new(major, minor, patch))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DiagnosticInfo {
     pub git_version: Option<String>,
+    pub parsed_version: Option<GitVersion>,
     pub git_path: String,
     pub path_env: String,
     pub platform: String,
@@ -81,6 +266,10 @@ pub enum GitCommandType {
     Push,
     Fetch,
     Branch,
+    Stash,
+    Tag,
+    Reset,
+    Revert,
     Other,
 }
 
@@ -94,6 +283,42 @@ pub struct GitCommandResult {
     pub command_type: GitCommandType,
 }
 
+/// Result of checking out a commit; `detached` reports whether HEAD ended up
+/// detached (it always should, but the UI keys its banner off this flag).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckoutCommitResult {
+    #[serde(flatten)]
+    pub result: GitCommandResult,
+    pub detached: bool,
+}
+
+/// Outcome of trying a merge, rebase or cherry-pick without keeping the result.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeDryRunResult {
+    pub would_conflict: bool,
+    pub conflicting_files: Vec<String>,
+}
+
+/// What a reset would leave behind, without running it.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetDryRunResult {
+    /// Commits reachable from HEAD but not from the reset target.
+    pub commits_left_behind: u32,
+    /// Files with uncommitted changes a hard reset would throw away.
+    pub discarded_files: Vec<String>,
+}
+
+/// Whether a branch merges into HEAD without conflicts.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeCheckResult {
+    pub clean: bool,
+    pub conflicting_files: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum RebaseStatus {
@@ -120,6 +345,10 @@ pub struct RebaseTodoItem {
     pub action: String,
     pub hash: String,
     pub message: String,
+    /// Replacement commit message for `reword`, or the combined message for
+    /// the last `squash` of a chain. `None` keeps git's default.
+    #[serde(default)]
+    pub new_message: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -1,10 +1,13 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod commands;
+mod confirmation;
 mod git;
 mod models;
+mod secrets;
 mod settings;
 mod terminal;
+mod watcher;
 
 use git::GitExecutor;
 use settings::AppState;
@@ -22,24 +25,41 @@ fn main() {
         .plugin(tauri_plugin_dialog::init())
         .setup(move |app| {
             let app_state = AppState::new(git_binary);
-            let saved_settings = settings::load_settings(app.handle());
+            let mut saved_settings = settings::load_settings(app.handle());
+            settings::migrate_gemini_token(app.handle(), &mut saved_settings);
+            app_state.sync_repo_envs(&saved_settings);
+            app_state.sync_timeouts(&saved_settings);
+            let open_repos: Vec<_> = saved_settings
+                .repos
+                .iter()
+                .filter(|r| saved_settings.open_repo_ids.contains(&r.id))
+                .map(|r| (r.id.clone(), r.path.clone()))
+                .collect();
             *app_state.settings.lock().expect("Failed to lock settings") = saved_settings;
 
             app.manage(app_state);
+            for (id, path) in open_repos {
+                watcher::watch_repo(app.handle(), id, path);
+            }
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::run_git,
             commands::cmd_diagnostics,
+            commands::cmd_get_operation_log,
             commands::cmd_get_conflicts,
             commands::cmd_get_conflict_file,
+            commands::cmd_get_conflict_merged,
+            commands::cmd_resolve_conflict_with_content,
             commands::cmd_resolve_ours,
             commands::cmd_resolve_theirs,
             commands::cmd_mark_resolved,
             commands::cmd_write_file,
             commands::cmd_get_operation_state,
+            commands::cmd_get_repo_state,
             commands::cmd_get_settings,
             commands::cmd_add_repo,
+            commands::cmd_git_init,
             commands::cmd_remove_repo,
             commands::cmd_set_active_repo,
             commands::cmd_open_repo,
@@ -47,7 +67,17 @@ fn main() {
             commands::cmd_get_active_repo,
             commands::cmd_git_status,
             commands::cmd_set_excluded_files,
+            commands::cmd_set_repo_excluded_files,
+            commands::cmd_preview_exclusions,
             commands::cmd_set_repo_filter,
+            commands::cmd_set_repo_env,
+            commands::cmd_set_require_confirmation,
+            commands::cmd_set_timeouts,
+            commands::cmd_set_date_format,
+            commands::cmd_set_max_file_view_bytes,
+            commands::cmd_export_settings,
+            commands::cmd_import_settings,
+            commands::cmd_prepare_destructive,
             commands::cmd_set_gemini_api_token,
             commands::cmd_set_gemini_model,
             commands::cmd_get_gemini_models,
@@ -56,7 +86,11 @@ fn main() {
             commands::cmd_git_pull,
             commands::cmd_git_push,
             commands::cmd_git_fetch,
+            commands::cmd_check_for_updates,
             commands::cmd_git_commit,
+            commands::cmd_git_amend_staged,
+            commands::cmd_get_commit_template,
+            commands::cmd_get_recent_commit_messages,
             commands::cmd_generate_commit_message,
             commands::cmd_get_default_ai_prompt,
             commands::cmd_git_add_all,
@@ -64,67 +98,135 @@ fn main() {
             commands::cmd_git_branch_list,
             commands::cmd_git_log,
             commands::cmd_get_commit_graph,
+            commands::cmd_get_commit_graph_structured,
+            commands::cmd_get_commit_graph_lanes,
+            commands::cmd_get_commit_parents,
+            commands::cmd_verify_commit_signature,
+            commands::cmd_git_log_pickaxe,
+            commands::cmd_git_describe,
+            commands::cmd_git_fsck,
+            commands::cmd_git_gc,
+            commands::cmd_get_repo_size,
             commands::cmd_check_conflict_state,
             commands::cmd_get_git_branches,
+            commands::cmd_get_branches_detailed,
+            commands::cmd_get_refs_for_commit,
             commands::cmd_get_current_branch,
+            commands::cmd_get_head_info,
             commands::cmd_git_switch_branch,
+            commands::cmd_git_checkout_commit,
+            commands::cmd_git_reattach,
             commands::cmd_git_checkout_new_branch,
             commands::cmd_git_create_branch,
             commands::cmd_git_merge,
+            commands::cmd_git_merge_abort,
+            commands::cmd_git_merge_dry_run,
+            commands::cmd_can_merge_cleanly,
+            commands::cmd_git_rebase_dry_run,
             commands::cmd_git_rebase,
             commands::cmd_git_cherry_pick,
+            commands::cmd_cherry_pick_range,
+            commands::cmd_git_cherry_pick_dry_run,
             commands::cmd_abort_operation,
+            commands::cmd_abort_current_operation,
             commands::cmd_get_pending_commits_count,
+            commands::cmd_get_unpushed_commits,
+            commands::cmd_get_commit_count,
+            commands::cmd_get_contributors,
             commands::cmd_get_status_files,
+            commands::cmd_get_change_summary,
             commands::cmd_get_diff_file,
+            commands::cmd_get_diff_file_stream,
             commands::cmd_get_file_base_content,
             commands::cmd_get_file_modified_content,
+            commands::cmd_get_diff_context,
             commands::cmd_git_add,
+            commands::cmd_git_mv,
             commands::cmd_git_stage_line,
             commands::cmd_git_unstage_line,
+            commands::cmd_git_apply_patch,
             commands::cmd_git_unstage,
             commands::cmd_git_discard_changes,
+            commands::cmd_git_clean_preview,
             commands::cmd_git_stash_file,
+            commands::cmd_git_stash_files,
             commands::cmd_git_stash_all,
             commands::cmd_git_apply_stash,
             commands::cmd_git_pop_stash,
             commands::cmd_git_delete_stash,
             commands::cmd_git_edit_stash_message,
             commands::cmd_create_patch_from_stash,
+            commands::cmd_git_stash_show,
+            commands::cmd_diff_working_vs_stash,
+            commands::cmd_get_stash_count,
             commands::cmd_open_repo_file,
             commands::cmd_git_ignore_file,
+            commands::cmd_get_local_exclude,
+            commands::cmd_set_local_exclude,
             commands::cmd_show_in_folder,
             commands::cmd_open_in_editor,
             commands::cmd_open_in_diff_tool,
             commands::cmd_create_patch,
             commands::cmd_create_patch_from_commit,
+            commands::cmd_format_patch,
+            commands::cmd_git_bundle_create,
             commands::cmd_delete_file,
             commands::cmd_git_blame,
+            commands::cmd_blame_line,
             commands::cmd_git_unstage_all,
             commands::cmd_get_file_history,
+            commands::cmd_get_line_history,
             commands::cmd_search_repo_files,
+            commands::cmd_get_tracked_file_tree,
+            commands::cmd_get_directory_contents,
             commands::cmd_get_commit_diff,
             commands::cmd_get_file_at_commit,
+            commands::cmd_restore_file_from_commit,
+            commands::cmd_cat_file,
+            commands::cmd_get_lfs_pointer_info,
+            commands::cmd_get_config,
+            commands::cmd_set_config,
+            commands::cmd_get_aliases,
+            commands::cmd_run_alias,
+            commands::cmd_sparse_checkout_init,
+            commands::cmd_sparse_checkout_set,
+            commands::cmd_sparse_checkout_list,
+            commands::cmd_sparse_checkout_disable,
+            commands::cmd_list_git_hooks,
+            commands::cmd_toggle_git_hook,
+            commands::cmd_git_lfs_track,
+            commands::cmd_git_lfs_untrack,
+            commands::cmd_git_lfs_status,
             commands::cmd_terminal_start,
             commands::cmd_terminal_write,
             commands::cmd_terminal_stop,
             commands::cmd_get_commit_changed_files,
             commands::cmd_get_commit_file_diff,
+            commands::cmd_get_commit_file_diff_parsed,
             commands::cmd_git_revert,
+            commands::cmd_git_reset_dry_run,
             commands::cmd_git_reset,
             commands::cmd_git_create_tag,
             commands::cmd_git_delete_branch,
+            commands::cmd_get_merged_branches,
+            commands::cmd_delete_branches,
             commands::cmd_git_delete_remote_branch,
+            commands::cmd_prune_remote,
             commands::cmd_git_rename_branch,
             commands::cmd_get_rebase_status,
             commands::cmd_rebase_start,
+            commands::cmd_rebase_onto,
             commands::cmd_rebase_interactive_prepare,
             commands::cmd_rebase_interactive_apply,
+            commands::cmd_get_rebase_todo,
+            commands::cmd_set_rebase_todo,
             commands::cmd_rebase_continue,
             commands::cmd_rebase_abort,
             commands::cmd_rebase_skip,
             commands::cmd_git_set_upstream,
             commands::cmd_get_branch_tip,
+            commands::cmd_get_merge_base,
+            commands::cmd_is_ancestor,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -40,6 +40,222 @@ pub struct FileCommit {
     pub author: String,
     pub date: String,
     pub message: String,
+    /// This commit's diff for the file, only filled in when requested.
+    pub patch: Option<DiffFile>,
+}
+
+/// Text for the file and diff viewers, or only its size when it is over
+/// `AppSettings::max_file_view_bytes`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileContent {
+    pub content: String,
+    pub too_large: bool,
+    /// Size in bytes, before decoding.
+    pub size: u64,
+}
+
+impl FileContent {
+    pub fn text(content: String, size: u64) -> Self {
+        Self {
+            content,
+            too_large: false,
+            size,
+        }
+    }
+
+    pub fn too_large(size: u64) -> Self {
+        Self {
+            content: String::new(),
+            too_large: true,
+            size,
+        }
+    }
+}
+
+/// Any git object as printed by `cat-file -p`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitObject {
+    /// `blob`, `tree`, `commit` or `tag`.
+    pub object_type: String,
+    pub content: FileContent,
+}
+
+/// A commit as shown in the graph (`%H|%P|%d|%an|%cd|%s`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphCommit {
+    pub hash: String,
+    pub parents: Vec<String>,
+    pub refs: Vec<String>,
+    pub author: String,
+    pub date: String,
+    pub subject: String,
+}
+
+/// A line in the commit graph from a lane in one row to a lane in the next.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphEdge {
+    pub from_lane: u32,
+    pub to_lane: u32,
+}
+
+/// A commit with its lane (column) in the graph and the edges drawn from
+/// its row down to the next one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphRow {
+    pub commit: GraphCommit,
+    pub lane: u32,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// A commit's immediate neighbours, for stepping through the graph.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitParents {
+    /// Full SHA of the commit itself.
+    pub hash: String,
+    pub parents: Vec<String>,
+    pub is_merge: bool,
+    /// Commits on any ref whose parent this is; `None` unless requested.
+    pub children: Option<Vec<String>>,
+}
+
+/// The result of checking a commit's GPG signature.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitSignature {
+    pub signed: bool,
+    /// A good signature from a key that hasn't expired or been revoked.
+    pub valid: bool,
+    pub signer: Option<String>,
+    pub key_id: Option<String>,
+}
+
+/// Tags and branches that point at or contain a commit.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitRefs {
+    /// Tags pointing directly at the commit.
+    pub tags: Vec<String>,
+    /// Local branches containing the commit.
+    pub branches: Vec<String>,
+    /// Remote-tracking branches containing the commit, e.g. `origin/main`.
+    pub remote_branches: Vec<String>,
+}
+
+/// Headline numbers for the repository overview panel.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitCount {
+    /// Commits reachable from the requested branch (or HEAD).
+    pub total_commits: u64,
+    /// Distinct author emails across all refs, after `.mailmap`.
+    pub contributors: u32,
+}
+
+/// The checked-out branch, or the commit HEAD is detached at.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentBranch {
+    pub branch: Option<String>,
+    pub detached: bool,
+    /// Short hash of the detached HEAD, e.g. during a bisect or rebase.
+    pub describe: Option<String>,
+}
+
+/// Everything the header shows about HEAD, gathered in one call.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HeadInfo {
+    /// `None` when HEAD is detached.
+    pub branch: Option<String>,
+    pub detached: bool,
+    /// Empty on an unborn branch (no commits yet).
+    pub commit: String,
+    pub short_commit: String,
+    pub upstream: Option<String>,
+    pub ahead: u32,
+    pub behind: u32,
+}
+
+/// A branch with its tip commit and upstream tracking state.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchInfo {
+    /// Short name; remote branches are `remotes/<remote>/<branch>` as in
+    /// `cmd_get_git_branches`.
+    pub name: String,
+    pub is_current: bool,
+    pub upstream: Option<String>,
+    pub last_commit_hash: String,
+    /// Committer date, ISO 8601.
+    pub last_commit_date: String,
+    pub last_commit_subject: String,
+    pub ahead: u32,
+    pub behind: u32,
+    /// The configured upstream no longer exists on the remote, typically
+    /// because it was deleted after merging.
+    pub upstream_gone: bool,
+}
+
+/// Order for `cmd_get_branches_detailed`. The current branch always comes
+/// first regardless.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum BranchSort {
+    Name,
+    /// Most recent commit first.
+    #[default]
+    CommitDate,
+    /// Most diverged from upstream (ahead + behind) first.
+    AheadBehind,
+}
+
+/// An author and how many commits they made (`git shortlog -sne`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Contributor {
+    pub name: String,
+    pub email: String,
+    pub commit_count: u32,
+}
+
+/// What the upstream gained since the last fetch, for the "new commits
+/// available" banner. All zero/empty when the branch has no upstream.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCheck {
+    pub has_upstream: bool,
+    pub behind: u32,
+    pub ahead: u32,
+    /// Upstream commits not yet in HEAD, newest first (capped).
+    pub new_commits: Vec<GraphCommit>,
+}
+
+/// A node of the tracked file tree; `children` is empty for files.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeNode {
+    pub name: String,
+    pub path: String,
+    pub is_dir: bool,
+    pub children: Vec<TreeNode>,
+}
+
+/// An immediate child of a directory at some commit (`git ls-tree -l`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryEntry {
+    pub name: String,
+    pub path: String,
+    /// `tree`, `blob` or `commit` (a submodule)
+    pub object_type: String,
+    /// Blob size in bytes; `None` for trees and submodules.
+    pub size: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -75,6 +291,10 @@ pub struct DiffFile {
     pub path: String,
     pub status: String, // "M", "A", "D", "R" etc
     pub hunks: Vec<DiffHunk>,
+    /// Hunks were left out because the whole diff was too long; load this
+    /// file on its own to see them.
+    #[serde(default)]
+    pub truncated: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
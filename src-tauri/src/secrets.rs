@@ -0,0 +1,38 @@
+use keyring::Entry;
+
+/// Service name the app's credentials are stored under in the OS keychain
+/// (Keychain on macOS, Credential Manager on Windows, Secret Service on Linux).
+const KEYRING_SERVICE: &str = "GitHelper";
+
+const GEMINI_TOKEN_ACCOUNT: &str = "gemini_api_token";
+
+fn gemini_entry() -> Result<Entry, String> {
+    Entry::new(KEYRING_SERVICE, GEMINI_TOKEN_ACCOUNT)
+        .map_err(|e| format!("Failed to open system keychain: {}", e))
+}
+
+/// The stored Gemini API token, or `None` if none has been saved.
+pub fn get_gemini_token() -> Result<Option<String>, String> {
+    match gemini_entry()?.get_password() {
+        Ok(token) => Ok(Some(token)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read Gemini token from keychain: {}", e)),
+    }
+}
+
+/// Store the Gemini API token, or remove it when `token` is `None`.
+pub fn set_gemini_token(token: Option<&str>) -> Result<(), String> {
+    let entry = gemini_entry()?;
+    match token {
+        Some(token) => entry
+            .set_password(token)
+            .map_err(|e| format!("Failed to save Gemini token to keychain: {}", e)),
+        None => match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(format!(
+                "Failed to remove Gemini token from keychain: {}",
+                e
+            )),
+        },
+    }
+}
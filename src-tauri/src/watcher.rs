@@ -0,0 +1,276 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex, Weak};
+use std::time::Duration;
+
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use serde_json::json;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::git::service::TIMEOUT_QUICK;
+use crate::settings::AppState;
+
+/// Quiet period before a burst of filesystem changes is reported. Rebases and
+/// checkouts touch many files in quick succession; they collapse into one event.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watches open repositories (worktree and git dir) and emits `git-event`
+/// when something changes outside the app, e.g. from a terminal or an IDE.
+pub struct RepoWatchManager {
+    watchers: Mutex<HashMap<String, Arc<Mutex<RepoWatch>>>>,
+}
+
+/// A repository's debouncer and the worktree directories it watches.
+struct RepoWatch {
+    debouncer: Debouncer<RecommendedWatcher>,
+    dirs: HashSet<PathBuf>,
+}
+
+/// What one repository's watcher looks at.
+#[derive(Clone)]
+struct WatchedRepo {
+    worktree: PathBuf,
+    git_dir: PathBuf,
+    /// Ignored files and directories such as `node_modules` or `target`.
+    /// Ignored directories are not watched, so their churn costs neither
+    /// watches nor events.
+    ignored: Arc<HashSet<PathBuf>>,
+}
+
+impl RepoWatchManager {
+    pub fn new() -> Self {
+        Self {
+            watchers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Start watching a repository under `repo_id`. No-op if already watched.
+    ///
+    /// The git dir is watched recursively; the worktree is watched one
+    /// directory at a time so `ignored` directories can be left out.
+    /// Directories created later are picked up as they appear.
+    fn start(&self, app: AppHandle, repo_id: &str, repo: WatchedRepo) -> Result<(), String> {
+        let mut watchers = self.watchers.lock().map_err(|e| e.to_string())?;
+        if watchers.contains_key(repo_id) {
+            return Ok(());
+        }
+
+        let (tx, rx) = mpsc::channel::<DebounceEventResult>();
+        let mut debouncer = new_debouncer(DEBOUNCE, tx)
+            .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+        debouncer
+            .watcher()
+            .watch(&repo.git_dir, RecursiveMode::Recursive)
+            .map_err(|e| format!("Failed to watch {}: {}", repo.git_dir.display(), e))?;
+        let mut watch = RepoWatch {
+            debouncer,
+            dirs: HashSet::new(),
+        };
+        watch_tree(&mut watch, &repo.worktree, &repo.ignored);
+
+        let watch = Arc::new(Mutex::new(watch));
+        let weak = Arc::downgrade(&watch);
+        let event_repo_id = repo_id.to_string();
+        // Ends once the debouncer is dropped, which closes the channel
+        std::thread::spawn(move || {
+            while let Ok(result) = rx.recv() {
+                let Ok(events) = result else {
+                    continue;
+                };
+                update_watched_dirs(&weak, &repo, events.iter().map(|event| &event.path));
+                if events
+                    .iter()
+                    .any(|event| is_relevant_change(&repo, &event.path))
+                {
+                    let _ = app.emit(
+                        "git-event",
+                        json!({ "type": "change", "source": "watcher", "repoId": event_repo_id }),
+                    );
+                }
+            }
+        });
+
+        watchers.insert(repo_id.to_string(), watch);
+        Ok(())
+    }
+
+    /// Stop watching `repo_id`. Dropping the debouncer stops its watcher thread.
+    pub fn stop(&self, repo_id: &str) -> Result<(), String> {
+        let mut watchers = self.watchers.lock().map_err(|e| e.to_string())?;
+        watchers.remove(repo_id);
+        Ok(())
+    }
+}
+
+/// Start watching an open repository in the background; finding its git dir
+/// and ignored directories takes a couple of git calls. Failures are logged,
+/// since the app works without a watcher, just without external refreshes.
+pub fn watch_repo(app: &AppHandle, repo_id: String, repo_path: String) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let result = match resolve_watched_repo(&state, Path::new(&repo_path)).await {
+            Ok(repo) => state.watchers.start(app.clone(), &repo_id, repo),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            println!("[WATCHER] Not watching {}: {}", repo_path, e);
+            return;
+        }
+
+        // The repository may have been closed while the watcher was starting
+        let still_open = state
+            .settings
+            .lock()
+            .is_ok_and(|settings| settings.open_repo_ids.contains(&repo_id));
+        if !still_open {
+            let _ = state.watchers.stop(&repo_id);
+        }
+    });
+}
+
+async fn resolve_watched_repo(state: &AppState, repo_path: &Path) -> Result<WatchedRepo, String> {
+    let args: Vec<String> = vec!["rev-parse".into(), "--absolute-git-dir".into()];
+    let resp = state
+        .git
+        .run(repo_path, &args, TIMEOUT_QUICK)
+        .await
+        .map_err(|e| e.to_string())?;
+    let git_dir = PathBuf::from(resp.stdout.trim());
+
+    let args: Vec<String> = vec![
+        "ls-files".into(),
+        "-z".into(),
+        "--others".into(),
+        "--ignored".into(),
+        "--exclude-standard".into(),
+        "--directory".into(),
+    ];
+    let resp = state
+        .git
+        .run(repo_path, &args, TIMEOUT_QUICK)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(WatchedRepo {
+        worktree: repo_path.to_path_buf(),
+        git_dir,
+        ignored: Arc::new(parse_ignored_paths(repo_path, &resp.stdout)),
+    })
+}
+
+/// Paths from `ls-files -z --others --ignored --directory`, which lists an
+/// ignored directory once, with a trailing slash, instead of its contents.
+/// Also used to keep ignored files out of discard backups.
+pub(crate) fn parse_ignored_paths(root: &Path, output: &str) -> HashSet<PathBuf> {
+    output
+        .split('\0')
+        .map(|entry| entry.trim_end_matches('/'))
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| root.join(entry))
+        .collect()
+}
+
+/// Watch `dir` and every directory below it, skipping `.git`, ignored
+/// directories, symlinks and directories that are already watched.
+fn watch_tree(watch: &mut RepoWatch, dir: &Path, ignored: &HashSet<PathBuf>) {
+    if watch.dirs.contains(dir)
+        || watch
+            .debouncer
+            .watcher()
+            .watch(dir, RecursiveMode::NonRecursive)
+            .is_err()
+    {
+        return;
+    }
+    watch.dirs.insert(dir.to_path_buf());
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        if is_dir && entry.file_name() != ".git" && !ignored.contains(&path) {
+            watch_tree(watch, &path, ignored);
+        }
+    }
+}
+
+/// Follow worktree directories created or removed since the watch started.
+fn update_watched_dirs<'a>(
+    watch: &Weak<Mutex<RepoWatch>>,
+    repo: &WatchedRepo,
+    paths: impl Iterator<Item = &'a PathBuf>,
+) {
+    let Some(watch) = watch.upgrade() else {
+        return;
+    };
+    let Ok(mut watch) = watch.lock() else {
+        return;
+    };
+    for path in paths {
+        if !path.starts_with(&repo.worktree) || path.starts_with(&repo.git_dir) {
+            continue;
+        }
+        if !path.exists() {
+            // The watches went with the directories
+            watch.dirs.retain(|dir| !dir.starts_with(path));
+        } else if path.is_dir() && !path.is_symlink() && is_relevant_change(repo, path) {
+            watch_tree(&mut watch, path, &repo.ignored);
+        }
+    }
+}
+
+/// Ignore churn that never changes what the UI shows: object database writes,
+/// transient `*.lock` files git creates while updating refs or the index, and
+/// anything in an ignored directory.
+fn is_relevant_change(repo: &WatchedRepo, path: &Path) -> bool {
+    if path.extension().is_some_and(|ext| ext == "lock") {
+        return false;
+    }
+    if let Ok(relative) = path.strip_prefix(&repo.git_dir) {
+        return !matches!(
+            relative.components().next(),
+            Some(Component::Normal(first)) if first == "objects" || first == "logs"
+        );
+    }
+    !repo.ignored.iter().any(|dir| path.starts_with(dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignored_dirs_and_object_writes_are_not_relevant() {
+        let root = PathBuf::from("repo");
+        let ignored = parse_ignored_paths(&root, "node_modules/\0build.log\0web/dist/\0");
+        let repo = WatchedRepo {
+            worktree: root.clone(),
+            git_dir: root.join(".git"),
+            ignored: Arc::new(ignored),
+        };
+
+        assert!(is_relevant_change(&repo, &root.join("src").join("main.rs")));
+        assert!(is_relevant_change(&repo, &root.join(".git").join("HEAD")));
+        assert!(!is_relevant_change(
+            &repo,
+            &root.join(".git").join("index.lock")
+        ));
+        assert!(!is_relevant_change(
+            &repo,
+            &root.join(".git").join("objects").join("ab")
+        ));
+        assert!(!is_relevant_change(
+            &repo,
+            &root.join("node_modules").join("x").join("index.js")
+        ));
+        assert!(!is_relevant_change(
+            &repo,
+            &root.join("web").join("dist").join("app.js")
+        ));
+        assert!(!is_relevant_change(&repo, &root.join("build.log")));
+        assert!(is_relevant_change(&repo, &root.join("build.log.txt")));
+    }
+}
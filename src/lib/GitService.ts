@@ -1,5 +1,6 @@
 import type { DiffStageLineTarget } from "./diff";
-import type { BlameLine, CommitDiff, FileCommit, GitCommandResult } from "./types";
+import type { BlameLine, CommitDiff, CurrentBranch, FileCommit, GitCommandResult } from "./types";
+import type { RebaseStepInfo } from "./rebaseStore";
 export type { GitCommandResult };
 import { RepositoryService } from "./services/RepositoryService";
 import { FileService } from "./services/FileService";
@@ -14,11 +15,45 @@ export interface ConflictFile {
   theirs: string;
 }
 
+export type MergeRegion =
+  | { unchanged: string }
+  | { conflict: { ours: string; theirs: string; base: string | null } };
+
+export interface ResolveConflictResult {
+  hasConflictMarkers: boolean;
+}
+
+export type ConfigScope = "local" | "global";
+
+export interface HookInfo {
+  name: string;
+  enabled: boolean;
+  isSample: boolean;
+}
+
+export interface LfsPointerInfo {
+  isLfsPointer: boolean;
+  oid: string | null;
+  size: number | null;
+}
+
+export interface LfsFileStatus {
+  path: string;
+  status: string;
+}
+
+export interface LfsStatus {
+  trackedPatterns: string[];
+  trackedFiles: string[];
+  modifiedFiles: LfsFileStatus[];
+}
+
 export interface GitOperationState {
   isMerging: boolean;
   isRebasing: boolean;
   isCherryPicking: boolean;
   isReverting: boolean;
+  isBisecting: boolean;
   hasConflicts: boolean;
   conflictPaths: string[];
   oursCommit?: string | null;
@@ -27,6 +62,29 @@ export interface GitOperationState {
   theirsBranch?: string | null;
 }
 
+export type RepoOperation =
+  | "clean"
+  | "rebasing"
+  | "merging"
+  | "cherryPicking"
+  | "reverting"
+  | "bisecting";
+
+/** Unified snapshot of any in-progress operation, from `cmd_get_repo_state`. */
+export interface RepoState {
+  operation: RepoOperation;
+  conflictPaths: string[];
+  /** Set while rebasing. */
+  rebaseStep: RebaseStepInfo | null;
+  /** Revisions left to test; set while bisecting once a bad commit is marked. */
+  bisectRemaining: number | null;
+}
+
+export interface AbortedOperation {
+  operation: RepoOperation;
+  result: GitCommandResult;
+}
+
 export interface RepoEntry {
   id: string;
   name: string;
@@ -39,22 +97,66 @@ export interface FileStatus {
   staged: boolean;
 }
 
+export interface ChangeSummary {
+  staged: number;
+  unstaged: number;
+  untracked: number;
+  conflicted: number;
+}
+
 export interface CommitChangedFile {
   path: string;
   status: string;
 }
 
+/** How commit dates are rendered in the log, graph and file history. */
+export type DateFormat = "iso" | "relative" | "local" | "unix";
+
+/** Seconds before a git command is killed, per command category. */
+export interface GitTimeouts {
+  local: number;
+  network: number;
+  quick: number;
+}
+
+/** One git invocation recorded by the backend, newest first in the log. */
+export interface OperationLogEntry {
+  /** Milliseconds since the Unix epoch. */
+  timestamp: number;
+  args: string[];
+  cwd: string;
+  /** Null when the command timed out. */
+  exit_code: number | null;
+  duration_ms: number;
+}
+
 export interface AppSettings {
+  /** Schema version of `settings.json`. */
+  version?: number;
   repos: RepoEntry[];
   active_repo_id: string | null;
   open_repo_ids: string[];
   excluded_files: string[];
+  /** Applied on top of `excluded_files`, keyed by repo id. */
+  repo_excluded_files?: Record<string, string[]>;
   repo_filters: Record<string, string>;
   file_encodings?: Record<string, string>;
-  gemini_api_token?: string | null;
+  /** The token itself lives in the OS keychain and is never sent to the UI. */
+  gemini_token_set?: boolean;
   gemini_model?: string | null;
   global_commit_prompt?: string | null;
   repo_commit_prompts?: Record<string, string>;
+  timeouts?: GitTimeouts;
+  /** `null` keeps each view's own date format. */
+  date_format?: DateFormat | null;
+  /** Files and diffs over this many bytes are not loaded; `null` uses the default. */
+  max_file_view_bytes?: number | null;
+}
+
+export interface SettingsImport {
+  settings: AppSettings;
+  /** Paths of imported repositories that don't exist on this machine. */
+  skippedRepos: string[];
 }
 
 export class GitService {
@@ -66,6 +168,10 @@ export class GitService {
     return RepositoryService.setExcludedFiles(exclusions);
   }
 
+  static async setRepoExcludedFiles(repoId: string, exclusions: string[]): Promise<AppSettings> {
+    return RepositoryService.setRepoExcludedFiles(repoId, exclusions);
+  }
+
   static async setRepoFilter(repoId: string, filter: string): Promise<AppSettings> {
     return RepositoryService.setRepoFilter(repoId, filter);
   }
@@ -248,7 +354,7 @@ export class GitService {
     return BranchService.getBranches(includeRemote, repoPath);
   }
 
-  static async getCurrentBranch(repoPath?: string): Promise<string> {
+  static async getCurrentBranch(repoPath?: string): Promise<CurrentBranch> {
     return BranchService.getCurrentBranch(repoPath);
   }
 
@@ -393,8 +499,13 @@ export class GitService {
     return CommitService.getCommitChangedFiles(commitHash, repoPath);
   }
 
-  static async getCommitFileDiff(commitHash: string, filePath: string, repoPath?: string): Promise<GitCommandResult> {
-    return CommitService.getCommitFileDiff(commitHash, filePath, repoPath);
+  static async getCommitFileDiff(
+    commitHash: string,
+    filePath: string,
+    repoPath?: string,
+    encoding?: string,
+  ): Promise<GitCommandResult> {
+    return CommitService.getCommitFileDiff(commitHash, filePath, repoPath, encoding);
   }
 
   static async getCommitDiff(
@@ -402,8 +513,9 @@ export class GitService {
     repoPath?: string,
     filePath?: string,
     encoding?: string,
+    maxLines?: number,
   ): Promise<CommitDiff> {
-    return CommitService.getCommitDiff(commitHash, repoPath, filePath, encoding);
+    return CommitService.getCommitDiff(commitHash, repoPath, filePath, encoding, maxLines);
   }
 
   static async getFileAtCommit(
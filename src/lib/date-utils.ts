@@ -0,0 +1,20 @@
+import type { DateFormat } from "./GitService";
+
+let configuredFormat: DateFormat | null = null;
+
+/** Remember the user's `date_format` setting whenever settings are loaded or saved. */
+export function setCommitDateFormat(format: DateFormat | null | undefined) {
+  configuredFormat = format ?? null;
+}
+
+/**
+ * Format a commit date from the backend for display. With a `DateFormat`
+ * configured the backend has already formatted the date and it is shown
+ * as-is; otherwise it is the view's own ISO date, rendered with `options`.
+ */
+export function formatCommitDate(raw: string, options?: Intl.DateTimeFormatOptions): string {
+  if (!raw || configuredFormat) return raw ?? "";
+  const parsed = new Date(raw);
+  if (Number.isNaN(parsed.getTime())) return raw;
+  return options ? parsed.toLocaleString(undefined, options) : parsed.toLocaleString();
+}
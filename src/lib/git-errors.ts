@@ -1,3 +1,5 @@
+import type { FileContent } from "./types";
+
 export function getAuthRequiredMessage(raw: string): string | null {
   const text = raw.toLowerCase();
   const isAuthError =
@@ -13,3 +15,45 @@ export function getAuthRequiredMessage(raw: string): string | null {
   if (!isAuthError) return null;
   return "Authentication required. Git credentials are needed for this operation.";
 }
+
+/** Thrown when a file or diff is over the size limit from the settings. */
+export class FileTooLargeError extends Error {
+  readonly size: number;
+
+  constructor(size: number) {
+    super(`File is too large to display (${(size / (1024 * 1024)).toFixed(1)} MB)`);
+    this.name = "FileTooLargeError";
+    this.size = size;
+  }
+}
+
+/** The text of a file content response, throwing when it was too large to load. */
+export function unwrapFileContent(result: FileContent): string {
+  if (result.tooLarge) throw new FileTooLargeError(result.size);
+  return result.content;
+}
+
+type CommandErrorPayload = { type: string; message?: unknown };
+
+export function formatCommandError(error: unknown): string {
+  if (error && typeof error === "object" && "type" in error) {
+    const { type, message } = error as CommandErrorPayload;
+    if (type === "Timeout") return `Git command timed out after ${message} seconds`;
+    if (type === "NotARepo") return `Not a git repository: ${message}`;
+    if (type === "InvalidRepoPath") return `Invalid repository path: ${message}`;
+    if (message !== undefined && message !== null) return String(message);
+    return type;
+  }
+  if (error instanceof Error) return error.message;
+  return String(error);
+}
+
+/** True when a commit failed because user.name/user.email are not configured. */
+export function isMissingIdentityError(error: unknown): boolean {
+  return (
+    !!error &&
+    typeof error === "object" &&
+    "type" in error &&
+    (error as CommandErrorPayload).type === "MissingIdentity"
+  );
+}
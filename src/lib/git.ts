@@ -5,6 +5,8 @@ export interface GitResponse {
   stderr: string;
   exit_code: number;
   duration_ms: number;
+  /** stdout was cut off at the command's output cap. */
+  truncated?: boolean;
 }
 
 export type GitError =
@@ -16,6 +16,8 @@ export interface RebaseTodoItem {
   action: "pick" | "reword" | "edit" | "squash" | "fixup" | "drop";
   hash: string;
   message: string;
+  /** New message for `reword`, or the combined message on the last `squash`. */
+  newMessage?: string;
 }
 
 export interface FullRebaseStatus {
@@ -227,6 +229,20 @@ function createRebaseStore() {
         return undefined;
       }
     },
+    startRebaseOnto: async (newBase: string, upstream: string, branch: string | undefined, repoPath: string) => {
+      update(s => ({ ...s, repoPath, status: "inProgress" }));
+      try {
+        const res: GitCommandResult = await invoke("cmd_rebase_onto", { newBase, upstream, branch, repoPath });
+        await checkRebaseStateAfterCommand(res, repoPath, "Rebase");
+        return res;
+      } catch (e: any) {
+        const errMsg = typeof e === "string" ? e : e?.message || "Unknown error";
+        toast.error(`Rebase failed: ${errMsg}`);
+        update(s => ({ ...s, repoPath, status: "idle" }));
+        stopPolling();
+        return undefined;
+      }
+    },
     prepareInteractive: async (baseCommit: string, repoPath: string) => {
       update(s => ({ ...s, repoPath, baseCommit, status: "editingTodo" }));
       try {
@@ -340,6 +356,18 @@ function createRebaseStore() {
     },
     cancelEditing: () => {
       update(s => ({ ...s, status: "idle", todoItems: [], baseCommit: null }));
+    },
+    getRemainingTodo: async (repoPath: string): Promise<RebaseTodoItem[]> => {
+      return invoke("cmd_get_rebase_todo", { repoPath });
+    },
+    setRemainingTodo: async (items: RebaseTodoItem[], repoPath: string) => {
+      try {
+        await invoke("cmd_set_rebase_todo", { items, repoPath });
+      } catch (e: any) {
+        const errMsg = typeof e === "string" ? e : e?.message || "Unknown error";
+        toast.error(`Failed to update rebase plan: ${errMsg}`);
+        throw e;
+      }
     }
   };
 }
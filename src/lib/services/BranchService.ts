@@ -1,5 +1,21 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { GitCommandResult } from "../types";
+import type {
+  BranchInfo,
+  BranchSort,
+  BundleInfo,
+  CheckoutCommitResult,
+  CommitParents,
+  CurrentBranch,
+  DiffFile,
+  GitCommandResult,
+  GraphCommit,
+  GraphRow,
+  HeadInfo,
+  MergeCheckResult,
+  MergeDryRunResult,
+  ResetDryRunResult,
+  UpdateCheck,
+} from "../types";
 import { executeGitCommand } from "./command-executor";
 
 type CheckoutMode = "switch" | "checkout";
@@ -10,14 +26,40 @@ export class BranchService {
     return invoke("cmd_get_git_branches", { includeRemote, repoPath });
   }
 
-  static async getCurrentBranch(repoPath?: string): Promise<string> {
+  static async getBranchesDetailed(
+    includeRemote = false,
+    repoPath?: string,
+    options?: { sort?: BranchSort; filter?: string },
+  ): Promise<BranchInfo[]> {
+    return invoke("cmd_get_branches_detailed", {
+      includeRemote,
+      sort: options?.sort,
+      filter: options?.filter,
+      repoPath,
+    });
+  }
+
+  static async getCurrentBranch(repoPath?: string): Promise<CurrentBranch> {
     return invoke("cmd_get_current_branch", { repoPath });
   }
 
+  static async getHeadInfo(repoPath?: string): Promise<HeadInfo> {
+    return invoke("cmd_get_head_info", { repoPath });
+  }
+
   static async getBranchTip(branchName: string, repoPath?: string): Promise<string> {
     return invoke("cmd_get_branch_tip", { branchName, repoPath });
   }
 
+  /** `null` when the two refs share no history. */
+  static async getMergeBase(a: string, b: string, repoPath?: string): Promise<string | null> {
+    return invoke("cmd_get_merge_base", { a, b, repoPath });
+  }
+
+  static async isAncestor(a: string, b: string, repoPath?: string): Promise<boolean> {
+    return invoke("cmd_is_ancestor", { a, b, repoPath });
+  }
+
   static async switchBranch(branchName: string, repoPath?: string): Promise<GitCommandResult> {
     return BranchService.checkoutBranch("switch", branchName, repoPath);
   }
@@ -26,6 +68,26 @@ export class BranchService {
     return BranchService.checkoutBranch("checkout", branchName, repoPath);
   }
 
+  static async checkoutCommit(commitHash: string, repoPath?: string): Promise<CheckoutCommitResult> {
+    return executeGitCommand(
+      "cmd_git_checkout_commit",
+      { commitHash, repoPath },
+      `Checked out ${commitHash.slice(0, 7)} (detached HEAD)`,
+      "Checkout failed",
+      { reloadGraph: true },
+    ) as Promise<CheckoutCommitResult>;
+  }
+
+  static async reattach(branchName: string, repoPath?: string): Promise<GitCommandResult> {
+    return executeGitCommand(
+      "cmd_git_reattach",
+      { branchName, repoPath },
+      `Switched to '${branchName}'`,
+      "Switch failed",
+      { reloadGraph: true },
+    );
+  }
+
   static async checkoutNew(name: string, startPoint: string, repoPath?: string): Promise<GitCommandResult> {
     return executeGitCommand(
       "cmd_git_checkout_new_branch",
@@ -49,16 +111,62 @@ export class BranchService {
     return invoke("cmd_get_commit_graph", { limit, repoPath });
   }
 
-  static async merge(branch: string, repoPath?: string): Promise<GitCommandResult> {
+  static async getCommitGraphStructured(limit: number, repoPath?: string): Promise<GraphCommit[]> {
+    return invoke("cmd_get_commit_graph_structured", { limit, repoPath });
+  }
+
+  static async getCommitGraphLanes(limit: number, repoPath?: string): Promise<GraphRow[]> {
+    return invoke("cmd_get_commit_graph_lanes", { limit, repoPath });
+  }
+
+  static async getCommitParents(
+    commitHash: string,
+    includeChildren = false,
+    repoPath?: string,
+  ): Promise<CommitParents> {
+    return invoke("cmd_get_commit_parents", { commitHash, includeChildren, repoPath });
+  }
+
+  static async merge(
+    branch: string,
+    repoPath?: string,
+    options?: { noFf?: boolean; squash?: boolean; message?: string },
+  ): Promise<GitCommandResult> {
     return executeGitCommand(
       "cmd_git_merge",
-      { branch, repoPath },
-      `Merged '${branch}'`,
+      {
+        branch,
+        noFf: options?.noFf,
+        squash: options?.squash,
+        message: options?.message,
+        repoPath,
+      },
+      options?.squash
+        ? `Squashed '${branch}' — changes are staged and ready to commit`
+        : `Merged '${branch}'`,
       "Merge failed",
       { reloadGraph: true },
     );
   }
 
+  static async mergeDryRun(branch: string, repoPath?: string): Promise<MergeDryRunResult> {
+    return invoke("cmd_git_merge_dry_run", { branch, repoPath });
+  }
+
+  static async canMergeCleanly(branch: string, repoPath?: string): Promise<MergeCheckResult> {
+    return invoke("cmd_can_merge_cleanly", { branch, repoPath });
+  }
+
+  static async mergeAbort(repoPath?: string): Promise<GitCommandResult> {
+    return executeGitCommand(
+      "cmd_git_merge_abort",
+      { repoPath },
+      "Merge aborted",
+      "Merge abort failed",
+      { reloadGraph: true },
+    );
+  }
+
   static async rebase(branch: string, repoPath?: string): Promise<GitCommandResult> {
     return executeGitCommand(
       "cmd_git_rebase",
@@ -69,6 +177,10 @@ export class BranchService {
     );
   }
 
+  static async rebaseDryRun(branch: string, repoPath?: string): Promise<MergeDryRunResult> {
+    return invoke("cmd_git_rebase_dry_run", { branch, repoPath });
+  }
+
   static async cherryPick(commitHash: string, repoPath?: string): Promise<GitCommandResult> {
     return executeGitCommand(
       "cmd_git_cherry_pick",
@@ -79,6 +191,21 @@ export class BranchService {
     );
   }
 
+  /** Picks `from..to`; `from` must be an ancestor of `to` and is itself excluded. */
+  static async cherryPickRange(from: string, to: string, repoPath?: string): Promise<GitCommandResult> {
+    return executeGitCommand(
+      "cmd_cherry_pick_range",
+      { from, to, repoPath },
+      `Cherry-picked ${from.slice(0, 8)}..${to.slice(0, 8)}`,
+      "Cherry-pick failed",
+      { reloadGraph: true },
+    );
+  }
+
+  static async cherryPickDryRun(commitHash: string, repoPath?: string): Promise<MergeDryRunResult> {
+    return invoke("cmd_git_cherry_pick_dry_run", { commitHash, repoPath });
+  }
+
   static async abortOperation(repoPath?: string): Promise<GitCommandResult> {
     return executeGitCommand(
       "cmd_abort_operation",
@@ -95,6 +222,10 @@ export class BranchService {
     });
   }
 
+  static async checkForUpdates(repoPath?: string): Promise<UpdateCheck> {
+    return invoke("cmd_check_for_updates", { repoPath });
+  }
+
   static async pull(repoPath?: string): Promise<GitCommandResult> {
     return executeGitCommand("cmd_git_pull", { repoPath }, "Pull completed", "Pull failed", {
       reloadGraph: true,
@@ -127,6 +258,10 @@ export class BranchService {
     );
   }
 
+  static async resetDryRun(commitHash: string, mode: ResetMode, repoPath?: string): Promise<ResetDryRunResult> {
+    return invoke("cmd_git_reset_dry_run", { commitHash, mode, repoPath });
+  }
+
   static async createTag(
     tagName: string,
     commitHash: string,
@@ -153,6 +288,21 @@ export class BranchService {
     );
   }
 
+  /** Local branches merged into `into` (default: current), excluding current and main/master/develop. */
+  static async getMergedBranches(into?: string, repoPath?: string): Promise<string[]> {
+    return invoke("cmd_get_merged_branches", { into, repoPath });
+  }
+
+  static async deleteBranches(names: string[], repoPath?: string): Promise<GitCommandResult> {
+    return executeGitCommand(
+      "cmd_delete_branches",
+      { names, repoPath },
+      `Deleted ${names.length} branch${names.length === 1 ? "" : "es"}`,
+      "Delete branches failed",
+      { reloadGraph: true },
+    );
+  }
+
   static async deleteRemoteBranch(
     remote: string,
     branchName: string,
@@ -167,6 +317,11 @@ export class BranchService {
     );
   }
 
+  /** Drop remote-tracking branches that are gone from `remote`; returns the pruned refs. */
+  static async pruneRemote(remote: string, repoPath?: string): Promise<string[]> {
+    return invoke("cmd_prune_remote", { remote, repoPath });
+  }
+
   static async renameBranch(oldName: string, newName: string, repoPath?: string): Promise<GitCommandResult> {
     return executeGitCommand(
       "cmd_git_rename_branch",
@@ -191,6 +346,15 @@ export class BranchService {
     return invoke("cmd_create_patch_from_commit", { commitHash, repoPath });
   }
 
+  static async formatPatch(from: string, outputDir: string, repoPath?: string, to?: string): Promise<string[]> {
+    return invoke("cmd_format_patch", { from, to, outputDir, repoPath });
+  }
+
+  /** Bundles every ref when `refs` is empty. */
+  static async createBundle(outputPath: string, refs: string[] = [], repoPath?: string): Promise<BundleInfo> {
+    return invoke("cmd_git_bundle_create", { outputPath, refs, repoPath });
+  }
+
   static async applyStash(commitHash: string, repoPath?: string): Promise<GitCommandResult> {
     return executeGitCommand(
       "cmd_git_apply_stash",
@@ -239,6 +403,18 @@ export class BranchService {
     return invoke("cmd_create_patch_from_stash", { commitHash, repoPath });
   }
 
+  static async stashShow(stashRef: string, repoPath?: string, encoding?: string): Promise<DiffFile[]> {
+    return invoke("cmd_git_stash_show", { stashRef, repoPath, encoding });
+  }
+
+  static async diffWorkingVsStash(stashRef: string, repoPath?: string, encoding?: string): Promise<DiffFile[]> {
+    return invoke("cmd_diff_working_vs_stash", { stashRef, repoPath, encoding });
+  }
+
+  static async getStashCount(repoPath?: string): Promise<number> {
+    return invoke("cmd_get_stash_count", { repoPath });
+  }
+
   private static async checkoutBranch(
     mode: CheckoutMode,
     branchName: string,
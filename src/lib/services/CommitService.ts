@@ -1,6 +1,23 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { CommitChangedFile } from "../GitService";
-import type { BlameLine, CommitDiff, FileCommit, GitCommandResult } from "../types";
+import type { CommitChangedFile, LfsPointerInfo } from "../GitService";
+import type {
+  BlameLine,
+  BlameLineInfo,
+  CommitCount,
+  CommitDiff,
+  CommitRefs,
+  CommitSignature,
+  Contributor,
+  DiffFile,
+  DirectoryEntry,
+  FileCommit,
+  FileContent,
+  GitCommandResult,
+  GitObject,
+  GraphCommit,
+  TreeNode,
+} from "../types";
+import { unwrapFileContent } from "../git-errors";
 import { executeGitCommand } from "./command-executor";
 
 export class CommitService {
@@ -12,28 +29,81 @@ export class CommitService {
     return invoke("cmd_get_default_ai_prompt");
   }
 
-  static async commit(message: string, repoPath?: string): Promise<GitCommandResult> {
+  static async commit(message: string, repoPath?: string, runHooks?: boolean): Promise<GitCommandResult> {
     return executeGitCommand(
       "cmd_git_commit",
-      { message, repoPath },
+      { message, runHooks, repoPath },
       "Commit successful",
       "Commit failed",
       { reloadGraph: true },
     );
   }
 
+  static async getCommitTemplate(repoPath?: string, includeComments?: boolean): Promise<string | null> {
+    return invoke("cmd_get_commit_template", { includeComments, repoPath });
+  }
+
+  static async getRecentCommitMessages(limit: number, repoPath?: string): Promise<string[]> {
+    return invoke("cmd_get_recent_commit_messages", { limit, repoPath });
+  }
+
+  static async amendStaged(repoPath?: string): Promise<GitCommandResult> {
+    return executeGitCommand(
+      "cmd_git_amend_staged",
+      { repoPath },
+      "Amended last commit",
+      "Amend failed",
+      { reloadGraph: true },
+    );
+  }
+
+  static async describe(repoPath?: string): Promise<string> {
+    return invoke("cmd_git_describe", { repoPath });
+  }
+
   static async getPendingCommitsCount(repoPath?: string): Promise<number> {
     return invoke("cmd_get_pending_commits_count", { repoPath });
   }
 
-  static async getFileHistory(filePath: string, limit = 100, repoPath?: string): Promise<FileCommit[]> {
-    return invoke("cmd_get_file_history", { filePath, limit, repoPath });
+  static async getUnpushedCommits(repoPath?: string): Promise<GraphCommit[]> {
+    return invoke("cmd_get_unpushed_commits", { repoPath });
+  }
+
+  static async getFileHistory(
+    filePath: string,
+    limit = 100,
+    repoPath?: string,
+    withPatch?: boolean,
+  ): Promise<FileCommit[]> {
+    return invoke("cmd_get_file_history", { filePath, limit, withPatch, repoPath });
+  }
+
+  static async getLineHistory(
+    filePath: string,
+    startLine: number,
+    endLine: number,
+    repoPath?: string,
+    encoding?: string,
+  ): Promise<FileCommit[]> {
+    return invoke("cmd_get_line_history", { filePath, startLine, endLine, encoding, repoPath });
+  }
+
+  static async searchCommitsByContent(term: string, regex = false, repoPath?: string): Promise<GraphCommit[]> {
+    return invoke("cmd_git_log_pickaxe", { term, regex, repoPath });
   }
 
   static async searchRepoFiles(pattern?: string, repoPath?: string): Promise<string[]> {
     return invoke("cmd_search_repo_files", { pattern, repoPath });
   }
 
+  static async getTrackedFileTree(repoPath?: string): Promise<TreeNode[]> {
+    return invoke("cmd_get_tracked_file_tree", { repoPath });
+  }
+
+  static async getDirectoryContents(dirPath: string, commit?: string, repoPath?: string): Promise<DirectoryEntry[]> {
+    return invoke("cmd_get_directory_contents", { dirPath, commit, repoPath });
+  }
+
   static async getCommitChangedFiles(commitHash: string, repoPath?: string): Promise<CommitChangedFile[]> {
     try {
       return await invoke<CommitChangedFile[]>("cmd_get_commit_changed_files", { commitHash, repoPath });
@@ -43,8 +113,30 @@ export class CommitService {
     }
   }
 
-  static async getCommitFileDiff(commitHash: string, filePath: string, repoPath?: string): Promise<GitCommandResult> {
-    return invoke("cmd_get_commit_file_diff", { commitHash, filePath, repoPath });
+  static async getCommitFileDiff(
+    commitHash: string,
+    filePath: string,
+    repoPath?: string,
+    encoding?: string,
+  ): Promise<GitCommandResult> {
+    return invoke("cmd_get_commit_file_diff", { commitHash, filePath, repoPath, encoding });
+  }
+
+  static async getCommitFileDiffParsed(
+    commitHash: string,
+    filePath: string,
+    repoPath?: string,
+    encoding?: string,
+  ): Promise<DiffFile> {
+    return invoke("cmd_get_commit_file_diff_parsed", { commitHash, filePath, repoPath, encoding });
+  }
+
+  static async getRefsForCommit(commitHash: string, repoPath?: string): Promise<CommitRefs> {
+    return invoke("cmd_get_refs_for_commit", { commitHash, repoPath });
+  }
+
+  static async verifyCommitSignature(commitHash: string, repoPath?: string): Promise<CommitSignature> {
+    return invoke("cmd_verify_commit_signature", { commitHash, repoPath });
   }
 
   static async getCommitDiff(
@@ -52,8 +144,9 @@ export class CommitService {
     repoPath?: string,
     filePath?: string,
     encoding?: string,
+    maxLines?: number,
   ): Promise<CommitDiff> {
-    return invoke("cmd_get_commit_diff", { commitHash, filePath, repoPath, encoding });
+    return invoke("cmd_get_commit_diff", { commitHash, filePath, repoPath, encoding, maxLines });
   }
 
   static async getFileAtCommit(
@@ -62,10 +155,42 @@ export class CommitService {
     repoPath?: string,
     encoding?: string,
   ): Promise<string> {
-    return invoke("cmd_get_file_at_commit", { commitHash, filePath, repoPath, encoding });
+    return unwrapFileContent(
+      await invoke<FileContent>("cmd_get_file_at_commit", { commitHash, filePath, repoPath, encoding }),
+    );
+  }
+
+  /** Low-level inspector: pretty-prints any object by SHA or revision. */
+  static async catFile(object: string, repoPath?: string, encoding?: string): Promise<GitObject> {
+    return invoke("cmd_cat_file", { object, repoPath, encoding });
+  }
+
+  static async getLfsPointerInfo(
+    filePath: string,
+    commitHash?: string,
+    repoPath?: string,
+  ): Promise<LfsPointerInfo> {
+    return invoke("cmd_get_lfs_pointer_info", { filePath, commitHash, repoPath });
+  }
+
+  static async getCommitCount(branch?: string, repoPath?: string): Promise<CommitCount> {
+    return invoke("cmd_get_commit_count", { branch, repoPath });
+  }
+
+  /** `since` is any date git understands, e.g. "2024-01-01" or "3 months ago". */
+  static async getContributors(since?: string, repoPath?: string): Promise<Contributor[]> {
+    return invoke("cmd_get_contributors", { since, repoPath });
   }
 
   static async getBlame(filePath: string, repoPath?: string): Promise<BlameLine[]> {
     return invoke("cmd_git_blame", { filePath, repoPath });
   }
+
+  static async getBlameForLine(
+    filePath: string,
+    lineNumber: number,
+    repoPath?: string,
+  ): Promise<BlameLineInfo> {
+    return invoke("cmd_blame_line", { filePath, lineNumber, repoPath });
+  }
 }
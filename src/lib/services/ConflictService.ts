@@ -1,5 +1,12 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { ConflictFile, GitOperationState } from "../GitService";
+import type {
+  AbortedOperation,
+  ConflictFile,
+  GitOperationState,
+  MergeRegion,
+  RepoState,
+  ResolveConflictResult,
+} from "../GitService";
 
 export class ConflictService {
   static async getConflicts(repoPath?: string): Promise<string[]> {
@@ -10,6 +17,10 @@ export class ConflictService {
     return invoke("cmd_get_conflict_file", { path, encoding, repoPath });
   }
 
+  static async getConflictMerged(path: string, repoPath?: string, encoding?: string): Promise<MergeRegion[]> {
+    return invoke("cmd_get_conflict_merged", { path, encoding, repoPath });
+  }
+
   static async resolveOurs(path: string, repoPath?: string): Promise<void> {
     return invoke("cmd_resolve_ours", { path, repoPath });
   }
@@ -26,6 +37,15 @@ export class ConflictService {
     return invoke("cmd_write_file", { path, content, encoding, repoPath });
   }
 
+  static async resolveWithContent(
+    path: string,
+    content: string,
+    repoPath?: string,
+    encoding?: string,
+  ): Promise<ResolveConflictResult> {
+    return invoke("cmd_resolve_conflict_with_content", { path, content, encoding, repoPath });
+  }
+
   static async checkConflictState(repoPath?: string): Promise<boolean> {
     return invoke("cmd_check_conflict_state", { repoPath });
   }
@@ -33,4 +53,13 @@ export class ConflictService {
   static async getOperationState(repoPath?: string): Promise<GitOperationState> {
     return invoke("cmd_get_operation_state", { repoPath });
   }
+
+  static async getRepoState(repoPath?: string): Promise<RepoState> {
+    return invoke("cmd_get_repo_state", { repoPath });
+  }
+
+  /** Abort whatever is in progress (merge, rebase, cherry-pick, revert or bisect). */
+  static async abortCurrentOperation(repoPath?: string): Promise<AbortedOperation> {
+    return invoke("cmd_abort_current_operation", { repoPath });
+  }
 }
@@ -1,6 +1,10 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import type { DiffStageLineTarget } from "../diff";
-import type { FileStatus } from "../GitService";
+import type { ChangeSummary, FileStatus } from "../GitService";
+import type { DiffChunkEvent, DiffContext, DiffDoneEvent, DiffHunk, DiscardResult, FileContent } from "../types";
+import { unwrapFileContent } from "../git-errors";
+import { toast } from "../toast.svelte";
 import { executeCommand } from "./command-executor";
 
 export class FileService {
@@ -8,8 +12,39 @@ export class FileService {
     return invoke("cmd_get_status_files", { repoPath });
   }
 
+  static async getChangeSummary(repoPath?: string): Promise<ChangeSummary> {
+    return invoke("cmd_get_change_summary", { repoPath });
+  }
+
   static async getDiff(filePath: string, staged: boolean, repoPath?: string, encoding?: string): Promise<string> {
-    return invoke("cmd_get_diff_file", { filePath, staged, repoPath, encoding });
+    return unwrapFileContent(
+      await invoke<FileContent>("cmd_get_diff_file", { filePath, staged, repoPath, encoding }),
+    );
+  }
+
+  /** Parsed diff delivered hunk by hunk to `onHunk`, for very large diffs. */
+  static async getDiffStream(
+    filePath: string,
+    staged: boolean,
+    onHunk: (hunk: DiffHunk, index: number) => void,
+    repoPath?: string,
+    encoding?: string,
+  ): Promise<DiffDoneEvent> {
+    const streamId = crypto.randomUUID();
+    const unlisten = await listen<DiffChunkEvent>("diff-chunk", (event) => {
+      if (event.payload.streamId === streamId) onHunk(event.payload.hunk, event.payload.index);
+    });
+    try {
+      return await invoke<DiffDoneEvent>("cmd_get_diff_file_stream", {
+        streamId,
+        filePath,
+        staged,
+        encoding,
+        repoPath,
+      });
+    } finally {
+      unlisten();
+    }
   }
 
   static async getFileBaseContent(
@@ -18,7 +53,9 @@ export class FileService {
     repoPath?: string,
     encoding?: string,
   ): Promise<string> {
-    return invoke("cmd_get_file_base_content", { filePath, staged, repoPath, encoding });
+    return unwrapFileContent(
+      await invoke<FileContent>("cmd_get_file_base_content", { filePath, staged, repoPath, encoding }),
+    );
   }
 
   static async getFileModifiedContent(
@@ -27,13 +64,36 @@ export class FileService {
     repoPath?: string,
     encoding?: string,
   ): Promise<string> {
-    return invoke("cmd_get_file_modified_content", { filePath, staged, repoPath, encoding });
+    return unwrapFileContent(
+      await invoke<FileContent>("cmd_get_file_modified_content", { filePath, staged, repoPath, encoding }),
+    );
+  }
+
+  static async getDiffContext(
+    filePath: string,
+    aroundLine: number,
+    contextLines: number,
+    commit?: string,
+    repoPath?: string,
+    encoding?: string,
+  ): Promise<DiffContext> {
+    return invoke("cmd_get_diff_context", { filePath, commit, aroundLine, contextLines, repoPath, encoding });
   }
 
   static async stageFile(path: string, repoPath?: string): Promise<void> {
     await executeCommand<void>("cmd_git_add", { path, repoPath }, `Staged ${path}`, "Stage failed");
   }
 
+  /** Restores and stages `filePath` as it was at `commitHash`. */
+  static async restoreFileFromCommit(filePath: string, commitHash: string, repoPath?: string): Promise<void> {
+    await executeCommand<void>(
+      "cmd_restore_file_from_commit",
+      { filePath, commitHash, repoPath },
+      `Restored ${filePath} from ${commitHash.slice(0, 8)}`,
+      "Restore failed",
+    );
+  }
+
   static async unstageFile(path: string, repoPath?: string): Promise<void> {
     await executeCommand<void>(
       "cmd_git_unstage",
@@ -43,6 +103,10 @@ export class FileService {
     );
   }
 
+  static async moveFile(from: string, to: string, repoPath?: string): Promise<void> {
+    await executeCommand<void>("cmd_git_mv", { from, to, repoPath }, `Moved ${from} to ${to}`, "Move failed");
+  }
+
   static async stageAll(repoPath?: string): Promise<void> {
     await executeCommand<void>("cmd_git_add_all", { repoPath }, "Staged all files", "Stage all failed");
   }
@@ -74,18 +138,51 @@ export class FileService {
     );
   }
 
-  static async discardChanges(files: FileStatus[], repoPath?: string): Promise<void> {
+  static async applyPatch(
+    patchContent: string,
+    repoPath?: string,
+    options?: { cached?: boolean; reverse?: boolean },
+  ): Promise<void> {
+    await executeCommand<void>(
+      "cmd_git_apply_patch",
+      {
+        patchContent,
+        cached: options?.cached ?? false,
+        reverse: options?.reverse ?? false,
+        repoPath,
+      },
+      "Applied patch",
+      "Apply patch failed",
+    );
+  }
+
+  /** Token for one destructive operation on exactly `paths`, required in safe mode. */
+  static async prepareDestructive(
+    operation: "discard_changes" | "reset_hard" | "clean",
+    paths: string[],
+  ): Promise<string> {
+    return invoke("cmd_prepare_destructive", { operation, paths });
+  }
+
+  static async discardChanges(
+    files: FileStatus[],
+    repoPath?: string,
+    options?: { confirmationToken?: string; cleanPaths?: string[]; cleanConfirmationToken?: string },
+  ): Promise<void> {
     if (files.length === 0) {
       return;
     }
 
     const successMessage = files.length === 1 ? `Discarded changes in ${files[0].path}` : "Discarded all changes";
-    await executeCommand<void>(
+    const result = await executeCommand<DiscardResult>(
       "cmd_git_discard_changes",
-      { files, repoPath },
+      { files, repoPath, ...options },
       successMessage,
       "Discard failed",
     );
+    if (result.backupWarning) {
+      toast.info(result.backupWarning, 8000);
+    }
   }
 
   static async stashFile(file: FileStatus, repoPath?: string): Promise<void> {
@@ -98,10 +195,28 @@ export class FileService {
     );
   }
 
-  static async stashAll(repoPath?: string): Promise<void> {
+  static async stashFiles(files: FileStatus[], message?: string, repoPath?: string): Promise<void> {
+    await executeCommand<void>(
+      "cmd_git_stash_files",
+      { files, message, repoPath },
+      `Stashed ${files.length} file${files.length === 1 ? "" : "s"}`,
+      "Stash failed",
+      { reloadGraph: true },
+    );
+  }
+
+  static async stashAll(
+    repoPath?: string,
+    options?: { includeUntracked?: boolean; keepIndex?: boolean; message?: string },
+  ): Promise<void> {
     await executeCommand<void>(
       "cmd_git_stash_all",
-      { repoPath },
+      {
+        includeUntracked: options?.includeUntracked,
+        keepIndex: options?.keepIndex,
+        message: options?.message,
+        repoPath,
+      },
       "Stashed all changes",
       "Stash all failed",
       { reloadGraph: true },
@@ -121,6 +236,20 @@ export class FileService {
     );
   }
 
+  /** `.git/info/exclude`: ignore rules that stay local and are never committed. */
+  static async getLocalExclude(repoPath?: string): Promise<string> {
+    return invoke("cmd_get_local_exclude", { repoPath });
+  }
+
+  static async setLocalExclude(content: string, repoPath?: string): Promise<void> {
+    await executeCommand<void>(
+      "cmd_set_local_exclude",
+      { content, repoPath },
+      "Saved local exclude rules",
+      "Save local exclude failed",
+    );
+  }
+
   static async openInDiffTool(filePath: string, staged: boolean, repoPath?: string): Promise<void> {
     await executeCommand<void>(
       "cmd_open_in_diff_tool",
@@ -1,5 +1,18 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { AppSettings, RepoEntry } from "../GitService";
+import type {
+  AppSettings,
+  ConfigScope,
+  DateFormat,
+  GitTimeouts,
+  HookInfo,
+  LfsStatus,
+  OperationLogEntry,
+  RepoEntry,
+  SettingsImport,
+} from "../GitService";
+import type { FsckResult, GcResult, GitCommandResult, RepoSize } from "../types";
+import { executeGitCommand } from "./command-executor";
+import { setCommitDateFormat } from "../date-utils";
 
 export class RepositoryService {
   static async getSettings(): Promise<AppSettings> {
@@ -10,10 +23,49 @@ export class RepositoryService {
     return invoke("cmd_set_excluded_files", { exclusions });
   }
 
+  static async setRepoExcludedFiles(repoId: string, exclusions: string[]): Promise<AppSettings> {
+    return invoke("cmd_set_repo_excluded_files", { repoId, exclusions });
+  }
+
+  /** Current files the given patterns would hide, for previewing before saving. */
+  static async previewExclusions(patterns: string[], repoPath?: string): Promise<string[]> {
+    return invoke("cmd_preview_exclusions", { patterns, repoPath });
+  }
+
   static async setRepoFilter(repoId: string, filter: string): Promise<AppSettings> {
     return invoke("cmd_set_repo_filter", { repoId, filter });
   }
 
+  static async getOperationLog(limit?: number): Promise<OperationLogEntry[]> {
+    return invoke("cmd_get_operation_log", { limit });
+  }
+
+  static async setTimeouts(timeouts: GitTimeouts): Promise<AppSettings> {
+    return invoke("cmd_set_timeouts", { timeouts });
+  }
+
+  /** `null` restores each view's own date format. */
+  static async setDateFormat(dateFormat: DateFormat | null): Promise<AppSettings> {
+    const settings = await invoke<AppSettings>("cmd_set_date_format", { dateFormat });
+    setCommitDateFormat(settings.date_format);
+    return settings;
+  }
+
+  /** `null` restores the default limit. */
+  static async setMaxFileViewBytes(maxBytes: number | null): Promise<AppSettings> {
+    return invoke("cmd_set_max_file_view_bytes", { maxBytes });
+  }
+
+  /** Writes the settings, minus the Gemini token, to a JSON file. */
+  static async exportSettings(path: string): Promise<void> {
+    return invoke("cmd_export_settings", { path });
+  }
+
+  /** Merges an exported file into the current settings; repos missing on this machine are skipped. */
+  static async importSettings(path: string): Promise<SettingsImport> {
+    return invoke("cmd_import_settings", { path });
+  }
+
   static async setGeminiApiToken(token: string): Promise<AppSettings> {
     return invoke("cmd_set_gemini_api_token", { token });
   }
@@ -58,4 +110,101 @@ export class RepositoryService {
   static async setRepoCommitPrompt(repoPath: string, prompt: string): Promise<AppSettings> {
     return invoke("cmd_set_repo_commit_prompt", { repoPath, prompt });
   }
+
+  static async getConfig(scope: ConfigScope, repoPath?: string): Promise<Record<string, string>> {
+    return invoke("cmd_get_config", { scope, repoPath });
+  }
+
+  static async setConfig(key: string, value: string, scope: ConfigScope, repoPath?: string): Promise<void> {
+    return invoke("cmd_set_config", { key, value, scope, repoPath });
+  }
+
+  static async getAliases(repoPath?: string): Promise<Record<string, string>> {
+    return invoke("cmd_get_aliases", { repoPath });
+  }
+
+  /** Shell (`!`) aliases are rejected by the backend. */
+  static async runAlias(alias: string, args: string[] = [], repoPath?: string): Promise<GitCommandResult> {
+    return executeGitCommand(
+      "cmd_run_alias",
+      { alias, args, repoPath },
+      `Ran alias '${alias}'`,
+      "Alias failed",
+    );
+  }
+
+  /** Slow on large repositories; progress arrives as `git-progress` events. */
+  static async fsck(repoPath?: string): Promise<FsckResult> {
+    return invoke("cmd_git_fsck", { repoPath });
+  }
+
+  static async gc(aggressive = false, repoPath?: string): Promise<GcResult> {
+    return invoke("cmd_git_gc", { aggressive, repoPath });
+  }
+
+  static async getRepoSize(repoPath?: string): Promise<RepoSize> {
+    return invoke("cmd_get_repo_size", { repoPath });
+  }
+
+  static async sparseCheckoutInit(cone = true, repoPath?: string): Promise<GitCommandResult> {
+    return executeGitCommand(
+      "cmd_sparse_checkout_init",
+      { cone, repoPath },
+      "Sparse checkout enabled",
+      "Sparse checkout failed",
+    );
+  }
+
+  static async sparseCheckoutSet(patterns: string[], repoPath?: string): Promise<GitCommandResult> {
+    return executeGitCommand(
+      "cmd_sparse_checkout_set",
+      { patterns, repoPath },
+      "Sparse checkout patterns updated",
+      "Sparse checkout failed",
+    );
+  }
+
+  /** Empty when the worktree isn't sparse. */
+  static async sparseCheckoutList(repoPath?: string): Promise<string[]> {
+    return invoke("cmd_sparse_checkout_list", { repoPath });
+  }
+
+  static async sparseCheckoutDisable(repoPath?: string): Promise<GitCommandResult> {
+    return executeGitCommand(
+      "cmd_sparse_checkout_disable",
+      { repoPath },
+      "Sparse checkout disabled",
+      "Sparse checkout failed",
+    );
+  }
+
+  static async listGitHooks(repoPath?: string): Promise<HookInfo[]> {
+    return invoke("cmd_list_git_hooks", { repoPath });
+  }
+
+  static async toggleGitHook(name: string, enable: boolean, repoPath?: string): Promise<void> {
+    return invoke("cmd_toggle_git_hook", { name, enable, repoPath });
+  }
+
+  static async lfsTrack(pattern: string, repoPath?: string): Promise<GitCommandResult> {
+    return executeGitCommand(
+      "cmd_git_lfs_track",
+      { pattern, repoPath },
+      `Tracking '${pattern}' with Git LFS`,
+      "LFS track failed",
+    );
+  }
+
+  static async lfsUntrack(pattern: string, repoPath?: string): Promise<GitCommandResult> {
+    return executeGitCommand(
+      "cmd_git_lfs_untrack",
+      { pattern, repoPath },
+      `Stopped tracking '${pattern}' with Git LFS`,
+      "LFS untrack failed",
+    );
+  }
+
+  static async getLfsStatus(repoPath?: string): Promise<LfsStatus> {
+    return invoke("cmd_git_lfs_status", { repoPath });
+  }
 }
@@ -1,3 +1,4 @@
+import { formatCommandError } from "../git-errors";
 import type { GitCommandResult } from "../types";
 import { invokeShared } from "./invoke-shared";
 
@@ -14,7 +15,7 @@ export async function executeCommand<T>(
 ): Promise<T> {
   return invokeShared<T>(command, params, {
     successToast: successMsg ? successMsg : false,
-    errorToast: (error) => `${errorMsg}: ${error}`,
+    errorToast: (error) => `${errorMsg}: ${formatCommandError(error)}`,
     reloadGraphOnSuccess: options?.reloadGraph,
   });
 }
@@ -30,7 +31,7 @@ export async function executeGitCommand(
     isSuccess: (result) => result.success,
     successToast: successMsg ? successMsg : false,
     failureToast: (result) => `${errorMsg}: ${result.stderr}`,
-    errorToast: (error) => `${errorMsg}: ${error}`,
+    errorToast: (error) => `${errorMsg}: ${formatCommandError(error)}`,
     reloadGraphOnSuccess: options?.reloadGraph,
   });
 }
@@ -22,17 +22,22 @@ export type GitCommandError =
   | { type: "commandFailed"; code: number | null; stderr: string }
   | { type: "io"; message: string };
 
+// Serialized by the backend with `rename_all = "camelCase"`
 export type GitCommandType =
-  | "Checkout"
-  | "Merge"
-  | "Rebase"
-  | "CherryPick"
-  | "Commit"
-  | "Pull"
-  | "Push"
-  | "Fetch"
-  | "Branch"
-  | "Other";
+  | "checkout"
+  | "merge"
+  | "rebase"
+  | "cherryPick"
+  | "commit"
+  | "pull"
+  | "push"
+  | "fetch"
+  | "branch"
+  | "stash"
+  | "tag"
+  | "reset"
+  | "revert"
+  | "other";
 
 export type GitCommandResult = {
   success: boolean;
@@ -42,11 +47,183 @@ export type GitCommandResult = {
   commandType: GitCommandType;
 };
 
+export type CheckoutCommitResult = GitCommandResult & {
+  detached: boolean;
+};
+
+/** Outcome of a merge, rebase or cherry-pick tried without keeping the result. */
+export interface MergeDryRunResult {
+  wouldConflict: boolean;
+  conflictingFiles: string[];
+}
+
+/** What a reset would leave behind; `discardedFiles` is only filled for hard resets. */
+export interface ResetDryRunResult {
+  commitsLeftBehind: number;
+  discardedFiles: string[];
+}
+
+/** Whether a branch merges into HEAD without conflicts. */
+export interface MergeCheckResult {
+  clean: boolean;
+  conflictingFiles: string[];
+}
+
 export interface FileCommit {
   hash: string;
   author: string;
   date: string;
   message: string;
+  patch: DiffFile | null;
+}
+
+export interface GraphCommit {
+  hash: string;
+  parents: string[];
+  refs: string[];
+  author: string;
+  date: string;
+  subject: string;
+}
+
+/** File or diff text, or only its size when it is over the viewer size limit. */
+export interface FileContent {
+  content: string;
+  tooLarge: boolean;
+  /** Size in bytes, before decoding. */
+  size: number;
+}
+
+export interface GitObject {
+  /** `blob`, `tree`, `commit` or `tag`. */
+  objectType: string;
+  content: FileContent;
+}
+
+/** A line from `fromLane` in one graph row to `toLane` in the next. */
+export interface GraphEdge {
+  fromLane: number;
+  toLane: number;
+}
+
+export interface GraphRow {
+  commit: GraphCommit;
+  lane: number;
+  edges: GraphEdge[];
+}
+
+export interface FsckResult {
+  healthy: boolean;
+  /** `<type> <sha>`, e.g. `commit 1a2b3c...`. */
+  dangling: string[];
+  errors: string[];
+}
+
+export interface GcResult {
+  sizeBefore: number;
+  sizeAfter: number;
+  /** Set when lock files suggested another git operation was running. */
+  warning: string | null;
+}
+
+export interface RepoSize {
+  gitDirBytes: number;
+  /** Everything outside `.git`, including untracked and ignored files. */
+  workingTreeBytes: number;
+  objectCount: number;
+  packCount: number;
+}
+
+export interface CurrentBranch {
+  branch: string | null;
+  detached: boolean;
+  /** Short hash of the detached HEAD. */
+  describe: string | null;
+}
+
+export interface HeadInfo {
+  /** `null` when HEAD is detached. */
+  branch: string | null;
+  detached: boolean;
+  /** Empty on an unborn branch. */
+  commit: string;
+  shortCommit: string;
+  upstream: string | null;
+  ahead: number;
+  behind: number;
+}
+
+export interface CommitRefs {
+  tags: string[];
+  branches: string[];
+  remoteBranches: string[];
+}
+
+export interface CommitSignature {
+  signed: boolean;
+  valid: boolean;
+  signer: string | null;
+  keyId: string | null;
+}
+
+export interface CommitParents {
+  hash: string;
+  parents: string[];
+  isMerge: boolean;
+  /** Only present when requested with `includeChildren`. */
+  children: string[] | null;
+}
+
+/** Where `discardChanges` backed up what it threw away. */
+export interface DiscardResult {
+  backupPatchPath: string | null;
+  backupUntrackedDir: string | null;
+  /** Why untracked files were discarded without a backup. */
+  backupWarning: string | null;
+}
+
+export interface BundleInfo {
+  path: string;
+  sizeBytes: number;
+}
+
+/** Order for `getBranchesDetailed`; the current branch is always listed first. */
+export type BranchSort = "name" | "commitDate" | "aheadBehind";
+
+export interface BranchInfo {
+  /** Remote branches are `remotes/<remote>/<branch>`, as in `getBranches`. */
+  name: string;
+  isCurrent: boolean;
+  upstream: string | null;
+  lastCommitHash: string;
+  /** ISO 8601 committer date. */
+  lastCommitDate: string;
+  lastCommitSubject: string;
+  ahead: number;
+  behind: number;
+  /** The upstream was deleted on the remote; the local branch is likely merged and safe to delete. */
+  upstreamGone: boolean;
+}
+
+/** Headline numbers for the repository overview panel. */
+export interface CommitCount {
+  totalCommits: number;
+  contributors: number;
+}
+
+export interface Contributor {
+  name: string;
+  email: string;
+  commitCount: number;
+}
+
+/** What the upstream gained, for the "new commits available" banner. */
+export interface UpdateCheck {
+  hasUpstream: boolean;
+  behind: number;
+  ahead: number;
+  /** Newest first, capped at 100; `behind` holds the full count. */
+  newCommits: GraphCommit[];
 }
 
 export interface BlameLine {
@@ -57,6 +234,15 @@ export interface BlameLine {
   content: string;
 }
 
+/** Who last touched a single line (`cmd_blame_line`). */
+export interface BlameLineInfo {
+  commitHash: string;
+  author: string;
+  /** Author time as Unix seconds, like `BlameLine.date`. */
+  date: string;
+  summary: string;
+}
+
 export type DiffLineType = "context" | "add" | "remove";
 
 export interface DiffLine {
@@ -73,10 +259,49 @@ export interface DiffHunk {
   lines: DiffLine[];
 }
 
+export interface TreeNode {
+  name: string;
+  path: string;
+  isDir: boolean;
+  children: TreeNode[];
+}
+
+export interface DirectoryEntry {
+  name: string;
+  path: string;
+  objectType: "tree" | "blob" | "commit";
+  size: number | null;
+}
+
+export interface DiffContext {
+  startLine: number;
+  lines: string[];
+  totalLines: number;
+}
+
 export interface DiffFile {
   path: string;
   status: string;
   hunks: DiffHunk[];
+  /** Hunks omitted from a long commit diff; fetch the file with `getCommitFileDiffParsed`. */
+  truncated?: boolean;
+}
+
+/** Payload of a `diff-chunk` event from `getDiffStream`. */
+export interface DiffChunkEvent {
+  streamId: string;
+  filePath: string;
+  index: number;
+  hunk: DiffHunk;
+}
+
+/** Payload of the `diff-done` event; no chunks are sent when `tooLarge`. */
+export interface DiffDoneEvent {
+  streamId: string;
+  filePath: string;
+  hunkCount: number;
+  tooLarge: boolean;
+  size: number;
 }
 
 export interface CommitDiff {